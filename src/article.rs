@@ -1,20 +1,34 @@
 use std::borrow::{Borrow, Cow};
+use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::{Context, Result};
+use bytes::Bytes;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use fnv::FnvHasher;
+#[cfg(not(target_arch = "wasm32"))]
+use futures::stream::{self, Stream, StreamExt};
 #[cfg(not(target_arch = "wasm32"))]
-use reqwest::header::{HeaderMap, USER_AGENT};
-use reqwest::{Client, IntoUrl, Url};
+use reqwest::header::{HeaderMap, ACCEPT_LANGUAGE, USER_AGENT};
+use reqwest::{Client, IntoUrl, RequestBuilder, Url};
 use select::document::Document;
 #[cfg(feature = "serde0")]
 use serde::{Deserialize, Serialize};
 
-use crate::date::ArticleDate;
+use crate::date::{ArticleDate, Date};
+use crate::embed::{OEmbed, SocialEmbed};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::extrablatt::{build_client, read_body, validate_header_value, Config};
 #[cfg(not(target_arch = "wasm32"))]
-use crate::extrablatt::Config;
+use crate::extrablatt::{fetch_configured_article, Jitter};
 use crate::extract::{DefaultExtractor, Extractor};
+use crate::image::Image;
 use crate::language::Language;
+use crate::text::ArticleTextNodeExtractor;
+use crate::video::Video;
 
 /// Extension for documents that are considered valid sources for articles.
 pub const ALLOWED_FILE_EXT: [&str; 12] = [
@@ -61,11 +75,36 @@ pub const BAD_SEGMENTS: [&str; 17] = [
     "admin",
 ];
 
+/// Query parameters that usually hold the id of an article, like WordPress'
+/// `?p=12345`.
+pub const ARTICLE_ID_QUERY_PARAMS: [&str; 10] = [
+    "p",
+    "id",
+    "articleid",
+    "article_id",
+    "storyid",
+    "story_id",
+    "newsid",
+    "postid",
+    "post_id",
+    "contentid",
+];
+
 /// Domain names that are treated as bad sources for articles.
 pub const BAD_DOMAINS: [&str; 4] = ["amazon", "doubleclick", "twitter", "outbrain"];
 
+/// Domain names of analytics services that embed tracking pixels.
+pub const TRACKING_DOMAINS: [&str; 5] = [
+    "scorecardresearch",
+    "google-analytics",
+    "googletagmanager",
+    "quantserve",
+    "chartbeat",
+];
+
 /// An identified url to an article and it's title.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
 pub struct ArticleUrl {
     /// The url of the article.
     pub url: Url,
@@ -158,7 +197,7 @@ pub struct Article {
 impl Article {
     /// Extract the article directly from the doc using the [`DefaultExtractor`]
     pub fn new<U: IntoUrl, T: AsRef<str>>(url: U, doc: T) -> Result<Article> {
-        Self::with_extractor(url, doc, &DefaultExtractor::default())
+        Self::with_extractor(url, doc, &DefaultExtractor)
     }
 
     pub fn with_extractor<U, T, TExtract>(url: U, doc: T, extractor: &TExtract) -> Result<Article>
@@ -231,6 +270,39 @@ impl Article {
         Self::builder(url)?.get_with_extractor(extractor).await
     }
 
+    /// Fetches the articles of all `urls` concurrently, at most
+    /// [`Config::max_concurrent_requests`] at a time.
+    ///
+    /// The `config` applies as it does to the articles of a crawl, including
+    /// its retries, jitter and body size limit. Articles whose content isn't
+    /// [`Config::is_complete`] fail with
+    /// [`crate::error::ExtrablattError::IncompleteArticle`].
+    ///
+    /// Each result is yielded together with its url as soon as it's done, so
+    /// the order of the `urls` isn't preserved.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn get_all<I: IntoIterator<Item = Url>>(
+        urls: I,
+        config: &Config,
+    ) -> Result<impl Stream<Item = (Url, Result<Article>)>> {
+        let client = build_client(config, HeaderMap::new(), false)?;
+        let max_concurrent_requests = config.max_concurrent_requests.max(1);
+        let jitter = Arc::new(Jitter::new(config.jitter_seed));
+        let config = Arc::new(config.clone());
+        Ok(stream::iter(urls)
+            .map(move |url| {
+                let client = client.clone();
+                let config = Arc::clone(&config);
+                let jitter = Arc::clone(&jitter);
+                async move {
+                    let article =
+                        fetch_configured_article(&client, &config, &jitter, url.clone()).await;
+                    (url, article)
+                }
+            })
+            .buffer_unordered(max_concurrent_requests))
+    }
+
     /// Convenience method for creating a new [`ArticleBuilder`]
     ///
     /// Same as calling [`ArticleBuilder::new`]
@@ -264,8 +336,10 @@ pub struct PureArticle {
 pub struct ArticleBuilder {
     url: Option<Url>,
     timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
     language: Option<Language>,
     browser_user_agent: Option<String>,
+    accept_language: Option<String>,
 }
 
 impl ArticleBuilder {
@@ -275,14 +349,20 @@ impl ArticleBuilder {
         Ok(ArticleBuilder {
             url: Some(url),
             timeout: None,
+            connect_timeout: None,
             language: None,
             browser_user_agent: None,
+            accept_language: None,
         })
     }
 
-    pub fn browser_user_agent<T: ToString>(mut self, browser_user_agent: T) -> Self {
-        self.browser_user_agent = Some(browser_user_agent.to_string());
-        self
+    /// Fails if the `browser_user_agent` isn't a valid header value, like one
+    /// with a newline.
+    pub fn browser_user_agent<T: ToString>(mut self, browser_user_agent: T) -> Result<Self> {
+        let browser_user_agent = browser_user_agent.to_string();
+        validate_header_value("user agent", &browser_user_agent)?;
+        self.browser_user_agent = Some(browser_user_agent);
+        Ok(self)
     }
 
     pub fn timeout(mut self, dur: Duration) -> Self {
@@ -290,15 +370,29 @@ impl ArticleBuilder {
         self
     }
 
+    /// Timeout for only the connect phase of the request.
+    pub fn connect_timeout(mut self, dur: Duration) -> Self {
+        self.connect_timeout = Some(dur);
+        self
+    }
+
     pub fn language(mut self, language: Language) -> Self {
         self.language = Some(language);
         self
     }
 
+    /// The `Accept-Language` header of the request.
+    ///
+    /// Defaults to the identifier of the [`ArticleBuilder::language`], if set.
+    pub fn accept_language<T: ToString>(mut self, accept_language: T) -> Self {
+        self.accept_language = Some(accept_language.to_string());
+        self
+    }
+
     /// Downloads the article and extract it's content using the
     /// [`crate::DefaultExtractor`].
     pub async fn get(self) -> Result<Article> {
-        self.get_with_extractor(&DefaultExtractor::default()).await
+        self.get_with_extractor(&DefaultExtractor).await
     }
 
     /// Downloads the article and extracts it's content using the provided
@@ -320,7 +414,7 @@ impl ArticleBuilder {
                 .timeout
                 .unwrap_or_else(|| Duration::from_secs(Config::DEFAULT_REQUEST_TIMEOUT_SEC));
 
-            let mut headers = HeaderMap::with_capacity(1);
+            let mut headers = HeaderMap::with_capacity(2);
 
             headers.insert(
                 USER_AGENT,
@@ -330,37 +424,58 @@ impl ArticleBuilder {
                     .context("Failed to parse user agent header.")?,
             );
 
-            Client::builder().default_headers(headers).timeout(timeout)
+            let language = self.language.as_ref().map(Language::identifier);
+            if let Some(accept_language) = self.accept_language.as_deref().or(language) {
+                headers.insert(
+                    ACCEPT_LANGUAGE,
+                    accept_language
+                        .parse()
+                        .context("Failed to parse accept language header.")?,
+                );
+            }
+
+            let builder = Client::builder().default_headers(headers).timeout(timeout);
+            if let Some(connect_timeout) = self.connect_timeout {
+                builder.connect_timeout(connect_timeout)
+            } else {
+                builder
+            }
         };
 
-        let resp = builder.build()?.get(url).send().await?;
+        fetch_article(builder.build()?.get(url), extractor, self.language).await
+    }
+}
 
-        if !resp.status().is_success() {
-            // let msg = format!("Unsuccessful request to {:?}", resp.url());
-            // return ExtrablattError::NoHttpSuccessResponse { response: resp
-            // }.context(msg);
-            return Err(anyhow::anyhow!("Unsuccessful request to {:?}", resp.url()));
-        }
+/// Sends the `request` and extracts the [`Article`] of the response with the
+/// `extractor`.
+async fn fetch_article<TExtract: Extractor>(
+    request: RequestBuilder,
+    extractor: &TExtract,
+    language: Option<Language>,
+) -> Result<Article> {
+    let resp = request.send().await?;
 
-        let url = resp.url().to_owned();
-        let doc = Document::from_read(&*resp.bytes().await?)
-            .context(format!("Failed to read {:?} html as document.", url))?;
+    if !resp.status().is_success() {
+        // let msg = format!("Unsuccessful request to {:?}", resp.url());
+        // return ExtrablattError::NoHttpSuccessResponse { response: resp
+        // }.context(msg);
+        return Err(anyhow::anyhow!("Unsuccessful request to {:?}", resp.url()));
+    }
 
-        let content = extractor
-            .article_content(
-                &doc,
-                extractor.base_url(&doc).as_ref(),
-                self.language.clone(),
-            )
-            .into_owned();
+    let url = resp.url().to_owned();
+    let doc = Document::from_read(&*resp.bytes().await?)
+        .context(format!("Failed to read {:?} html as document.", url))?;
 
-        Ok(Article {
-            url,
-            doc,
-            content,
-            language: self.language.unwrap_or_default(),
-        })
-    }
+    let content = extractor
+        .article_content(&doc, extractor.base_url(&doc).as_ref(), language.clone())
+        .into_owned();
+
+    Ok(Article {
+        url,
+        doc,
+        content,
+        language: language.unwrap_or_default(),
+    })
 }
 
 /// Bundles all the content found for an article.
@@ -368,17 +483,304 @@ impl ArticleBuilder {
 #[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
 pub struct ArticleContent<'a> {
     pub authors: Vec<Cow<'a, str>>,
+    /// Structured details about the authors, if provided by the page.
+    pub author_details: Vec<Author>,
     pub title: Option<Cow<'a, str>>,
     pub publishing_date: Option<ArticleDate>,
     pub keywords: Vec<Cow<'a, str>>,
     pub description: Option<Cow<'a, str>>,
     pub text: Option<Cow<'a, str>>,
+    /// Where the `text` was taken from.
+    pub text_source: Option<TextSource>,
     pub language: Option<Language>,
     pub thumbnail: Option<Url>,
     pub top_image: Option<Url>,
+    /// The [`ArticleContent::top_image`] with its declared dimensions.
+    pub top_image_details: Option<Image>,
+    /// The first substantial image inside the article's text.
+    pub lead_image: Option<Image>,
     pub references: Vec<Url>,
-    pub images: Vec<Url>,
-    pub videos: Vec<Url>,
+    /// Quotes within the article's text.
+    pub quotes: Vec<String>,
+    pub images: Vec<Image>,
+    pub videos: Vec<Video>,
+    /// Social media posts embedded in the article.
+    pub social_embeds: Vec<SocialEmbed>,
+    /// The original source, if the article is republished content.
+    pub syndication: Option<Syndication>,
+    /// Whether the article can be read for free.
+    pub content_tier: ContentTier,
+    /// The section of the site the article belongs to, like `Politics`.
+    pub section: Option<String>,
+    /// Where the `section` was taken from.
+    pub section_source: Option<SectionSource>,
+    /// All sections the article is listed under, if declared.
+    pub sections: Vec<String>,
+    /// Number of internal, outbound and affiliate links in the article's text.
+    pub link_stats: LinkStats,
+    /// Recipe or how-to content declared by the page's JSON-LD.
+    pub structured: Option<StructuredContent>,
+    /// Comments rendered on the page, see [`crate::Config::extract_comments`].
+    pub comments: Vec<Comment>,
+    /// The items of a listicle, see [`Extractor::listicle_items`].
+    pub list_items: Vec<ListItem>,
+    /// The entries of the article's table of contents and their anchors, see
+    /// [`Extractor::table_of_contents`].
+    pub toc: Vec<(String, Option<String>)>,
+    /// The product offered on the page, if it's an e-commerce page, see
+    /// [`Extractor::product`].
+    pub product: Option<Product>,
+    /// Where the article is located, see [`Extractor::geo`].
+    pub geo: Option<GeoLocation>,
+    /// Whether the language declared by the article's url or html differs from
+    /// the language of its text, see
+    /// [`Extractor::section_language_consistency`].
+    #[cfg_attr(feature = "serde0", serde(default))]
+    pub language_mismatch: bool,
+    /// Whether the page looks like a section or index page rather than an
+    /// article, see [`Extractor::looks_like_index`].
+    #[cfg_attr(feature = "serde0", serde(default))]
+    pub looks_like_index: bool,
+    /// Whether the text was cut short, see
+    /// [`crate::Config::truncate_text_to`].
+    #[cfg_attr(feature = "serde0", serde(default))]
+    pub text_truncated: bool,
+    /// The roles credited at the end of the text, like `Reporting by`, see
+    /// [`Extractor::attributions`].
+    #[cfg_attr(feature = "serde0", serde(default))]
+    pub attributions: Vec<Attribution>,
+}
+
+/// A person credited for a role in an article's attribution line, like
+/// `Reporting by Jane Doe`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct Attribution {
+    /// The role as written, like `Reporting` or `Additional reporting`.
+    pub role: String,
+    pub name: String,
+}
+
+impl Attribution {
+    /// Whether the role is writing or reporting, rather than editing.
+    pub fn is_reporter(&self) -> bool {
+        let role = self.role.to_lowercase();
+        role.contains("reporting") || role.contains("writing")
+    }
+}
+
+/// A reader's comment on an article.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct Comment {
+    pub author: Option<String>,
+    pub text: String,
+    /// When the comment was posted.
+    pub date: Option<NaiveDateTime>,
+}
+
+/// An item of a listicle, like "3. The best pizza in town".
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct ListItem {
+    pub heading: String,
+    /// The paragraphs below the heading, separated by blank lines.
+    pub body: String,
+    pub image: Option<Image>,
+}
+
+/// Counts the links in an article's text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct LinkStats {
+    /// Links to the same site.
+    pub internal: usize,
+    /// Links to other sites.
+    pub outbound: usize,
+    /// Links that are affiliate links, whether internal or outbound.
+    pub affiliate: usize,
+}
+
+impl LinkStats {
+    /// Number of all links.
+    pub fn total(&self) -> usize {
+        self.internal + self.outbound
+    }
+
+    /// The share of affiliate links among all links, `0.0` if there are no
+    /// links.
+    pub fn affiliate_ratio(&self) -> f64 {
+        if self.total() == 0 {
+            0.0
+        } else {
+            self.affiliate as f64 / self.total() as f64
+        }
+    }
+}
+
+/// Details about an author of an article.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct Author {
+    /// The author's name.
+    pub name: String,
+    /// Link to the author's profile.
+    pub url: Option<Url>,
+    /// Other profiles of the author, like social media accounts.
+    pub same_as: Vec<Url>,
+    /// The author's job title.
+    pub job_title: Option<String>,
+}
+
+impl Author {
+    pub fn new<T: ToString>(name: T) -> Self {
+        Self {
+            name: name.to_string(),
+            url: None,
+            same_as: Vec::new(),
+            job_title: None,
+        }
+    }
+}
+
+/// The original source of a syndicated article, like a wire story.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct Syndication {
+    /// Url of the original article.
+    pub source_url: Option<Url>,
+    /// Name of the organization that originally published the article.
+    pub source_org: Option<String>,
+}
+
+/// The changes between two versions of an article, see
+/// [`ArticleContent::diff`].
+///
+/// Paragraphs are the non empty lines of the text.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct ContentDiff {
+    pub title_changed: bool,
+    /// Whether the paragraphs of the text changed, ignoring whitespace.
+    pub text_changed: bool,
+    pub authors_changed: bool,
+    pub date_changed: bool,
+    pub images_changed: bool,
+    /// Paragraphs only in the newer version, in order.
+    pub added_paragraphs: Vec<String>,
+    /// Paragraphs only in the older version, in order.
+    pub removed_paragraphs: Vec<String>,
+}
+
+impl ContentDiff {
+    /// Whether anything changed.
+    pub fn has_changes(&self) -> bool {
+        *self != Self::default()
+    }
+}
+
+/// How the canonical and AMP links of a page relate to the url it was
+/// fetched from, see [`Extractor::link_consistency`].
+///
+/// Pages that aren't their own canonical are likely duplicates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct LinkConsistency {
+    /// Whether the canonical link is missing or points to the fetched url.
+    pub self_canonical: bool,
+    /// Whether the canonical link is missing or is on the fetched url's host.
+    pub canonical_host_matches: bool,
+    /// Whether the page links to an AMP version.
+    pub has_amp: bool,
+}
+
+/// Structured content of a page that is not a plain article, taken from its
+/// JSON-LD.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub enum StructuredContent {
+    /// A JSON-LD `Recipe`.
+    Recipe {
+        name: Option<String>,
+        ingredients: Vec<String>,
+        steps: Vec<String>,
+    },
+    /// A JSON-LD `HowTo`.
+    HowTo {
+        name: Option<String>,
+        steps: Vec<String>,
+    },
+}
+
+/// The product offered on an e-commerce page, like the deals pages of news
+/// sites.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct Product {
+    pub name: Option<String>,
+    /// The price as declared, like `19.99`.
+    pub price: Option<String>,
+    /// The currency of the price, like `USD`.
+    pub currency: Option<String>,
+    /// The availability without the `schema.org` prefix, like `InStock`.
+    pub availability: Option<String>,
+}
+
+/// The location an article is about.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct GeoLocation {
+    /// The latitude in degrees.
+    pub lat: Option<f64>,
+    /// The longitude in degrees.
+    pub lon: Option<f64>,
+    /// The name of the place, like `Berlin`.
+    pub name: Option<String>,
+}
+
+/// How an article is accessible to readers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub enum ContentTier {
+    /// Freely accessible.
+    Free,
+    /// Free for a limited number of articles.
+    Metered,
+    /// Behind a paywall, possibly with a free teaser.
+    Locked,
+    /// The page doesn't say.
+    Unknown,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for ContentTier {
+    fn default() -> Self {
+        ContentTier::Unknown
+    }
+}
+
+/// The origin of an article's text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub enum TextSource {
+    /// Extracted from the article's body.
+    Body,
+    /// The meta description, used because no body text could be extracted.
+    Description,
+}
+
+/// The origin of an article's section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub enum SectionSource {
+    /// The `articleSection` of the JSON-LD.
+    JsonLd,
+    /// The `article:section` meta tag.
+    Meta,
+    /// The breadcrumb navigation.
+    Breadcrumbs,
+    /// The first segment of the article's url path.
+    Url,
 }
 
 impl<'a> ArticleContent<'a> {
@@ -387,6 +789,303 @@ impl<'a> ArticleContent<'a> {
         ArticleContentBuilder::default()
     }
 
+    /// The urls of all the [`ArticleContent::videos`].
+    pub fn video_urls(&self) -> impl Iterator<Item = &Url> {
+        self.videos.iter().map(|video| &video.url)
+    }
+
+    /// What changed in the `other`, newer version of the article, like an
+    /// updated breaking news story.
+    pub fn diff(&self, other: &ArticleContent) -> ContentDiff {
+        let paragraphs = |content: &ArticleContent| -> Vec<String> {
+            content
+                .text
+                .as_deref()
+                .unwrap_or_default()
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect()
+        };
+        let (old, new) = (paragraphs(self), paragraphs(other));
+        ContentDiff {
+            title_changed: self.title != other.title,
+            text_changed: old != new,
+            authors_changed: self.authors != other.authors,
+            date_changed: self.publishing_date != other.publishing_date,
+            images_changed: self.images != other.images,
+            added_paragraphs: new.iter().filter(|p| !old.contains(p)).cloned().collect(),
+            removed_paragraphs: old.iter().filter(|p| !new.contains(p)).cloned().collect(),
+        }
+    }
+
+    /// How long ago the article was published, relative to `now`.
+    ///
+    /// Dates without a time are assumed to be published at midnight UTC and
+    /// articles published after `now` have an age of zero.
+    pub fn age(&self, now: DateTime<Utc>) -> Option<Duration> {
+        let published = match &self.publishing_date.as_ref()?.published {
+            Date::Date(date) => date.and_hms_opt(0, 0, 0)?,
+            Date::DateTime(date) => *date,
+        };
+        Some(
+            now.naive_utc()
+                .signed_duration_since(published)
+                .to_std()
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Downloads the [`ArticleContent::top_image`] using the `client`.
+    pub async fn download_top_image(&self, client: &Client) -> Result<Bytes> {
+        let url = self
+            .top_image
+            .as_ref()
+            .context("The article has no top image.")?;
+        download_bytes(client, url).await
+    }
+
+    /// Downloads the [`ArticleContent::thumbnail`] using the `client`.
+    pub async fn download_thumbnail(&self, client: &Client) -> Result<Bytes> {
+        let url = self
+            .thumbnail
+            .as_ref()
+            .context("The article has no thumbnail.")?;
+        download_bytes(client, url).await
+    }
+
+    /// Downloads the [`ArticleContent::images`], the lead and top image and
+    /// the thumbnails of the [`ArticleContent::videos`] using the `client`,
+    /// at most `max_concurrent_requests` at a time, and stores their bytes
+    /// alongside their urls.
+    ///
+    /// A [`ArticleContent::top_image`] without details gets them, so that its
+    /// bytes can be stored. Failed downloads and files larger than
+    /// `max_body_bytes` are skipped. Returns the number of downloaded files.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn download_media(
+        &mut self,
+        client: &Client,
+        max_concurrent_requests: usize,
+        max_body_bytes: Option<usize>,
+    ) -> usize {
+        let media = download_all_bytes(
+            client,
+            self.media_urls(),
+            max_concurrent_requests,
+            max_body_bytes,
+        )
+        .await;
+        self.set_media(&media);
+        media.len()
+    }
+
+    /// The urls of all images and video thumbnails, see
+    /// [`ArticleContent::download_media`].
+    pub(crate) fn media_urls(&self) -> Vec<Url> {
+        let mut urls: Vec<Url> = Vec::new();
+        let images = self
+            .images
+            .iter()
+            .chain(self.lead_image.iter())
+            .map(|image| &image.url)
+            .chain(self.top_image.iter());
+        let thumbnails = self
+            .videos
+            .iter()
+            .filter_map(|video| video.thumbnail.as_ref());
+        for url in images.chain(thumbnails) {
+            if !urls.contains(url) {
+                urls.push(url.clone());
+            }
+        }
+        urls
+    }
+
+    /// Stores the bytes of the downloaded `media` alongside the urls of the
+    /// images and video thumbnails.
+    pub(crate) fn set_media(&mut self, media: &HashMap<Url, Bytes>) {
+        if let (Some(url), None) = (&self.top_image, &self.top_image_details) {
+            self.top_image_details = Some(Image::new(url.clone()));
+        }
+        for image in self
+            .images
+            .iter_mut()
+            .chain(self.lead_image.iter_mut())
+            .chain(self.top_image_details.iter_mut())
+        {
+            image.bytes = media.get(&image.url).cloned();
+        }
+        for video in &mut self.videos {
+            video.thumbnail_bytes = video
+                .thumbnail
+                .as_ref()
+                .and_then(|url| media.get(url).cloned());
+        }
+    }
+
+    /// Fetches the oEmbed `endpoints`, like those of
+    /// [`Extractor::oembed_endpoints`], using the `client` and adds the
+    /// embedded videos and social media posts to the
+    /// [`ArticleContent::videos`] and [`ArticleContent::social_embeds`].
+    ///
+    /// Endpoints that fail or don't respond with valid JSON are skipped.
+    pub async fn resolve_oembeds(&mut self, client: &Client, endpoints: &[Url]) -> Vec<OEmbed> {
+        let mut resolved = Vec::new();
+        for endpoint in endpoints {
+            let oembed = match download_bytes(client, endpoint).await {
+                Ok(json) => OEmbed::from_json(endpoint, &json),
+                Err(_) => None,
+            };
+            if let Some(oembed) = oembed {
+                if let Some(video) = oembed.video() {
+                    if self.videos.iter().all(|v| v.url != video.url) {
+                        self.videos.push(video);
+                    }
+                } else if let Some(embed) = oembed.social_embed() {
+                    if self.social_embeds.iter().all(|e| e.url != embed.url) {
+                        self.social_embeds.push(embed);
+                    }
+                }
+                resolved.push(oembed);
+            }
+        }
+        resolved
+    }
+
+    /// A hash over the title and text, lowercased and with collapsed
+    /// whitespace, to detect identical articles with different urls.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = FnvHasher::default();
+        for txt in [&self.title, &self.text].iter() {
+            for word in txt.as_deref().unwrap_or_default().split_whitespace() {
+                word.to_lowercase().hash(&mut hasher);
+            }
+            // separates the title from the text
+            0u8.hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// Whether there is a text that isn't only whitespace.
+    pub fn has_text(&self) -> bool {
+        self.text
+            .as_deref()
+            .map(|text| !text.trim().is_empty())
+            .unwrap_or_default()
+    }
+
+    /// Whether there is a title that isn't only whitespace.
+    pub fn has_title(&self) -> bool {
+        self.title
+            .as_deref()
+            .map(|title| !title.trim().is_empty())
+            .unwrap_or_default()
+    }
+
+    /// Whether nothing usable was extracted: no title, no text and no images.
+    pub fn is_empty(&self) -> bool {
+        !self.has_title() && !self.has_text() && self.images.is_empty()
+    }
+
+    /// All [`ArticleContent::images`] without an alternative text.
+    pub fn images_missing_alt(&self) -> Vec<&Image> {
+        self.images
+            .iter()
+            .filter(|image| image.is_missing_alt())
+            .collect()
+    }
+
+    /// Counts how often each lowercased word occurs in the text.
+    ///
+    /// Stopwords of the `lang` are skipped, unless `include_stopwords` is set.
+    pub fn word_frequencies(
+        &self,
+        lang: Language,
+        include_stopwords: bool,
+    ) -> HashMap<String, usize> {
+        #[cfg(feature = "stopwords")]
+        let stopwords = if include_stopwords {
+            None
+        } else {
+            lang.stopwords()
+        };
+        #[cfg(not(feature = "stopwords"))]
+        let _ = (lang, include_stopwords);
+
+        let mut frequencies = HashMap::new();
+        if let Some(text) = &self.text {
+            for word in ArticleTextNodeExtractor::words(text) {
+                let word = word.to_lowercase();
+                #[cfg(feature = "stopwords")]
+                {
+                    if let Some(stopwords) = stopwords {
+                        if stopwords.contains(&word.as_str()) {
+                            continue;
+                        }
+                    }
+                }
+                *frequencies.entry(word).or_insert(0) += 1;
+            }
+        }
+        frequencies
+    }
+
+    /// Renders the content as markdown.
+    ///
+    /// The title becomes the heading, followed by the authors and publishing
+    /// date, the top image, the paragraphs of the text and a list of all
+    /// references.
+    pub fn to_markdown(&self) -> String {
+        let mut blocks = Vec::new();
+        if let Some(title) = &self.title {
+            blocks.push(format!("# {}", title.trim()));
+        }
+
+        let mut meta = Vec::new();
+        if !self.authors.is_empty() {
+            meta.push(format!("*By {}*", self.authors.join(", ")));
+        }
+        if let Some(date) = &self.publishing_date {
+            let published = match &date.published {
+                Date::Date(date) => date.to_string(),
+                Date::DateTime(date) => date.to_string(),
+            };
+            meta.push(format!("*Published {}*", published));
+        }
+        if !meta.is_empty() {
+            blocks.push(meta.join("  \n"));
+        }
+
+        if let Some(img) = &self.top_image {
+            blocks.push(format!("![]({})", img));
+        }
+
+        if let Some(text) = &self.text {
+            blocks.extend(
+                text.lines()
+                    .map(str::trim)
+                    .filter(|p| !p.is_empty())
+                    .map(str::to_string),
+            );
+        }
+
+        if !self.references.is_empty() {
+            blocks.push(
+                self.references
+                    .iter()
+                    .map(|url| format!("- <{}>", url))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            );
+        }
+
+        let mut markdown = blocks.join("\n\n");
+        markdown.push('\n');
+        markdown
+    }
+
     /// Transfers ownership of the content directly to this `ArticleContent`.
     pub fn into_owned(self) -> ArticleContent<'static> {
         ArticleContent {
@@ -396,6 +1095,7 @@ impl<'a> ArticleContent<'a> {
                 .map(Cow::into_owned)
                 .map(Cow::Owned)
                 .collect(),
+            author_details: self.author_details,
             title: self.title.map(Cow::into_owned).map(Cow::Owned),
             publishing_date: self.publishing_date,
             keywords: self
@@ -406,12 +1106,33 @@ impl<'a> ArticleContent<'a> {
                 .collect(),
             description: self.description.map(Cow::into_owned).map(Cow::Owned),
             text: self.text.map(Cow::into_owned).map(Cow::Owned),
+            text_source: self.text_source,
             language: self.language,
             thumbnail: self.thumbnail,
             top_image: self.top_image,
+            top_image_details: self.top_image_details,
+            lead_image: self.lead_image,
             references: self.references,
+            quotes: self.quotes,
             images: self.images,
             videos: self.videos,
+            social_embeds: self.social_embeds,
+            syndication: self.syndication,
+            content_tier: self.content_tier,
+            section: self.section,
+            section_source: self.section_source,
+            sections: self.sections,
+            link_stats: self.link_stats,
+            structured: self.structured,
+            comments: self.comments,
+            list_items: self.list_items,
+            toc: self.toc,
+            product: self.product,
+            geo: self.geo,
+            language_mismatch: self.language_mismatch,
+            looks_like_index: self.looks_like_index,
+            text_truncated: self.text_truncated,
+            attributions: self.attributions,
         }
     }
 }
@@ -419,17 +1140,39 @@ impl<'a> ArticleContent<'a> {
 #[derive(Debug, Default)]
 pub struct ArticleContentBuilder<'a> {
     pub authors: Option<Vec<Cow<'a, str>>>,
+    pub author_details: Option<Vec<Author>>,
     pub title: Option<Cow<'a, str>>,
     pub publishing_date: Option<ArticleDate>,
     pub keywords: Option<Vec<Cow<'a, str>>>,
     pub description: Option<Cow<'a, str>>,
     pub text: Option<Cow<'a, str>>,
+    pub text_source: Option<TextSource>,
     pub language: Option<Language>,
     pub thumbnail: Option<Url>,
     pub top_image: Option<Url>,
+    pub top_image_details: Option<Image>,
+    pub lead_image: Option<Image>,
     pub references: Option<Vec<Url>>,
-    pub images: Option<Vec<Url>>,
-    pub videos: Option<Vec<Url>>,
+    pub quotes: Option<Vec<String>>,
+    pub images: Option<Vec<Image>>,
+    pub videos: Option<Vec<Video>>,
+    pub social_embeds: Option<Vec<SocialEmbed>>,
+    pub syndication: Option<Syndication>,
+    pub content_tier: Option<ContentTier>,
+    pub section: Option<String>,
+    pub section_source: Option<SectionSource>,
+    pub sections: Option<Vec<String>>,
+    pub link_stats: Option<LinkStats>,
+    pub structured: Option<StructuredContent>,
+    pub comments: Option<Vec<Comment>>,
+    pub list_items: Option<Vec<ListItem>>,
+    pub toc: Option<Vec<(String, Option<String>)>>,
+    pub product: Option<Product>,
+    pub geo: Option<GeoLocation>,
+    pub language_mismatch: Option<bool>,
+    pub looks_like_index: Option<bool>,
+    pub text_truncated: Option<bool>,
+    pub attributions: Option<Vec<Attribution>>,
 }
 
 impl<'a> ArticleContentBuilder<'a> {
@@ -438,6 +1181,11 @@ impl<'a> ArticleContentBuilder<'a> {
         self
     }
 
+    pub fn author_details(mut self, author_details: Vec<Author>) -> Self {
+        self.author_details = Some(author_details);
+        self
+    }
+
     pub fn title(mut self, title: Cow<'a, str>) -> Self {
         self.title = Some(title);
         self
@@ -463,6 +1211,11 @@ impl<'a> ArticleContentBuilder<'a> {
         self
     }
 
+    pub fn text_source(mut self, text_source: TextSource) -> Self {
+        self.text_source = Some(text_source);
+        self
+    }
+
     pub fn language(mut self, language: Language) -> Self {
         self.language = Some(language);
         self
@@ -478,35 +1231,462 @@ impl<'a> ArticleContentBuilder<'a> {
         self
     }
 
+    pub fn top_image_details(mut self, top_image_details: Image) -> Self {
+        self.top_image_details = Some(top_image_details);
+        self
+    }
+
     pub fn references(mut self, references: Vec<Url>) -> Self {
         self.references = Some(references);
         self
     }
 
-    pub fn images(mut self, images: Vec<Url>) -> Self {
+    pub fn quotes(mut self, quotes: Vec<String>) -> Self {
+        self.quotes = Some(quotes);
+        self
+    }
+
+    pub fn lead_image(mut self, lead_image: Image) -> Self {
+        self.lead_image = Some(lead_image);
+        self
+    }
+
+    pub fn images(mut self, images: Vec<Image>) -> Self {
         self.images = Some(images);
         self
     }
 
-    pub fn videos(mut self, videos: Vec<Url>) -> Self {
+    pub fn videos(mut self, videos: Vec<Video>) -> Self {
         self.videos = Some(videos);
         self
     }
 
+    pub fn social_embeds(mut self, social_embeds: Vec<SocialEmbed>) -> Self {
+        self.social_embeds = Some(social_embeds);
+        self
+    }
+
+    pub fn syndication(mut self, syndication: Syndication) -> Self {
+        self.syndication = Some(syndication);
+        self
+    }
+
+    pub fn content_tier(mut self, content_tier: ContentTier) -> Self {
+        self.content_tier = Some(content_tier);
+        self
+    }
+
+    pub fn section<T: ToString>(mut self, section: T, source: SectionSource) -> Self {
+        self.section = Some(section.to_string());
+        self.section_source = Some(source);
+        self
+    }
+
+    pub fn sections(mut self, sections: Vec<String>) -> Self {
+        self.sections = Some(sections);
+        self
+    }
+
+    pub fn link_stats(mut self, link_stats: LinkStats) -> Self {
+        self.link_stats = Some(link_stats);
+        self
+    }
+
+    pub fn structured(mut self, structured: StructuredContent) -> Self {
+        self.structured = Some(structured);
+        self
+    }
+
+    pub fn comments(mut self, comments: Vec<Comment>) -> Self {
+        self.comments = Some(comments);
+        self
+    }
+
+    pub fn list_items(mut self, list_items: Vec<ListItem>) -> Self {
+        self.list_items = Some(list_items);
+        self
+    }
+
+    pub fn toc(mut self, toc: Vec<(String, Option<String>)>) -> Self {
+        self.toc = Some(toc);
+        self
+    }
+
+    pub fn product(mut self, product: Product) -> Self {
+        self.product = Some(product);
+        self
+    }
+
+    pub fn geo(mut self, geo: GeoLocation) -> Self {
+        self.geo = Some(geo);
+        self
+    }
+
+    pub fn language_mismatch(mut self, language_mismatch: bool) -> Self {
+        self.language_mismatch = Some(language_mismatch);
+        self
+    }
+
+    pub fn looks_like_index(mut self, looks_like_index: bool) -> Self {
+        self.looks_like_index = Some(looks_like_index);
+        self
+    }
+
+    pub fn text_truncated(mut self, text_truncated: bool) -> Self {
+        self.text_truncated = Some(text_truncated);
+        self
+    }
+
+    pub fn attributions(mut self, attributions: Vec<Attribution>) -> Self {
+        self.attributions = Some(attributions);
+        self
+    }
+
     pub fn build(self) -> ArticleContent<'a> {
         ArticleContent {
             authors: self.authors.unwrap_or_default(),
+            author_details: self.author_details.unwrap_or_default(),
             title: self.title,
             publishing_date: self.publishing_date,
             keywords: self.keywords.unwrap_or_default(),
             description: self.description,
             text: self.text,
+            text_source: self.text_source,
             language: self.language,
             thumbnail: self.thumbnail,
             top_image: self.top_image,
+            top_image_details: self.top_image_details,
+            lead_image: self.lead_image,
             references: self.references.unwrap_or_default(),
+            quotes: self.quotes.unwrap_or_default(),
             images: self.images.unwrap_or_default(),
             videos: self.videos.unwrap_or_default(),
+            social_embeds: self.social_embeds.unwrap_or_default(),
+            syndication: self.syndication,
+            content_tier: self.content_tier.unwrap_or_default(),
+            section: self.section,
+            section_source: self.section_source,
+            sections: self.sections.unwrap_or_default(),
+            link_stats: self.link_stats.unwrap_or_default(),
+            structured: self.structured,
+            comments: self.comments.unwrap_or_default(),
+            list_items: self.list_items.unwrap_or_default(),
+            toc: self.toc.unwrap_or_default(),
+            product: self.product,
+            geo: self.geo,
+            language_mismatch: self.language_mismatch.unwrap_or_default(),
+            looks_like_index: self.looks_like_index.unwrap_or_default(),
+            text_truncated: self.text_truncated.unwrap_or_default(),
+            attributions: self.attributions.unwrap_or_default(),
         }
     }
 }
+
+/// Downloads the body of the `url`, failing for non success responses.
+async fn download_bytes(client: &Client, url: &Url) -> Result<Bytes> {
+    let resp = client.get(url.clone()).send().await?;
+    if !resp.status().is_success() {
+        return Err(anyhow::anyhow!("Unsuccessful request to {:?}", resp.url()));
+    }
+    Ok(resp.bytes().await?)
+}
+
+/// Downloads the `urls` using the `client`, at most `max_concurrent_requests`
+/// at a time, and skips the failed downloads and those larger than `limit`.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn download_all_bytes(
+    client: &Client,
+    urls: Vec<Url>,
+    max_concurrent_requests: usize,
+    limit: Option<usize>,
+) -> HashMap<Url, Bytes> {
+    use futures::StreamExt as _;
+
+    futures::stream::iter(urls)
+        .map(|url| async move {
+            let resp = client.get(url.clone()).send().await.ok()?;
+            if !resp.status().is_success() {
+                return None;
+            }
+            let bytes = read_body(resp, limit).await.ok()?;
+            Some((url, bytes))
+        })
+        .buffer_unordered(max_concurrent_requests.max(1))
+        .filter_map(|media| async move { media })
+        .collect()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone};
+
+    #[test]
+    fn article_age() {
+        let now = Utc.with_ymd_and_hms(2021, 3, 12, 12, 0, 0).unwrap();
+        let published = NaiveDate::from_ymd_opt(2021, 3, 10)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        let content = ArticleContent::builder()
+            .publishing_date(ArticleDate {
+                published: Date::DateTime(published),
+                last_updated: None,
+                published_from_modified: false,
+            })
+            .build();
+        assert_eq!(content.age(now), Some(Duration::from_secs(2 * 24 * 3600)));
+
+        let content = ArticleContent::builder()
+            .publishing_date(ArticleDate {
+                published: Date::Date(published.date()),
+                last_updated: None,
+                published_from_modified: false,
+            })
+            .build();
+        assert_eq!(content.age(now), Some(Duration::from_secs(60 * 3600)));
+
+        assert_eq!(ArticleContent::builder().build().age(now), None);
+    }
+
+    #[test]
+    #[cfg(feature = "stopwords")]
+    fn word_frequencies() {
+        let content = ArticleContent::builder()
+            .text("The cat saw the dog. The dog saw the cat, and the cat ran.".into())
+            .build();
+
+        let frequencies = content.word_frequencies(Language::English, true);
+        assert_eq!(frequencies["the"], 5);
+        assert_eq!(frequencies["cat"], 3);
+
+        let frequencies = content.word_frequencies(Language::English, false);
+        assert!(!frequencies.contains_key("the"));
+        assert!(!frequencies.contains_key("and"));
+        assert_eq!(frequencies["cat"], 3);
+        assert_eq!(frequencies["dog"], 2);
+        assert!(!frequencies.contains_key("saw"));
+    }
+
+    #[test]
+    fn empty_content() {
+        let content = ArticleContent::builder()
+            .title("Title".into())
+            .text(" \n\t ".into())
+            .build();
+        assert!(!content.has_text());
+        assert!(content.has_title());
+        assert!(!content.is_empty());
+
+        let empty = ArticleContent::builder().title("  ".into()).build();
+        assert!(!empty.has_title());
+        assert!(empty.is_empty());
+        assert!(ArticleContent::builder().build().is_empty());
+
+        let images = ArticleContent::builder()
+            .images(vec![Image::new(
+                Url::parse("https://extrablatt.com/a.png").unwrap(),
+            )])
+            .build();
+        assert!(!images.is_empty());
+    }
+
+    #[test]
+    fn markdown() {
+        let content = ArticleContent::builder()
+            .title("Some Title".into())
+            .authors(vec!["Jane Doe".into()])
+            .text("First paragraph.\n\nSecond paragraph.".into())
+            .references(vec![Url::parse("https://extrablatt.com/source").unwrap()])
+            .build();
+
+        assert_eq!(
+            content.to_markdown(),
+            "# Some Title\n\n*By Jane Doe*\n\nFirst paragraph.\n\nSecond paragraph.\n\n- <https://extrablatt.com/source>\n"
+        );
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn download_images() {
+        use crate::testutil::{MockResponse, MockServer};
+
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/top.png" => MockResponse::ok(&b"\x89PNG top"[..]),
+            _ => MockResponse::status(404),
+        });
+        let client = Client::new();
+        let content = ArticleContent::builder()
+            .top_image(server.url("/top.png"))
+            .thumbnail(server.url("/missing.png"))
+            .build();
+
+        let bytes = content.download_top_image(&client).await.unwrap();
+        assert_eq!(&bytes[..], b"\x89PNG top");
+        assert!(content.download_thumbnail(&client).await.is_err());
+
+        let err = ArticleContent::builder()
+            .build()
+            .download_top_image(&client)
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "The article has no top image.");
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn resolve_oembeds() {
+        use crate::embed::SocialProvider;
+        use crate::testutil::{MockResponse, MockServer};
+        use crate::video::VideoProvider;
+
+        let server = MockServer::start(|req| {
+            if req.path.starts_with("/video") {
+                MockResponse::ok(
+                    r#"{"type": "video", "version": "1.0", "title": "Council meeting",
+                    "author_name": "Extrablatt", "thumbnail_url": "https://i.ytimg.com/vi/abc/hq.jpg",
+                    "html": "<iframe src=\"https://www.youtube.com/embed/abc\"></iframe>"}"#,
+                )
+            } else if req.path.starts_with("/tweet") {
+                MockResponse::ok(
+                    r#"{"type": "rich", "author_name": "Extrablatt", "html": "<blockquote></blockquote>"}"#,
+                )
+            } else if req.path.starts_with("/broken") {
+                MockResponse::ok("not json")
+            } else {
+                MockResponse::status(404)
+            }
+        });
+        let endpoint = |path: &str, url: &str| {
+            let mut endpoint = server.url(path);
+            endpoint.query_pairs_mut().append_pair("url", url);
+            endpoint
+        };
+        let endpoints = vec![
+            endpoint("/video", "https://www.youtube.com/watch?v=abc"),
+            endpoint("/tweet", "https://twitter.com/extrablatt/status/1234567890"),
+            endpoint("/broken", "https://extrablatt.com/"),
+            endpoint("/missing", "https://extrablatt.com/"),
+        ];
+
+        let mut content = ArticleContent::builder().build();
+        let resolved = content.resolve_oembeds(&Client::new(), &endpoints).await;
+        assert_eq!(resolved.len(), 2);
+        assert_eq!(resolved[0].title.as_deref(), Some("Council meeting"));
+        assert_eq!(resolved[1].author_name.as_deref(), Some("Extrablatt"));
+
+        assert_eq!(content.videos.len(), 1);
+        assert_eq!(
+            content.videos[0].url.as_str(),
+            "https://www.youtube.com/watch?v=abc"
+        );
+        assert_eq!(content.videos[0].provider, Some(VideoProvider::Youtube));
+        assert_eq!(
+            content.videos[0].thumbnail.as_ref().map(Url::as_str),
+            Some("https://i.ytimg.com/vi/abc/hq.jpg")
+        );
+        assert_eq!(content.social_embeds.len(), 1);
+        assert_eq!(content.social_embeds[0].provider, SocialProvider::Twitter);
+    }
+
+    #[tokio::test]
+    async fn get_all() {
+        use crate::error::ExtrablattError;
+        use crate::testutil::{MockResponse, MockServer};
+        use crate::RetryConfig;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let flaky_requests = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&flaky_requests);
+        let server = MockServer::start(move |req| {
+            if req.path.starts_with("/missing") {
+                MockResponse::status(404)
+            } else if req.path == "/flaky" && counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                MockResponse::status(503)
+            } else if req.path == "/short" {
+                MockResponse::ok(
+                    r#"<html><head><meta property="og:title" content="A"></head></html>"#,
+                )
+            } else {
+                MockResponse::ok(format!(
+                    r#"<html><head><meta property="og:title" content="Article {}"></head></html>"#,
+                    req.path
+                ))
+            }
+        });
+        let urls = vec![
+            server.url("/first"),
+            server.url("/missing"),
+            server.url("/second"),
+            server.url("/missing-too"),
+            server.url("/flaky"),
+            server.url("/short"),
+        ];
+        let config = Config::builder()
+            .max_concurrent_requests(2)
+            .min_title_len(2)
+            .retry(RetryConfig {
+                max_attempts: 2,
+                delay: Duration::from_millis(10),
+            })
+            .build();
+        let mut results: Vec<_> = Article::get_all(urls, &config)
+            .unwrap()
+            .map(|(url, article)| {
+                if url.path() == "/short" {
+                    let error = article.unwrap_err();
+                    assert!(matches!(
+                        error.downcast_ref(),
+                        Some(ExtrablattError::IncompleteArticle { .. })
+                    ));
+                    return (url.path().to_string(), None);
+                }
+                let title = article.map(|article| article.content.title.unwrap().into_owned());
+                (url.path().to_string(), title.ok())
+            })
+            .collect()
+            .await;
+        results.sort();
+        assert_eq!(flaky_requests.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            results,
+            vec![
+                ("/first".to_string(), Some("Article /first".to_string())),
+                ("/flaky".to_string(), Some("Article /flaky".to_string())),
+                ("/missing".to_string(), None),
+                ("/missing-too".to_string(), None),
+                ("/second".to_string(), Some("Article /second".to_string())),
+                ("/short".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn content_diff() {
+        let version = |text: &str| {
+            ArticleContent::builder()
+                .title("Storm hits the coast".into())
+                .text(text.to_string().into())
+                .build()
+        };
+        let old = version("The storm reached the coast at noon.\nThousands are without power.");
+        let new = version(
+            "The storm reached the coast at noon.\n\nThousands are without power.\nUpdate: Two people were injured.",
+        );
+
+        assert!(!old.diff(&old).has_changes());
+        assert_eq!(
+            old.diff(&new),
+            ContentDiff {
+                text_changed: true,
+                added_paragraphs: vec!["Update: Two people were injured.".to_string()],
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            new.diff(&old).removed_paragraphs,
+            vec!["Update: Two people were injured."]
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+use anyhow::{anyhow, Result};
+use reqwest::{Client, Url};
+
+/// The future returned by [`Authenticator::authenticate`].
+pub type AuthFuture<'a> = Pin<Box<dyn Future<Output = Result<()>> + 'a>>;
+
+/// Establishes a session before the first page of a site is fetched, e.g. for
+/// subscriber-only outlets.
+pub trait Authenticator: fmt::Debug {
+    /// Logs in using the `client`. The client keeps the session cookies in its
+    /// cookie store and sends them with every subsequent request.
+    fn authenticate<'a>(&'a self, client: &'a Client) -> AuthFuture<'a>;
+}
+
+/// Logs in by posting a form.
+#[derive(Debug, Clone)]
+pub struct FormLoginAuthenticator {
+    /// The url the login form is posted to.
+    pub login_url: Url,
+    /// The fields of the form, like the username and password.
+    pub fields: Vec<(String, String)>,
+}
+
+impl FormLoginAuthenticator {
+    pub fn new<I, K, V>(login_url: Url, fields: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: ToString,
+        V: ToString,
+    {
+        Self {
+            login_url,
+            fields: fields
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl Authenticator for FormLoginAuthenticator {
+    fn authenticate<'a>(&'a self, client: &'a Client) -> AuthFuture<'a> {
+        Box::pin(async move {
+            let response = client
+                .post(self.login_url.clone())
+                .form(&self.fields)
+                .send()
+                .await?;
+            if !response.status().is_success() {
+                return Err(anyhow!(
+                    "Login at {} failed with status {}",
+                    self.login_url,
+                    response.status()
+                ));
+            }
+            Ok(())
+        })
+    }
+}
@@ -14,7 +14,18 @@ pub struct Category {
 }
 
 impl Category {
-    pub fn new(url: Url) -> Self {
+    /// Creates a new category for the normalized `url`.
+    ///
+    /// The trailing slash of the path and the fragment are removed, so that
+    /// `https://some-news.com/sports/` and `https://some-news.com/sports`
+    /// are the same category. Scheme and host are already lowercased and
+    /// default ports dropped by the [`Url`] parser.
+    pub fn new(mut url: Url) -> Self {
+        url.set_fragment(None);
+        if url.path().len() > 1 && url.path().ends_with('/') {
+            let path = url.path().trim_end_matches('/').to_string();
+            url.set_path(&path);
+        }
         Self { url }
     }
 
@@ -67,6 +78,20 @@ impl Category {
     ) -> Result<impl Stream<Item = std::result::Result<Article, ExtrablattError>>> {
         Ok(ArticleStream::new_with_extractor(self.url, extractor).await?)
     }
+
+    /// Same as [`Category::into_stream_with_extractor`], but also follows the
+    /// category's pagination, like `rel="next"` or numbered page links, and
+    /// fetches the article urls of up to `max_pages` pages in total.
+    ///
+    /// Every page is requested at most once, so pagination that links back to
+    /// previous pages doesn't loop.
+    pub async fn into_paginated_stream<TExtractor: Extractor + Unpin>(
+        self,
+        max_pages: usize,
+        extractor: TExtractor,
+    ) -> Result<impl Stream<Item = std::result::Result<Article, ExtrablattError>>> {
+        ArticleStream::new_paginated_with_extractor(self.url, max_pages, extractor).await
+    }
 }
 
 impl Borrow<str> for Category {
@@ -90,4 +115,98 @@ mod tests {
         let category = Category::new(Url::parse("https://cnn.com/Europe").unwrap());
         assert_eq!(category.language_hint(), None);
     }
+
+    #[test]
+    fn normalized_category_url() {
+        let sports = Category::new(Url::parse("https://some-news.com/sports").unwrap());
+        assert_eq!(
+            sports,
+            Category::new(Url::parse("https://some-news.com/sports/").unwrap())
+        );
+        assert_eq!(
+            sports,
+            Category::new(Url::parse("HTTPS://Some-News.com:443/sports#top").unwrap())
+        );
+        assert_eq!(
+            Category::new(Url::parse("https://some-news.com/").unwrap())
+                .url
+                .as_str(),
+            "https://some-news.com/"
+        );
+
+        let mut set = std::collections::HashSet::new();
+        set.insert(sports);
+        assert!(set.contains("https://some-news.com/sports"));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn paginated_category_stream() {
+        use crate::testutil::{MockResponse, MockServer};
+        use futures::StreamExt;
+
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/politics" => MockResponse::ok(include_str!("../fixtures/category_page_1.html")),
+            "/politics/page/2" => {
+                MockResponse::ok(include_str!("../fixtures/category_page_2.html"))
+            }
+            path if path.starts_with("/politics/2020/") => MockResponse::ok(
+                "<html><head><title>Article</title></head><body><p>Article</p></body></html>",
+            ),
+            _ => MockResponse::status(404),
+        });
+        let paths = |max_pages: usize| {
+            let category = Category::new(server.url("/politics"));
+            async move {
+                let mut paths: Vec<_> = category
+                    .into_paginated_stream(max_pages, DefaultExtractor)
+                    .await
+                    .unwrap()
+                    .filter_map(|article| async move {
+                        article.ok().map(|article| article.url.path().to_string())
+                    })
+                    .collect()
+                    .await;
+                paths.sort();
+                paths
+            }
+        };
+
+        assert_eq!(
+            paths(5).await,
+            vec![
+                "/politics/2020/06/01/city-council-approves-new-budget-for-schools",
+                "/politics/2020/06/02/local-team-wins-championship-after-extra-time",
+                "/politics/2020/06/03/storm-causes-power-outages-across-the-region",
+                "/politics/2020/06/04/new-bike-lanes-open-in-the-city-center",
+            ]
+        );
+        assert_eq!(paths(1).await.len(), 2);
+    }
+
+    #[test]
+    fn dedupe_categories() {
+        let doc = select::document::Document::from(
+            r#"<html><body>
+            <a href="/sports">Sports</a>
+            <a href="/sports/">Sports</a>
+            <a href="https://some-news.com/sports/#latest">Sports</a>
+            <a href="/world/">World</a>
+            </body></html>"#,
+        );
+        let base_url = Url::parse("https://some-news.com/").unwrap();
+        let mut categories: Vec<_> = DefaultExtractor
+            .categories(&doc, &base_url)
+            .into_iter()
+            .map(|cat| cat.url.to_string())
+            .collect();
+        categories.sort();
+        assert_eq!(
+            categories,
+            vec![
+                "https://some-news.com/sports",
+                "https://some-news.com/world"
+            ]
+        );
+    }
 }
@@ -5,12 +5,18 @@ use select::node::{Descendants, Node};
 use select::predicate::{Name, Predicate};
 use std::borrow::Cow;
 use std::collections::HashSet;
+use std::ops::Range;
+use unicode_normalization::{is_nfc, UnicodeNormalization};
 
 lazy_static! {
 
     /// A [`Regex`] to determine whether a `Node`'s attribute should be ignored
     pub static ref RE_BAD_NODES_ATTR : Regex = Regex::new(r###"(?mi)^side$|combx|retweet|mediaarticlerelated|menucontainer|navbar|storytopbar-bucket|utility-bar|inline-share-tools|comment|PopularQuestions|contact|foot(er|note)?|cnn_strycaptiontxt|cnn_html_slideshow|cnn_strylftcntnt|links|meta$|shoutbox|sponsor|tags|socialnetworking|socialNetworking|cnnStryHghLght|cnn_stryspcvbx|^inset$|pagetools|post-attributes|welcome_form|contentTools2|the_answers|communitypromo|runaroundLeft|subscribe|vcard|articleheadings|date|^print$|popup|author-dropdown|tools|socialtools|byline|konafilter|breadcrumbs|^fn$|wp-caption-text|legende|ajoutVideo|timestamp|js_replies|[^-]facebook(-broadcasting)?|google|[^-]twitter|styln-briefing-block|read-more-link|js-body-read-more"###).unwrap();
 
+    /// A [`Regex`] for the `id`, `class` or `name` of ad and sponsored
+    /// blocks, like `ad-slot` or `taboola-feed-below-article`
+    pub static ref RE_AD_NODES_ATTR : Regex = Regex::new(r"(?i)(^|[\s_-])(ads?|advert(isement|ising)?|sponsor(ed)?|promoted|paid-content|taboola|outbrain|ob-widget)([\s_-]|$)").unwrap();
+
 }
 
 pub const BAD_NODE_NAMES: &[&str; 5] = &["script", "style", "figcaption", "figure", "button"];
@@ -24,6 +30,7 @@ pub trait DocumentCleaner {
         fn recur_text<T: DocumentCleaner + ?Sized>(
             node: Node,
             txt: &mut String,
+            links: &mut Vec<Range<usize>>,
             cleaner: &T,
         ) -> bool {
             if cleaner.is_bad_node_name(node) {
@@ -32,6 +39,10 @@ pub trait DocumentCleaner {
 
             let mut txt_added = false;
             if cleaner.is_good_node(node) {
+                let start = txt.len();
+                if Name("li").matches(&node) {
+                    txt.push_str(cleaner.list_item_marker());
+                }
                 let mut needs_ws = false;
                 for child in node.children() {
                     if needs_ws {
@@ -43,19 +54,31 @@ pub trait DocumentCleaner {
                             txt.push_str(txt_fragment);
                             txt_added = true
                         }
+                    } else if cleaner.is_call_to_action(child) || cleaner.is_ad_block(child) {
+                        // the node itself is never dropped, only the prompts and ads it contains
+                        continue;
                     } else if Name("a").matches(&child) {
                         // escape the content of a `<a>...</a>` tag that is embedded between
                         // text nodes
                         let mut a = String::new();
-                        if recur_text(child, &mut a, cleaner) && txt_added {
+                        if recur_text(child, &mut a, &mut Vec::new(), cleaner) && txt_added {
                             txt.push(' ');
+                            links.push(txt.len()..txt.len() + a.len());
                             txt.push_str(&a);
                             needs_ws = true;
                         }
                     } else {
-                        recur_text(child, txt, cleaner);
+                        // block level nodes start on a new line
+                        if is_para(child) && !txt.is_empty() && !txt.ends_with('\n') {
+                            txt.push('\n');
+                        }
+                        recur_text(child, txt, links, cleaner);
                     }
                 }
+                if txt.len() == start + cleaner.list_item_marker().len() {
+                    // drop the marker of an empty list item
+                    txt.truncate(start);
+                }
                 if (txt_added && is_para(node)) || needs_ws {
                     txt.push('\n');
                 }
@@ -64,8 +87,27 @@ pub trait DocumentCleaner {
         }
 
         let mut txt = String::new();
-        recur_text(node, &mut txt, self);
-        txt
+        let mut links = Vec::new();
+        recur_text(node, &mut txt, &mut links, self);
+        filter_lines(
+            txt,
+            &links,
+            self.max_line_link_density(),
+            self.min_line_words(),
+        )
+    }
+
+    /// Same as [`DocumentCleaner::clean_node_text`], but with normalized
+    /// whitespace, see [`normalize_whitespace`].
+    fn clean_node_text_normalized(&self, node: Node) -> String {
+        normalize_whitespace(&self.clean_node_text(node))
+    }
+
+    /// The prefix of the text of each `<li>`, like `- `.
+    ///
+    /// Empty by default.
+    fn list_item_marker(&self) -> &str {
+        ""
     }
 
     /// Whether the node should be considered
@@ -78,6 +120,37 @@ pub trait DocumentCleaner {
         is_bad_node(node)
     }
 
+    /// Lines of which a greater share of the non-whitespace characters is
+    /// link text, like menus, are dropped.
+    ///
+    /// Disabled (`1.0`) by default, as this also drops paragraphs that are
+    /// mostly a link, see [`MAX_LINE_LINK_DENSITY`] for a stricter value.
+    fn max_line_link_density(&self) -> f64 {
+        1.0
+    }
+
+    /// Lines with fewer words are dropped, unless they are adjacent to a line
+    /// with at least that many words, like headings are to their paragraphs.
+    ///
+    /// Disabled (`0`) by default, as this also drops the items of lists.
+    fn min_line_words(&self) -> usize {
+        0
+    }
+
+    /// Whether the node is an inline call to action, like a subscription
+    /// prompt, that should be ignored regardless of its attributes.
+    ///
+    /// Never by default.
+    fn is_call_to_action(&self, _node: Node) -> bool {
+        false
+    }
+
+    /// Whether the node is an ad or sponsored block, like an Outbrain widget,
+    /// see [`is_ad_block`].
+    fn is_ad_block(&self, node: Node) -> bool {
+        is_ad_block(node)
+    }
+
     /// Create an iterator that yields every node that this cleaner considers
     /// good
     fn iter_clean_nodes<'a>(&'a self, node: Node<'a>) -> CleanNodeIter<'a, Self>
@@ -132,6 +205,13 @@ where
     pub bad_node_names: HashSet<Cow<'static, str>>,
     /// Predicate to decide whether a node can hold valid textual content
     pub is_good_node: P,
+    /// Lowercased phrases of calls to action, like `enter your email`, whose
+    /// nodes are ignored, see [`CommonCleaner::with_cta_patterns`].
+    pub cta_patterns: Vec<Cow<'static, str>>,
+    /// See [`DocumentCleaner::max_line_link_density`].
+    pub max_line_link_density: f64,
+    /// See [`DocumentCleaner::min_line_words`].
+    pub min_line_words: usize,
 }
 
 impl<P> CommonCleaner<P>
@@ -156,8 +236,41 @@ where
         Self {
             bad_node_names: bad_names.into_iter().map(I::into).collect(),
             is_good_node,
+            cta_patterns: Vec::new(),
+            max_line_link_density: 1.0,
+            min_line_words: 0,
         }
     }
+
+    /// Drop lines that are mostly link text or have fewer than
+    /// `min_line_words` words and aren't adjacent to a longer line, see
+    /// [`DocumentCleaner::max_line_link_density`] and
+    /// [`DocumentCleaner::min_line_words`].
+    pub fn with_line_thresholds(
+        mut self,
+        max_line_link_density: f64,
+        min_line_words: usize,
+    ) -> Self {
+        self.max_line_link_density = max_line_link_density;
+        self.min_line_words = min_line_words;
+        self
+    }
+
+    /// Also ignore nodes of at most [`CTA_MAX_WORDS`] words that contain one
+    /// of the `patterns`, ignoring case, like the
+    /// [`DEFAULT_CTA_PATTERNS`].
+    pub fn with_cta_patterns<T, I>(mut self, patterns: T) -> Self
+    where
+        T: IntoIterator<Item = I>,
+        I: Into<Cow<'static, str>>,
+    {
+        self.cta_patterns = patterns
+            .into_iter()
+            .map(|p| Cow::<str>::Owned(p.into().trim().to_lowercase()))
+            .filter(|p| !p.is_empty())
+            .collect();
+        self
+    }
 }
 
 impl Default for CommonCleaner<for<'s> fn(Node<'s>) -> bool> {
@@ -182,6 +295,315 @@ where
             true
         }
     }
+
+    fn is_call_to_action(&self, node: Node) -> bool {
+        if self.cta_patterns.is_empty() || node.name().is_none() {
+            return false;
+        }
+        let txt = node.text();
+        if txt.split_whitespace().count() > CTA_MAX_WORDS {
+            return false;
+        }
+        let txt = txt
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+        self.cta_patterns.iter().any(|p| txt.contains(p.as_ref()))
+    }
+
+    fn max_line_link_density(&self) -> f64 {
+        self.max_line_link_density
+    }
+
+    fn min_line_words(&self) -> usize {
+        self.min_line_words
+    }
+}
+
+/// Phrases of inline subscription and newsletter prompts, see
+/// [`CommonCleaner::with_cta_patterns`].
+pub const DEFAULT_CTA_PATTERNS: [&str; 7] = [
+    "already a subscriber?",
+    "get unlimited access",
+    "enter your email",
+    "subscribe now",
+    "sign up for our newsletter",
+    "create a free account",
+    "support our journalism",
+];
+
+/// Max. number of words of a call to action node, so that nodes containing
+/// the article are never ignored.
+pub const CTA_MAX_WORDS: usize = 60;
+
+/// A max. share of link text of a line that drops menus and lists of links,
+/// see [`DocumentCleaner::max_line_link_density`].
+pub const MAX_LINE_LINK_DENSITY: f64 = 0.5;
+
+/// Drops the lines of the `txt` that are mostly made of the `links` or are
+/// short and not adjacent to a paragraph.
+///
+/// If no line is a paragraph, only the link density is checked.
+fn filter_lines(
+    txt: String,
+    links: &[Range<usize>],
+    max_link_density: f64,
+    min_words: usize,
+) -> String {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    for line in txt.split('\n') {
+        let (mut chars, mut link_chars) = (0usize, 0usize);
+        for (idx, _) in line.char_indices().filter(|(_, c)| !c.is_whitespace()) {
+            chars += 1;
+            if links.iter().any(|link| link.contains(&(start + idx))) {
+                link_chars += 1;
+            }
+        }
+        let is_menu = chars > 0 && link_chars as f64 > chars as f64 * max_link_density;
+        let is_para = !is_menu && line.split_whitespace().count() >= min_words;
+        lines.push((line, chars == 0, is_menu, is_para));
+        start += line.len() + 1;
+    }
+
+    let has_para = min_words > 0 && lines.iter().any(|(_, empty, _, para)| !empty && *para);
+    let is_near_para = |idx: usize| {
+        let mut neighbours = lines[..idx]
+            .iter()
+            .rev()
+            .find(|(_, empty, ..)| !empty)
+            .into_iter()
+            .chain(lines[idx + 1..].iter().find(|(_, empty, ..)| !empty));
+        neighbours.any(|(.., para)| *para)
+    };
+    let keep: Vec<_> = (0..lines.len())
+        .map(|idx| {
+            let (_, empty, menu, para) = lines[idx];
+            empty || (!menu && (para || !has_para || is_near_para(idx)))
+        })
+        .collect();
+    if keep.iter().all(|keep| *keep) {
+        return txt;
+    }
+    lines
+        .iter()
+        .zip(keep)
+        .filter(|(_, keep)| *keep)
+        .map(|((line, ..), _)| *line)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Lowercased labels of ads and sponsored blocks, like `Advertisement`.
+pub const AD_LABELS: [&str; 10] = [
+    "ad",
+    "advertisement",
+    "anzeige",
+    "paid content",
+    "paid post",
+    "promoted",
+    "publicité",
+    "sponsored",
+    "sponsored content",
+    "story continues below advertisement",
+];
+
+/// Max. number of words of an ad block that is detected by its label, so that
+/// nodes containing the article are never ignored.
+pub const AD_MAX_WORDS: usize = 100;
+
+/// Whether the node is an ad or sponsored block.
+///
+/// That is the case if its `id`, `class` or `name` matches the
+/// [`RE_AD_NODES_ATTR`], or its text is or starts with one of the
+/// [`AD_LABELS`], like `<div><span>Advertisement</span>...</div>`.
+pub fn is_ad_block(node: Node) -> bool {
+    if node.name().is_none() {
+        return false;
+    }
+    if ATTR_TO_CHECK
+        .iter()
+        .filter_map(|attr| node.attr(attr))
+        .any(|value| RE_AD_NODES_ATTR.is_match(value))
+    {
+        return true;
+    }
+    let is_label = |txt: &str| {
+        let txt = txt.split_whitespace().collect::<Vec<_>>().join(" ");
+        AD_LABELS.contains(&txt.trim_end_matches(':').to_lowercase().as_str())
+    };
+    let label = node
+        .children()
+        .find(|child| child.name().is_some() || !child.text().trim().is_empty());
+    match label {
+        Some(label) => {
+            is_label(&label.text()) && node.text().split_whitespace().count() <= AD_MAX_WORDS
+        }
+        None => false,
+    }
+}
+
+/// Phrases of paragraphs that are usually not part of the article, like
+/// newsletter signups.
+pub const DEFAULT_BOILERPLATE_PHRASES: [&str; 8] = [
+    "sign up for our newsletter",
+    "subscribe to our newsletter",
+    "follow us on twitter",
+    "follow us on facebook",
+    "follow us on instagram",
+    "this article was originally published",
+    "click here to subscribe",
+    "all rights reserved",
+];
+
+/// Removes the leading and trailing paragraphs of the `txt` that contain one
+/// of the `phrases`, ignoring case.
+pub fn strip_boilerplate<T: AsRef<str>>(txt: &str, phrases: &[T]) -> String {
+    let phrases: Vec<_> = phrases
+        .iter()
+        .map(|p| p.as_ref().trim().to_lowercase())
+        .filter(|p| !p.is_empty())
+        .collect();
+    let is_boilerplate = |line: &&str| {
+        let line = line.trim();
+        if line.is_empty() {
+            return true;
+        }
+        let line = line.to_lowercase();
+        phrases.iter().any(|p| line.contains(p.as_str()))
+    };
+
+    let lines: Vec<_> = txt.lines().collect();
+    let start = lines
+        .iter()
+        .position(|line| !is_boilerplate(line))
+        .unwrap_or(lines.len());
+    let end = lines
+        .iter()
+        .rposition(|line| !is_boilerplate(line))
+        .map(|idx| idx + 1)
+        .unwrap_or(start);
+    lines[start..end.max(start)].join("\n")
+}
+
+/// The `txt` cut to at most `max_chars` chars at the last whitespace, without
+/// trailing whitespace.
+///
+/// A single word longer than `max_chars` is cut at `max_chars`.
+pub fn truncate_words(txt: &str, max_chars: usize) -> &str {
+    let end = match txt.char_indices().nth(max_chars) {
+        Some((end, _)) => end,
+        None => return txt,
+    };
+    let truncated = if txt[end..].starts_with(char::is_whitespace) {
+        &txt[..end]
+    } else {
+        txt[..end]
+            .rfind(char::is_whitespace)
+            .map(|idx| &txt[..idx])
+            .unwrap_or(&txt[..end])
+    };
+    truncated.trim_end()
+}
+
+/// The characters of Windows-1252 for the bytes `0x80..=0x9F`, in which it
+/// differs from Latin-1. Unassigned bytes map to the C1 control characters.
+const CP1252_HIGH: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8D}', 'Ž', '\u{8F}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9D}', 'ž', 'Ÿ',
+];
+
+/// The Windows-1252 byte of the character, if it's not ASCII.
+fn cp1252_byte(c: char) -> Option<u8> {
+    match c as u32 {
+        0xA0..=0xFF => Some(c as u8),
+        _ => CP1252_HIGH
+            .iter()
+            .position(|high| *high == c)
+            .map(|idx| 0x80 + idx as u8),
+    }
+}
+
+/// Repairs UTF-8 text that was decoded as Windows-1252 or Latin-1, like
+/// `donâ€™t`.
+///
+/// Runs of non ASCII characters are encoded back to Windows-1252 and every
+/// valid UTF-8 sequence in them is decoded again. Characters that aren't
+/// part of such a sequence, like a single `é`, are kept.
+pub fn repair_mojibake(txt: &str) -> Cow<'_, str> {
+    fn flush(run: &mut Vec<char>, out: &mut String) {
+        let bytes: Vec<u8> = run.iter().filter_map(|c| cp1252_byte(*c)).collect();
+        let mut i = 0;
+        while i < bytes.len() {
+            match std::str::from_utf8(&bytes[i..]) {
+                Ok(decoded) => {
+                    out.push_str(decoded);
+                    break;
+                }
+                Err(err) => {
+                    let valid = err.valid_up_to();
+                    out.push_str(std::str::from_utf8(&bytes[i..i + valid]).unwrap_or_default());
+                    // every byte stems from exactly one character of the run
+                    out.push(run[i + valid]);
+                    i += valid + 1;
+                }
+            }
+        }
+        run.clear();
+    }
+
+    if txt.is_ascii() {
+        return Cow::Borrowed(txt);
+    }
+    let mut out = String::with_capacity(txt.len());
+    let mut run = Vec::new();
+    for c in txt.chars() {
+        if cp1252_byte(c).is_some() {
+            run.push(c);
+        } else {
+            flush(&mut run, &mut out);
+            out.push(c);
+        }
+    }
+    flush(&mut run, &mut out);
+
+    if out == txt {
+        Cow::Borrowed(txt)
+    } else {
+        Cow::Owned(out)
+    }
+}
+
+/// Composes letters followed by combining diacritical marks into their
+/// precomposed form, e.g. `e\u{301}` into `é`, by normalizing the text to
+/// Unicode Normalization Form C (NFC).
+pub fn compose_diacritics(txt: &str) -> Cow<'_, str> {
+    if is_nfc(txt) {
+        Cow::Borrowed(txt)
+    } else {
+        Cow::Owned(txt.nfc().collect())
+    }
+}
+
+/// Trims the trailing whitespace of every line, collapses consecutive blank
+/// lines into a single one and trims the whole text.
+pub fn normalize_whitespace(txt: &str) -> String {
+    let mut normalized = String::with_capacity(txt.len());
+    let mut blank_lines = 0;
+    for line in txt.lines().map(str::trim_end) {
+        if line.is_empty() {
+            blank_lines += 1;
+            if blank_lines > 1 {
+                continue;
+            }
+        } else {
+            blank_lines = 0;
+        }
+        normalized.push_str(line);
+        normalized.push('\n');
+    }
+    normalized.trim().to_string()
 }
 
 pub fn is_bad_node(node: Node) -> bool {
@@ -199,6 +621,7 @@ fn is_para(node: Node) -> bool {
             "dl",
             "div",
             "img",
+            "li",
             "ol",
             "p",
             "pre",
@@ -223,3 +646,124 @@ pub fn has_bad_attr(node: Node) -> bool {
     }
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use select::document::Document;
+    use select::predicate::Attr;
+
+    #[test]
+    fn boilerplate() {
+        let txt = "Follow us on Twitter!\nFirst paragraph.\n\nSecond paragraph.\n\nSign up for our newsletter to get the latest news.";
+        assert_eq!(
+            strip_boilerplate(txt, &DEFAULT_BOILERPLATE_PHRASES),
+            "First paragraph.\n\nSecond paragraph."
+        );
+        assert_eq!(strip_boilerplate(txt, &["not found"]), txt);
+    }
+
+    #[test]
+    fn list_items_and_blockquotes() {
+        let doc = Document::from(
+            "<html><body><div id=\"article\">
+                Introduction
+                <ul><li>First item</li><li>Second item</li><li>Third item</li><li></li></ul>
+                <blockquote>A quote on its own.</blockquote>
+                <p>Last paragraph.</p>
+            </div></body></html>",
+        );
+        let node = doc.find(Attr("id", "article")).next().unwrap();
+        assert_eq!(
+            DefaultDocumentCleaner.clean_node_text_normalized(node),
+            "Introduction\nFirst item\nSecond item\nThird item\nA quote on its own.\nLast paragraph."
+        );
+
+        struct MarkdownCleaner;
+
+        impl DocumentCleaner for MarkdownCleaner {
+            fn list_item_marker(&self) -> &str {
+                "- "
+            }
+        }
+
+        let list = doc.find(Name("ul")).next().unwrap();
+        assert_eq!(
+            MarkdownCleaner.clean_node_text_normalized(list),
+            "- First item\n- Second item\n- Third item"
+        );
+    }
+
+    #[test]
+    fn call_to_action_nodes() {
+        let doc = Document::from(
+            "<html><body><div id=\"article\">
+                <p>First paragraph.</p>
+                <div class=\"inline-block\">
+                    <p>Already a subscriber? <a href=\"/login\">Log in</a></p>
+                    <p>Get unlimited access for $1 a week.</p>
+                </div>
+                <p>Second paragraph.</p>
+                <aside><h4>Morning Briefing</h4><p>Enter your email</p></aside>
+                <p>Last paragraph.</p>
+            </div></body></html>",
+        );
+        let node = doc.find(Attr("id", "article")).next().unwrap();
+
+        let cleaner =
+            CommonCleaner::default().with_cta_patterns(DEFAULT_CTA_PATTERNS.iter().cloned());
+        assert_eq!(
+            cleaner.clean_node_text_normalized(node),
+            "First paragraph.\nSecond paragraph.\nLast paragraph."
+        );
+        assert!(CommonCleaner::default()
+            .clean_node_text_normalized(node)
+            .contains("Already a subscriber?"));
+    }
+
+    #[test]
+    fn truncated_words() {
+        assert_eq!(truncate_words("Zürich is great", 10), "Zürich is");
+        assert_eq!(truncate_words("Zürich is great", 9), "Zürich is");
+        assert_eq!(truncate_words("Zürich is great", 15), "Zürich is great");
+        assert_eq!(truncate_words("Donaudampfschiff", 5), "Donau");
+    }
+
+    #[test]
+    fn normalized_text() {
+        let doc = Document::from(
+            "<html><body><div id=\"article\">
+                <div><div><p>   First paragraph.   </p></div></div>
+                <pre>Second paragraph.   \n\n\n\n\nThird paragraph.  </pre>
+                <div>   </div>
+            </div></body></html>",
+        );
+        let node = doc.find(Attr("id", "article")).next().unwrap();
+        assert_eq!(
+            DefaultDocumentCleaner.clean_node_text_normalized(node),
+            "First paragraph.\nSecond paragraph.\n\nThird paragraph."
+        );
+    }
+
+    #[test]
+    fn sanitize_unicode() {
+        assert_eq!(repair_mojibake("donâ€™t"), "don’t");
+        assert_eq!(
+            repair_mojibake("Ã¼ber MÃ¼nchen â€” 5 â‚¬, naïve"),
+            "über München — 5 €, naïve"
+        );
+        assert!(matches!(
+            repair_mojibake("naïve café, 5 €"),
+            Cow::Borrowed("naïve café, 5 €")
+        ));
+        assert!(matches!(repair_mojibake("plain"), Cow::Borrowed("plain")));
+
+        assert_eq!(
+            compose_diacritics("cafe\u{301} Zu\u{308}rich"),
+            "café Zürich"
+        );
+        assert_eq!(compose_diacritics("x\u{301}"), "x\u{301}");
+        assert_eq!(compose_diacritics("Ha\u{300} No\u{323}\u{302}i"), "Hà Nội");
+        assert!(matches!(compose_diacritics("café"), Cow::Borrowed("café")));
+    }
+}
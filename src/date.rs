@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
 
 use regex::Regex;
 use select::document::Document;
@@ -23,6 +23,12 @@ lazy_static! {
 
     pub(crate) static ref RE_KEY_VALUE_MODIFIED_DATE: Regex = Regex::new(r#"(?mi)"\s*(([^"]|\w)*)?((date[\s_-]?modified|modified[\s_-]?date))\s*"\s*[:=]\s*"\s*(?P<date>[^"]*)\s*""#).unwrap();
 
+    /// Relative dates like `3 hours ago`.
+    pub(crate) static ref RE_RELATIVE_DATE: Regex = Regex::new(r"(?i)\b(?P<num>\d+|an?|one)\s+(?P<unit>second|sec|minute|min|hour|hr|day|week)s?\s+ago\b").unwrap();
+
+    /// A four digit year, to make sure that text contains an absolute date.
+    pub(crate) static ref RE_YEAR: Regex = Regex::new(r"\b(19|20)\d{2}\b").unwrap();
+
     /// Common nodes that hold the article's modification date.
     pub(crate) static ref  MODIFIED_DATE_NODES: Vec<NodeValueQuery<'static>> = {
             let mut nodes = Vec::with_capacity(8);
@@ -81,7 +87,7 @@ lazy_static! {
         };
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
 pub enum Date {
     /// The ISO 8601 date, a pair of year, month and day of the year.
@@ -90,7 +96,7 @@ pub enum Date {
     DateTime(NaiveDateTime),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
 pub enum Update {
     /// The ISO 8601 date, a pair of year, month and day of the year.
@@ -101,13 +107,17 @@ pub enum Update {
     Time(NaiveTime),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
 pub struct ArticleDate {
     /// When the article was first published.
     pub published: Date,
     /// Last time the article was updated.
     pub last_updated: Option<Update>,
+    /// Whether `published` is the modification date, because no publishing
+    /// date was found.
+    #[cfg_attr(feature = "serde0", serde(default))]
+    pub published_from_modified: bool,
 }
 
 pub struct DateExtractor;
@@ -117,22 +127,27 @@ impl DateExtractor {
     ///
     /// 1. Look in the common `<meta>` nodes.
     /// 2. Regex the `<head>` node
+    ///
+    /// If only a modification date is found, it is used as publishing date
+    /// and [`ArticleDate::published_from_modified`] is set.
     pub fn extract_from_doc(doc: &Document) -> Option<ArticleDate> {
-        if let Some(published) =
-            DateExtractor::extract_date(doc, &PUBLISH_DATE_NODES, &RE_KEY_VALUE_PUBLISH_DATE)
-        {
-            let last_updated = DateExtractor::extract_date(
-                &doc,
-                &MODIFIED_DATE_NODES,
-                &RE_KEY_VALUE_MODIFIED_DATE,
-            )
-            .map(Update::DateTime);
-            return Some(ArticleDate {
+        let published =
+            DateExtractor::extract_date(doc, &PUBLISH_DATE_NODES, &RE_KEY_VALUE_PUBLISH_DATE);
+        let modified =
+            DateExtractor::extract_date(&doc, &MODIFIED_DATE_NODES, &RE_KEY_VALUE_MODIFIED_DATE);
+        match (published, modified) {
+            (Some(published), modified) => Some(ArticleDate {
                 published: Date::DateTime(published),
-                last_updated,
-            });
+                last_updated: modified.map(Update::DateTime),
+                published_from_modified: false,
+            }),
+            (None, Some(modified)) => Some(ArticleDate {
+                published: Date::DateTime(modified),
+                last_updated: Some(Update::DateTime(modified)),
+                published_from_modified: true,
+            }),
+            (None, None) => None,
         }
-        None
     }
 
     fn extract_date<'a>(
@@ -167,7 +182,7 @@ impl DateExtractor {
             .next()
     }
 
-    fn fuzzy_dtparse(s: &str) -> Option<NaiveDateTime> {
+    pub(crate) fn fuzzy_dtparse(s: &str) -> Option<NaiveDateTime> {
         let mut tzinfod = HashMap::new();
         tzinfod.insert("ET".to_string(), 14400);
         let parser = dtparse::Parser::default();
@@ -181,12 +196,40 @@ impl DateExtractor {
             .ok()
     }
 
+    /// Parses relative dates like `3 hours ago`, `yesterday` or `just now`
+    /// relative to `now`.
+    pub fn extract_relative(s: &str, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        if let Some(caps) = RE_RELATIVE_DATE.captures(s) {
+            let num = match caps.name("num")?.as_str().to_lowercase().as_str() {
+                "a" | "an" | "one" => 1,
+                num => num.parse().ok()?,
+            };
+            let duration = match caps.name("unit")?.as_str().to_lowercase().as_str() {
+                "second" | "sec" => Duration::seconds(num),
+                "minute" | "min" => Duration::minutes(num),
+                "hour" | "hr" => Duration::hours(num),
+                "day" => Duration::days(num),
+                _ => Duration::weeks(num),
+            };
+            return now.checked_sub_signed(duration);
+        }
+        let s = s.to_lowercase();
+        if s.contains("yesterday") {
+            return now.checked_sub_signed(Duration::days(1));
+        }
+        if s.contains("just now") || s.contains("today") {
+            return Some(now);
+        }
+        None
+    }
+
     /// Extract the publishing timestamp from plain text using fuzzy searching
     /// with `dtparse`.
     pub fn extract_from_str(s: &str) -> Option<ArticleDate> {
         DateExtractor::fuzzy_dtparse(s).map(|published| ArticleDate {
             published: Date::DateTime(published),
             last_updated: None,
+            published_from_modified: false,
         })
     }
 }
@@ -195,6 +238,30 @@ impl DateExtractor {
 mod tests {
     use super::*;
 
+    fn dt(y: i32, m: u32, d: u32, h: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .and_then(|date| date.and_hms_opt(h, 0, 0))
+            .unwrap()
+    }
+
+    #[test]
+    fn relative_dates() {
+        let now = dt(2020, 5, 5, 12);
+        assert_eq!(
+            DateExtractor::extract_relative("Updated 3 hours ago", now),
+            Some(dt(2020, 5, 5, 9))
+        );
+        assert_eq!(
+            DateExtractor::extract_relative("an hour ago", now),
+            Some(dt(2020, 5, 5, 11))
+        );
+        assert_eq!(
+            DateExtractor::extract_relative("Yesterday", now),
+            Some(dt(2020, 5, 4, 12))
+        );
+        assert_eq!(DateExtractor::extract_relative("By John Smith", now), None);
+    }
+
     #[test]
     fn date_modified() {
         let caps = RE_KEY_VALUE_MODIFIED_DATE
@@ -216,4 +283,27 @@ mod tests {
             "2019-12-05T15:34:34+0100"
         )
     }
+
+    #[test]
+    fn modified_date_only() {
+        let doc = Document::from(
+            r#"<html><head><meta property="article:modified" content="2020-05-01T10:00:00"></head></html>"#,
+        );
+        let date = DateExtractor::extract_from_doc(&doc).unwrap();
+        assert!(date.published_from_modified);
+        match date.published {
+            Date::DateTime(published) => assert_eq!(published, dt(2020, 5, 1, 10)),
+            Date::Date(_) => panic!("expected a datetime"),
+        }
+        assert!(matches!(date.last_updated, Some(Update::DateTime(_))));
+
+        let doc = Document::from(
+            r#"<html><head>
+            <meta property="article:published_time" content="2020-04-01T10:00:00">
+            <meta property="article:modified" content="2020-05-01T10:00:00">
+            </head></html>"#,
+        );
+        let date = DateExtractor::extract_from_doc(&doc).unwrap();
+        assert!(!date.published_from_modified);
+    }
 }
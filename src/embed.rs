@@ -0,0 +1,224 @@
+use select::document::Document;
+use select::node::Node;
+use select::predicate::{Attr, Class, Name, Predicate};
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use url::Url;
+
+use crate::video::Video;
+
+/// The platform a [`SocialEmbed`] originates from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub enum SocialProvider {
+    Twitter,
+    Instagram,
+    TikTok,
+    Facebook,
+}
+
+impl SocialProvider {
+    /// The social platform hosted at `host`, if any.
+    pub fn from_host(host: &str) -> Option<Self> {
+        // the domain itself or one of its subdomains
+        let is = |domain: &str| {
+            host == domain
+                || host
+                    .strip_suffix(domain)
+                    .map(|sub| sub.ends_with('.'))
+                    .unwrap_or_default()
+        };
+        if is("twitter.com") || is("x.com") || host == "t.co" {
+            return Some(SocialProvider::Twitter);
+        }
+        if is("instagram.com") {
+            return Some(SocialProvider::Instagram);
+        }
+        if is("tiktok.com") {
+            return Some(SocialProvider::TikTok);
+        }
+        if is("facebook.com") {
+            return Some(SocialProvider::Facebook);
+        }
+        None
+    }
+}
+
+/// A social media post embedded in an article.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct SocialEmbed {
+    /// The permalink of the embedded post.
+    pub url: Url,
+    pub provider: SocialProvider,
+}
+
+/// Finds all embedded tweets, instagram and tiktok posts and iframe players of
+/// social platforms in the document.
+pub fn social_embeds(doc: &Document, base_url: Option<&Url>) -> Vec<SocialEmbed> {
+    let options = Url::options().base_url(base_url);
+    let parse = |href: &str| options.parse(href).ok();
+
+    let mut embeds: Vec<SocialEmbed> = Vec::new();
+    let mut push = |url: Option<Url>, provider: SocialProvider| {
+        if let Some(url) = url {
+            if embeds.iter().all(|e| e.url != url) {
+                embeds.push(SocialEmbed { url, provider });
+            }
+        }
+    };
+
+    for node in doc.find(Name("blockquote").and(Class("twitter-tweet"))) {
+        // the permalink is the last link, the others are mentions and hashtags
+        let url = links(node)
+            .filter(|href| href.contains("/status/"))
+            .last()
+            .and_then(parse);
+        push(url, SocialProvider::Twitter);
+    }
+
+    for node in doc.find(Name("blockquote").and(Class("instagram-media"))) {
+        let url = node
+            .attr("data-instgrm-permalink")
+            .or_else(|| links(node).next())
+            .and_then(parse);
+        push(url, SocialProvider::Instagram);
+    }
+
+    for node in doc.find(Name("blockquote").and(Class("tiktok-embed"))) {
+        let url = node
+            .attr("cite")
+            .or_else(|| links(node).next())
+            .and_then(parse);
+        push(url, SocialProvider::TikTok);
+    }
+
+    for node in doc.find(Name("iframe")) {
+        if let Some(url) = node.attr("src").and_then(parse) {
+            if let Some(provider) = url.host_str().and_then(SocialProvider::from_host) {
+                push(Some(url), provider);
+            }
+        }
+    }
+
+    embeds
+}
+
+/// The urls of all `<link rel="alternate" type="application/json+oembed">`
+/// of the document, see <https://oembed.com/#section4>.
+pub fn oembed_endpoints(doc: &Document, base_url: Option<&Url>) -> Vec<Url> {
+    let options = Url::options().base_url(base_url);
+    let mut endpoints: Vec<Url> = Vec::new();
+    for link in doc.find(Name("link").and(Attr("type", "application/json+oembed"))) {
+        let is_alternate = link
+            .attr("rel")
+            .map(|rel| {
+                rel.split_whitespace()
+                    .any(|rel| rel.eq_ignore_ascii_case("alternate"))
+            })
+            .unwrap_or_default();
+        if let Some(url) = link
+            .attr("href")
+            .filter(|_| is_alternate)
+            .and_then(|href| options.parse(href.trim()).ok())
+        {
+            if !endpoints.contains(&url) {
+                endpoints.push(url);
+            }
+        }
+    }
+    endpoints
+}
+
+/// The response of an oEmbed endpoint.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct OEmbed {
+    /// The type of the resource, like `video` or `rich`.
+    pub kind: Option<String>,
+    /// The embedded resource, taken from the endpoint's `url` parameter.
+    pub url: Option<Url>,
+    pub title: Option<String>,
+    pub author_name: Option<String>,
+    pub author_url: Option<Url>,
+    pub provider_name: Option<String>,
+    pub thumbnail_url: Option<Url>,
+    /// The html to embed the resource.
+    pub html: Option<String>,
+}
+
+impl OEmbed {
+    /// Parses the JSON response of the oEmbed `endpoint`.
+    pub fn from_json(endpoint: &Url, json: &[u8]) -> Option<Self> {
+        let value: Value = serde_json::from_slice(json).ok()?;
+        let string = |key: &str| {
+            value
+                .get(key)
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+        };
+        let url = |key: &str| string(key).and_then(|url| Url::parse(&url).ok());
+        Some(Self {
+            kind: string("type"),
+            url: endpoint
+                .query_pairs()
+                .find(|(key, _)| key == "url")
+                .and_then(|(_, url)| Url::parse(&url).ok()),
+            title: string("title"),
+            author_name: string("author_name"),
+            author_url: url("author_url"),
+            provider_name: string("provider_name"),
+            thumbnail_url: url("thumbnail_url"),
+            html: string("html"),
+        })
+    }
+
+    /// The embedded resource as [`Video`], if it's of type `video`.
+    pub fn video(&self) -> Option<Video> {
+        if self.kind.as_deref() != Some("video") {
+            return None;
+        }
+        let mut video = Video::new(self.url.clone()?);
+        video.thumbnail = self.thumbnail_url.clone();
+        Some(video)
+    }
+
+    /// The embedded resource as [`SocialEmbed`], if it's hosted by a social
+    /// platform.
+    pub fn social_embed(&self) -> Option<SocialEmbed> {
+        let url = self.url.clone()?;
+        let provider = url.host_str().and_then(SocialProvider::from_host)?;
+        Some(SocialEmbed { url, provider })
+    }
+}
+
+fn links<'a>(node: Node<'a>) -> impl Iterator<Item = &'a str> {
+    node.find(Name("a")).filter_map(|a| a.attr("href"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn provider_from_host() {
+        assert_eq!(
+            SocialProvider::from_host("twitter.com"),
+            Some(SocialProvider::Twitter)
+        );
+        assert_eq!(
+            SocialProvider::from_host("mobile.twitter.com"),
+            Some(SocialProvider::Twitter)
+        );
+        assert_eq!(
+            SocialProvider::from_host("www.instagram.com"),
+            Some(SocialProvider::Instagram)
+        );
+        assert_eq!(SocialProvider::from_host("nottwitter.com"), None);
+        assert_eq!(SocialProvider::from_host("fakefacebook.com"), None);
+        assert_eq!(SocialProvider::from_host("tiktok.com.evil.org"), None);
+    }
+}
@@ -24,6 +24,22 @@ pub enum ExtrablattError {
         /// The content the resulted in the error.
         body: Bytes,
     },
+    /// The response body exceeded the configured size limit.
+    #[error("Body of {url} exceeds the limit of {limit} bytes")]
+    BodyTooLarge {
+        /// The requested url.
+        url: reqwest::Url,
+        /// The configured max. number of bytes.
+        limit: usize,
+    },
+    /// Failed to build a news source of a [`crate::MultiSource`].
+    #[error("Failed to build news source: {error}")]
+    SourceBuildFailure {
+        /// The url of the news source.
+        url: Option<reqwest::Url>,
+        /// Why the news source couldn't be built.
+        error: anyhow::Error,
+    },
     /// Identified an article, but it's content doesn't fulfill the configured
     /// requirements.
     #[error("Found incomplete Article for {}", article.url)]
@@ -32,3 +48,156 @@ pub enum ExtrablattError {
         article: Box<PureArticle>,
     },
 }
+
+impl ExtrablattError {
+    /// Whether the error is likely temporary, so that repeating the request
+    /// might succeed.
+    ///
+    /// This is the case for timeouts, connection failures and `408`, `429`,
+    /// `502`, `503` and `504` responses.
+    pub fn is_retriable(&self) -> bool {
+        match self {
+            ExtrablattError::NoHttpSuccessResponse { response } => {
+                matches!(response.status().as_u16(), 408 | 429 | 502 | 503 | 504)
+            }
+            ExtrablattError::HttpRequestFailure { error } => {
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    error.is_timeout() || error.is_connect()
+                }
+                #[cfg(target_arch = "wasm32")]
+                {
+                    error.is_timeout()
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether the request timed out.
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            ExtrablattError::HttpRequestFailure { error } => error.is_timeout(),
+            _ => false,
+        }
+    }
+
+    /// The HTTP status code a server built on top of this crate should
+    /// respond with.
+    ///
+    /// Non success responses keep the upstream status, timeouts map to `504`,
+    /// other request failures, oversized bodies and news sources that can't be
+    /// built to `502` and documents without a usable article to `422`.
+    pub fn http_status(&self) -> u16 {
+        match self {
+            ExtrablattError::NoHttpSuccessResponse { response } => response.status().as_u16(),
+            ExtrablattError::HttpRequestFailure { .. } if self.is_timeout() => 504,
+            ExtrablattError::HttpRequestFailure { .. } => 502,
+            ExtrablattError::BodyTooLarge { .. } => 502,
+            ExtrablattError::SourceBuildFailure { .. } => 502,
+            ExtrablattError::ReadDocumentError { .. } => 422,
+            ExtrablattError::IncompleteArticle { .. } => 422,
+        }
+    }
+
+    /// A message that is safe to show to clients, without the internals of
+    /// the underlying error.
+    pub fn public_message(&self) -> String {
+        match self {
+            ExtrablattError::NoHttpSuccessResponse { response } => {
+                format!("The article's server responded with {}", response.status())
+            }
+            ExtrablattError::HttpRequestFailure { .. } if self.is_timeout() => {
+                "Timed out fetching the article".to_string()
+            }
+            ExtrablattError::HttpRequestFailure { .. } => "Failed to fetch the article".to_string(),
+            ExtrablattError::BodyTooLarge { .. } => "The article is too large".to_string(),
+            ExtrablattError::SourceBuildFailure { .. } => {
+                "Failed to fetch the news source".to_string()
+            }
+            ExtrablattError::ReadDocumentError { .. } => {
+                "The article's document could not be read".to_string()
+            }
+            ExtrablattError::IncompleteArticle { .. } => {
+                "The article's content is incomplete".to_string()
+            }
+        }
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::article::ArticleContent;
+    use crate::testutil::{MockResponse, MockServer};
+    use crate::Language;
+    use std::net::TcpListener;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn http_status_of_responses() {
+        let server = MockServer::start(|_| MockResponse::status(404));
+        let response = reqwest::get(server.url("/missing")).await.unwrap();
+        let err = ExtrablattError::NoHttpSuccessResponse { response };
+        assert_eq!(err.http_status(), 404);
+        assert_eq!(
+            err.public_message(),
+            "The article's server responded with 404 Not Found"
+        );
+    }
+
+    #[tokio::test]
+    async fn http_status_of_request_failures() {
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let error = reqwest::get(&format!("http://{}/", addr))
+            .await
+            .unwrap_err();
+        let err = ExtrablattError::HttpRequestFailure { error };
+        assert_eq!(err.http_status(), 502);
+        assert_eq!(err.public_message(), "Failed to fetch the article");
+
+        // accepts the connection, but never responds
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let error = reqwest::Client::builder()
+            .timeout(Duration::from_millis(50))
+            .build()
+            .unwrap()
+            .get(&format!("http://{}/", listener.local_addr().unwrap()))
+            .send()
+            .await
+            .unwrap_err();
+        let err = ExtrablattError::HttpRequestFailure { error };
+        assert!(err.is_timeout());
+        assert_eq!(err.http_status(), 504);
+        assert_eq!(err.public_message(), "Timed out fetching the article");
+    }
+
+    #[test]
+    fn http_status_of_documents() {
+        let url = reqwest::Url::parse("https://extrablatt.com/article.html").unwrap();
+        let err = ExtrablattError::ReadDocumentError {
+            body: Bytes::from_static(b"\xff"),
+        };
+        assert_eq!(err.http_status(), 422);
+
+        let err = ExtrablattError::BodyTooLarge {
+            url: url.clone(),
+            limit: 1024,
+        };
+        assert_eq!(err.http_status(), 502);
+        assert_eq!(err.public_message(), "The article is too large");
+
+        let err = ExtrablattError::IncompleteArticle {
+            article: Box::new(PureArticle {
+                url,
+                content: ArticleContent::builder().build(),
+                language: Language::English,
+            }),
+        };
+        assert_eq!(err.http_status(), 422);
+        assert_eq!(err.public_message(), "The article's content is incomplete");
+    }
+}
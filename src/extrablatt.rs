@@ -1,26 +1,43 @@
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
 use std::ops::{Deref, DerefMut};
 use std::pin::Pin;
+use std::sync::atomic::{self, AtomicBool, AtomicU64};
+use std::sync::Arc;
 use std::time::Duration;
 
 use bytes::Bytes;
-use fnv::FnvHashMap;
-use futures::stream::{self, Stream};
+use fnv::{FnvHashMap, FnvHashSet};
+use futures::stream::{self, FusedStream, Stream};
 use futures::task::Poll;
-use futures::{Future, FutureExt, StreamExt, TryFutureExt};
-use reqwest::header::HeaderMap;
+use futures::{Future, StreamExt};
 #[cfg(not(target_arch = "wasm32"))]
 use reqwest::header::USER_AGENT;
-use reqwest::Response;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT_LANGUAGE};
 use reqwest::{Client, IntoUrl, Url};
+use reqwest::{RequestBuilder, Response};
 use select::document::Document;
-use wasm_timer::Instant;
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+use wasm_timer::{Delay, Instant, SystemTime, UNIX_EPOCH};
 
 use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
 
-use crate::article::{Article, ArticleContent, ArticleUrl, PureArticle};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::article::download_all_bytes;
+use crate::article::{
+    Article, ArticleContent, ArticleUrl, PureArticle, TextSource, ARTICLE_ID_QUERY_PARAMS,
+};
+use crate::auth::Authenticator;
+use crate::clean::{strip_boilerplate, truncate_words};
 use crate::error::ExtrablattError;
-use crate::extract::{DefaultExtractor, Extractor};
+use crate::extract::{desktop_variant, DefaultExtractor, Extractor};
 use crate::language::Language;
+use crate::sitemap::{self, NewsMetadata};
+use crate::sites::KnownSite;
 use crate::text::ArticleTextNodeExtractor;
 use crate::Category;
 
@@ -45,6 +62,13 @@ pub struct Extrablatt<TExtractor: Extractor = DefaultExtractor> {
     categories: FnvHashMap<Category, DocumentDownloadState>,
     /// Configuration for article extraction.
     config: Config,
+    /// Random delays before requests.
+    jitter: Jitter,
+    /// Metadata of the articles found in Google News sitemaps.
+    news_metadata: FnvHashMap<Url, NewsMetadata>,
+    /// The downloaded images and video thumbnails of the articles, see
+    /// [`Config::download_media`].
+    media: HashMap<Url, Bytes>,
 }
 
 impl Extrablatt<DefaultExtractor> {
@@ -74,24 +98,236 @@ impl<TExtractor: Extractor> Extrablatt<TExtractor> {
         &mut self.config
     }
 
+    /// Replaces the config and drops all cached article and category urls
+    /// that don't qualify under the new `config` anymore, until at most
+    /// [`Config::max_doc_cache`] articles remain.
+    ///
+    /// Successfully downloaded documents are kept. The client is not rebuilt,
+    /// so the user agent and timeouts of requests remain unchanged.
+    pub fn apply_config(&mut self, config: Config) {
+        if config.jitter_seed.is_some() {
+            self.jitter = Jitter::new(config.jitter_seed);
+        }
+        self.config = config;
+
+        let base_url = &self.base_url;
+        let params = &self.config.article_id_query_params;
+        self.articles.retain(|article, state| {
+            state.is_success()
+                || TExtractor::is_article(article, base_url)
+                || TExtractor::is_query_article(article, base_url, params)
+        });
+        self.categories.retain(|category, state| {
+            state.is_success() || TExtractor::is_category(category, base_url)
+        });
+
+        let mut excess = self
+            .articles
+            .len()
+            .saturating_sub(self.config.max_doc_cache);
+        if excess > 0 {
+            self.articles.retain(|_, state| {
+                if excess > 0 && !state.is_success() {
+                    excess -= 1;
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+    }
+
     /// The extractor used to retrieve content for an article.
     #[inline]
     pub fn extractor(&self) -> &TExtractor {
         &self.extractor
     }
 
+    /// Mutable access to the extractor, to tune it between downloads.
+    #[inline]
+    pub fn extractor_mut(&mut self) -> &mut TExtractor {
+        &mut self.extractor
+    }
+
+    /// Replaces the extractor, while keeping all the already downloaded
+    /// documents.
+    pub fn map_extractor<T: Extractor>(self, extractor: T) -> Extrablatt<T> {
+        Extrablatt {
+            client: self.client,
+            language: self.language,
+            main_page: self.main_page,
+            base_url: self.base_url,
+            extractor,
+            articles: self.articles,
+            categories: self.categories,
+            config: self.config,
+            jitter: self.jitter,
+            news_metadata: self.news_metadata,
+            media: self.media,
+        }
+    }
+
+    /// Writes the whole cache of the newspaper as JSON to the `path`, so that
+    /// a crawl can be resumed with [`Extrablatt::load_cache`].
+    ///
+    /// Besides the base url, language and config, this includes the state of
+    /// every article and category and the html of the downloaded ones.
+    #[cfg(feature = "serde0")]
+    pub fn save_cache<P: AsRef<std::path::Path>>(&self, path: P) -> Result<()> {
+        let cache = Cache {
+            base_url: self.base_url.clone(),
+            language: self.language.clone(),
+            config: self.config.clone(),
+            main_page: document_html(&self.main_page),
+            articles: self
+                .articles
+                .iter()
+                .map(|(article, state)| (article.clone(), CachedState::from(state)))
+                .collect(),
+            categories: self
+                .categories
+                .iter()
+                .map(|(category, state)| (category.url.clone(), CachedState::from(state)))
+                .collect(),
+            news_metadata: self
+                .news_metadata
+                .iter()
+                .map(|(url, metadata)| (url.clone(), metadata.clone()))
+                .collect(),
+        };
+        let json = serde_json::to_vec(&cache)?;
+        std::fs::write(path.as_ref(), json).context(format!(
+            "Failed to write the cache to {}",
+            path.as_ref().display()
+        ))
+    }
+
+    /// Restores a newspaper from the cache written by
+    /// [`Extrablatt::save_cache`] that uses the `extractor`.
+    ///
+    /// The html of downloaded documents is parsed again and the time they were
+    /// received is reset to now.
+    ///
+    /// The client is built from the restored config only, so neither the
+    /// headers of [`ExtrablattBuilder::headers`] nor the session of an
+    /// [`ExtrablattBuilder::authenticator`] are restored. Fields missing in
+    /// the cache, e.g. of a cache written by an older version, are set to
+    /// their default.
+    #[cfg(feature = "serde0")]
+    pub fn load_cache<P: AsRef<std::path::Path>>(path: P, extractor: TExtractor) -> Result<Self> {
+        let json = std::fs::read(path.as_ref()).context(format!(
+            "Failed to read the cache from {}",
+            path.as_ref().display()
+        ))?;
+        let cache: Cache = serde_json::from_slice(&json)?;
+
+        #[cfg(target_arch = "wasm32")]
+        let client = Client::builder().build()?;
+        #[cfg(not(target_arch = "wasm32"))]
+        let client = build_client(&cache.config, HeaderMap::new(), false)?;
+
+        Ok(Extrablatt {
+            client,
+            language: cache.language,
+            main_page: Document::from(cache.main_page.as_str()),
+            base_url: cache.base_url,
+            extractor,
+            articles: cache
+                .articles
+                .into_iter()
+                .map(|(article, state)| (article, state.into_state()))
+                .collect(),
+            categories: cache
+                .categories
+                .into_iter()
+                .map(|(url, state)| (Category::new(url), state.into_state()))
+                .collect(),
+            jitter: Jitter::new(cache.config.jitter_seed),
+            news_metadata: cache.news_metadata.into_iter().collect(),
+            media: Default::default(),
+            config: cache.config,
+        })
+    }
+
     /// All available categories.
     #[inline]
     pub fn categories(&self) -> &FnvHashMap<Category, DocumentDownloadState> {
         &self.categories
     }
 
+    /// The categories linked from the page of each downloaded category,
+    /// including its sub-categories, see [`Extractor::categories`] and
+    /// [`Extractor::subcategories`].
+    ///
+    /// Both the categories and the linked categories are ordered by their url.
+    pub fn category_graph(&self) -> Vec<(Category, Vec<Category>)> {
+        let by_url = |a: &Category, b: &Category| a.url.as_str().cmp(b.url.as_str());
+        let mut graph: Vec<_> = self
+            .categories
+            .iter()
+            .filter_map(|(category, state)| {
+                if let DocumentDownloadState::Success { doc, .. } = state {
+                    let mut linked: Vec<_> = self
+                        .extract_categories(doc)
+                        .into_iter()
+                        .chain(self.extractor.subcategories(doc, category))
+                        .filter(|linked| linked != category)
+                        .collect();
+                    linked.sort_by(by_url);
+                    linked.dedup();
+                    Some((category.clone(), linked))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        graph.sort_by(|(a, _), (b, _)| by_url(a, b));
+        graph
+    }
+
     /// All available articles.
     #[inline]
     pub fn articles(&self) -> &FnvHashMap<ArticleUrl, DocumentDownloadState> {
         &self.articles
     }
 
+    /// Number of categories and articles that haven't been requested yet.
+    pub fn outstanding(&self) -> Outstanding {
+        Outstanding {
+            categories: self
+                .categories
+                .values()
+                .filter(|s| s.is_not_requested())
+                .count(),
+            articles: self
+                .articles
+                .values()
+                .filter(|s| s.is_not_requested())
+                .count(),
+        }
+    }
+
+    /// Number of categories and articles that were already requested, split
+    /// into successes and failures.
+    pub fn completed(&self) -> Completed {
+        let mut completed = Completed::default();
+        for state in self.categories.values() {
+            if state.is_success() {
+                completed.category_successes += 1;
+            } else if !state.is_not_requested() {
+                completed.category_failures += 1;
+            }
+        }
+        for state in self.articles.values() {
+            if state.is_success() {
+                completed.article_successes += 1;
+            } else if !state.is_not_requested() {
+                completed.article_failures += 1;
+            }
+        }
+        completed
+    }
+
     /// Clear all cached articles and categories.
     #[inline]
     pub fn clear(&mut self) {
@@ -99,9 +335,23 @@ impl<TExtractor: Extractor> Extrablatt<TExtractor> {
         self.categories.clear()
     }
 
+    /// The categories linked in the `doc`, see [`Extractor::categories`].
+    ///
+    /// The query of the category urls is only kept with
+    /// [`ConfigBuilder::strip_category_query`] disabled, in which case
+    /// [`Extractor::categories_with`] is used instead.
+    fn extract_categories(&self, doc: &Document) -> Vec<Category> {
+        if self.config.strip_category_query {
+            self.extractor.categories(doc, &self.base_url)
+        } else {
+            self.extractor.categories_with(doc, &self.base_url, false)
+        }
+    }
+
     /// Insert all categories extracted from the main page.
     fn insert_new_categories(&mut self) {
-        for category in self.extractor.categories(&self.main_page, &self.base_url) {
+        let categories = self.extract_categories(&self.main_page);
+        for category in categories {
             self.categories
                 .entry(category)
                 .or_insert(DocumentDownloadState::NotRequested);
@@ -111,6 +361,11 @@ impl<TExtractor: Extractor> Extrablatt<TExtractor> {
     /// Download and store all outstanding articles and returns an iterator over
     /// their results.
     ///
+    /// Articles that were redirected are stored under their final url, so
+    /// that several urls redirecting to the same article are only stored
+    /// once. Their original urls are kept as
+    /// [`DocumentDownloadState::Redirected`], so they aren't requested again.
+    ///
     /// # Example
     ///
     /// Loop over all downloaded articles.
@@ -128,33 +383,180 @@ impl<TExtractor: Extractor> Extrablatt<TExtractor> {
     /// # }
     /// ```
     pub async fn download_articles(&mut self) -> ArticleDownloadIter<'_, TExtractor> {
-        let results = stream::iter(
-            self.articles
-                .iter()
-                .filter_map(|(article, state)| {
-                    if state.is_not_requested() {
-                        Some(article.url.clone())
-                    } else {
-                        None
-                    }
-                })
-                .map(|url| {
-                    self.client.get(url.clone()).send().then(|res| async {
-                        (url, DocumentDownloadState::from_response(res).await)
-                    })
-                }),
+        self.download_articles_with(|_, _| {}).await
+    }
+
+    /// Same as [`Extrablatt::download_articles`], but invokes `on_progress`
+    /// with the article's url and its new state as soon as each download
+    /// resolves, e.g. to report progress.
+    pub async fn download_articles_with<F>(
+        &mut self,
+        mut on_progress: F,
+    ) -> ArticleDownloadIter<'_, TExtractor>
+    where
+        F: FnMut(&Url, &DocumentDownloadState),
+    {
+        let mut urls: Vec<_> = self
+            .articles
+            .iter()
+            .filter_map(|(article, state)| {
+                if state.is_not_requested() {
+                    Some(article.url.clone())
+                } else {
+                    None
+                }
+            })
+            .collect();
+        self.sort_by_recency(&mut urls);
+        self.download_article_urls(urls.clone(), &mut on_progress)
+            .await;
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.config.download_media {
+            self.download_article_media(&urls).await;
+        }
+
+        ArticleDownloadIter {
+            inner: self.articles.iter(),
+            extractor: &self.extractor,
+            language: self.language.clone(),
+            base_url: &self.base_url,
+            config: &self.config,
+            media: &self.media,
+        }
+    }
+
+    /// Downloads the images and video thumbnails of the downloaded articles
+    /// with the `urls` that weren't downloaded before, see
+    /// [`Config::download_media`].
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn download_article_media(&mut self, urls: &[Url]) {
+        let mut media_urls = Vec::new();
+        for doc in urls
+            .iter()
+            .filter_map(|url| self.article_state(url)?.success_document())
+        {
+            let content = self.config.extract_content(
+                &self.extractor,
+                doc,
+                Some(&self.base_url),
+                Some(self.language.clone()),
+            );
+            for url in content.media_urls() {
+                if !self.media.contains_key(&url) && !media_urls.contains(&url) {
+                    media_urls.push(url);
+                }
+            }
+        }
+        let media = download_all_bytes(
+            &self.client,
+            media_urls,
+            self.config.max_concurrent_requests,
+            self.config.max_body_bytes,
         )
-        .buffer_unordered(10)
-        .collect::<Vec<_>>()
         .await;
+        self.media.extend(media);
+    }
+
+    /// Crawls the site breadth-first.
+    ///
+    /// Starts with the unrequested articles and those linked on the main page,
+    /// then downloads all articles linked from the already downloaded ones,
+    /// following links up to `max_depth` times. Articles are only downloaded
+    /// once and at most `max_articles` are downloaded in total. Returns the
+    /// number of downloaded articles.
+    pub async fn spider(&mut self, max_depth: usize, max_articles: usize) -> usize {
+        for article in self.article_urls(&self.main_page) {
+            self.articles
+                .entry(article)
+                .or_insert(DocumentDownloadState::NotRequested);
+        }
+        let mut frontier: Vec<_> = self
+            .articles
+            .iter()
+            .filter(|(_, state)| state.is_not_requested())
+            .map(|(article, _)| article.url.clone())
+            .collect();
+        self.sort_by_recency(&mut frontier);
+
+        let mut downloaded = 0;
+        for depth in 0..=max_depth {
+            frontier.truncate(max_articles - downloaded);
+            if frontier.is_empty() {
+                break;
+            }
+            downloaded += frontier.len();
+            self.download_article_urls(frontier.clone(), &mut |_, _| {})
+                .await;
+            if depth == max_depth {
+                break;
+            }
+
+            let linked: Vec<_> = frontier
+                .iter()
+                .filter_map(|url| self.article_state(url)?.success_document())
+                .flat_map(|doc| self.article_urls(doc))
+                .collect();
+            frontier.clear();
+            for article in linked {
+                if let Entry::Vacant(entry) = self.articles.entry(article) {
+                    frontier.push(entry.key().url.clone());
+                    entry.insert(DocumentDownloadState::NotRequested);
+                }
+            }
+        }
+        downloaded
+    }
+
+    /// The state of the article with the `url`, or of the article it was
+    /// redirected to.
+    fn article_state(&self, url: &Url) -> Option<&DocumentDownloadState> {
+        match self.articles.get(url)? {
+            DocumentDownloadState::Redirected { to } => self.articles.get(to),
+            state => Some(state),
+        }
+    }
+
+    /// Downloads the articles with the `urls` and stores their new state.
+    async fn download_article_urls<F>(&mut self, urls: Vec<Url>, on_progress: &mut F)
+    where
+        F: FnMut(&Url, &DocumentDownloadState),
+    {
+        let client = &self.client;
+        let config = &self.config;
+        let jitter = &self.jitter;
+        let mut results = stream::iter(urls.into_iter().map(|url| {
+            let options = config.read_options();
+            let delay = config.request_jitter.map(|max| jitter.delay(max));
+            let mut request = client.get(url.clone());
+            if let Some(headers) = config.headers_for(&url) {
+                request = request.headers(headers.clone());
+            }
+            async move {
+                if let Some(delay) = delay {
+                    let _ = Delay::new(delay).await;
+                }
+                let res = request.send().await;
+                let final_url = res.as_ref().ok().map(|resp| resp.url().clone());
+                (
+                    url,
+                    final_url,
+                    DocumentDownloadState::from_response(res, options).await,
+                )
+            }
+        }))
+        .buffer_unordered(config.max_concurrent_requests.max(1));
 
-        for (url, doc) in results {
+        while let Some((url, final_url, doc)) = results.next().await {
             let state = match doc {
                 Ok((doc, received)) => DocumentDownloadState::Success { received, doc },
                 Err((state, err)) => {
-                    if !self.config.http_success_only {
+                    if !config.http_success_only {
                         if let Ok((doc, received)) =
-                            DocumentDownloadState::advance_non_http_success(err).await
+                            DocumentDownloadState::advance_non_http_success(
+                                err,
+                                config.read_options(),
+                            )
+                            .await
                         {
                             DocumentDownloadState::Success { doc, received }
                         } else {
@@ -166,14 +568,27 @@ impl<TExtractor: Extractor> Extrablatt<TExtractor> {
                 }
             };
 
-            *self.articles.get_mut(&url).unwrap() = state;
-        }
-
-        ArticleDownloadIter {
-            inner: self.articles.iter(),
-            extractor: &self.extractor,
-            language: self.language.clone(),
-            base_url: &self.base_url,
+            on_progress(&url, &state);
+            match final_url.filter(|final_url| *final_url != url && state.is_success()) {
+                Some(final_url) => {
+                    // collapse redirected articles onto their final url, so that articles
+                    // redirecting to the same one are only stored once
+                    let is_downloaded = self
+                        .articles
+                        .get(&final_url)
+                        .map(DocumentDownloadState::is_success)
+                        .unwrap_or_default();
+                    if !is_downloaded {
+                        let mut article =
+                            ArticleUrl::clone(self.articles.get_key_value(&url).unwrap().0);
+                        article.url = final_url.clone();
+                        self.articles.insert(article, state);
+                    }
+                    *self.articles.get_mut(&url).unwrap() =
+                        DocumentDownloadState::Redirected { to: final_url };
+                }
+                None => *self.articles.get_mut(&url).unwrap() = state,
+            }
         }
     }
 
@@ -184,13 +599,149 @@ impl<TExtractor: Extractor> Extrablatt<TExtractor> {
             extractor: &self.extractor,
             language: self.language.clone(),
             base_url: &self.base_url,
+            config: &self.config,
+            media: &self.media,
+        }
+    }
+
+    /// The content of all successfully downloaded articles that satisfy the
+    /// `predicate`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use extrablatt::Extrablatt;
+    /// # async fn run() -> anyhow::Result<()> {
+    /// let mut paper = Extrablatt::builder("https://some-news.com/")?.build().await?;
+    /// let _ = paper.download_articles().await;
+    /// let budget = paper.articles_matching(|content| {
+    ///     content.keywords.iter().any(|keyword| keyword == "budget")
+    /// });
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn articles_matching<F: Fn(&ArticleContent) -> bool>(
+        &self,
+        predicate: F,
+    ) -> Vec<(ArticleUrl, ArticleContent<'static>)> {
+        self.iter_articles()
+            .successes()
+            .filter(|(_, content)| predicate(content))
+            .map(|(url, content)| (url.clone(), content.into_owned()))
+            .collect()
+    }
+
+    /// All article urls of the `doc`, including those identified by the
+    /// configured query parameters.
+    fn article_urls(&self, doc: &Document) -> Vec<ArticleUrl> {
+        let mut urls = self.extractor.article_urls_with_query_params(
+            doc,
+            Some(&self.base_url),
+            &self.config.article_id_query_params,
+        );
+        if self.config.normalize_to_desktop {
+            for article in &mut urls {
+                if let Some(url) = desktop_variant(&article.url) {
+                    article.url = url;
+                }
+            }
+        }
+        urls
+    }
+
+    /// Inserts the articles of the Google News sitemaps listed in the site's
+    /// `robots.txt` as unrequested and stores their [`NewsMetadata`].
+    ///
+    /// Sitemap indexes are followed one level deep, but only to sitemaps with
+    /// `news` in their url, and each sitemap is requested once. Returns the
+    /// number of newly inserted articles.
+    pub async fn insert_news_sitemap_articles(&mut self) -> usize {
+        let robots = match self.base_url.join("/robots.txt") {
+            Ok(url) => self.get_text(url).await,
+            Err(_) => None,
+        };
+        let mut sitemaps: Vec<_> = robots
+            .map(|robots| sitemap::robots_sitemap_urls(&robots, Some(&self.base_url)))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|url| (url, 0))
+            .collect();
+
+        let mut visited = FnvHashSet::default();
+        let mut entries = Vec::new();
+        while let Some((url, depth)) = sitemaps.pop() {
+            if !visited.insert(url.clone()) {
+                continue;
+            }
+            let xml = match self.get_text(url.clone()).await {
+                Some(xml) => xml,
+                None => continue,
+            };
+            if sitemap::is_sitemap_index(&xml) {
+                if depth > 0 {
+                    continue;
+                }
+                sitemaps.extend(
+                    sitemap::sitemap_index_urls(&xml, Some(&url))
+                        .into_iter()
+                        .filter(|url| url.as_str().contains("news"))
+                        .map(|url| (url, depth + 1)),
+                );
+            } else {
+                entries.extend(sitemap::parse_news_sitemap(&xml, Some(&url)));
+            }
+        }
+
+        let mut inserted = 0;
+        for (article, metadata) in entries {
+            if !TExtractor::is_article(&article, &self.base_url) {
+                continue;
+            }
+            self.news_metadata.insert(article.url.clone(), metadata);
+            if let Entry::Vacant(entry) = self.articles.entry(article) {
+                entry.insert(DocumentDownloadState::NotRequested);
+                inserted += 1;
+            }
+        }
+        inserted
+    }
+
+    /// The metadata of the article with the `url` from a Google News sitemap,
+    /// see [`Extrablatt::insert_news_sitemap_articles`].
+    pub fn news_metadata(&self, url: &Url) -> Option<&NewsMetadata> {
+        self.news_metadata.get(url)
+    }
+
+    /// Orders the `urls` by their [`NewsMetadata::publication_date`], most
+    /// recent first and urls without a date last, otherwise by the url.
+    fn sort_by_recency(&self, urls: &mut [Url]) {
+        let date = |url: &Url| {
+            self.news_metadata
+                .get(url)
+                .and_then(|metadata| metadata.publication_date)
+        };
+        urls.sort_by(|a, b| {
+            Reverse(date(a))
+                .cmp(&Reverse(date(b)))
+                .then_with(|| a.as_str().cmp(b.as_str()))
+        });
+    }
+
+    /// The body of a successful response for the `url`, if it doesn't exceed
+    /// the [`ConfigBuilder::max_body_bytes`].
+    async fn get_text(&self, url: Url) -> Option<String> {
+        let resp = self.request(url).send().await.ok()?;
+        if !resp.status().is_success() {
+            return None;
         }
+        let body = read_body(resp, self.config.max_body_bytes).await.ok()?;
+        Some(String::from_utf8_lossy(&body).into_owned())
     }
 
     /// For each successfully downloaded category document, insert their article
     /// urls as unrequested.
     fn insert_article_urls(&mut self, doc: &Document) {
-        for url in self.extractor.article_urls(doc, Some(&self.base_url)) {
+        for url in self.article_urls(doc) {
             self.articles
                 .entry(url)
                 .or_insert(DocumentDownloadState::NotRequested);
@@ -214,14 +765,20 @@ impl<TExtractor: Extractor> Extrablatt<TExtractor> {
             return Ok(self.categories[&category].success_document().unwrap());
         }
 
-        let result = match self.get_document(category.url.clone()).await {
+        let request = self.category_request(category.url.clone());
+        let result = match self.get_document(request).await {
             Ok((doc, received)) => {
                 self.insert_article_urls(&doc);
                 Ok((doc, received))
             }
             Err((state, err)) => {
                 if !self.config.http_success_only {
-                    match DocumentDownloadState::advance_non_http_success(err).await {
+                    match DocumentDownloadState::advance_non_http_success(
+                        err,
+                        self.config.read_options(),
+                    )
+                    .await
+                    {
                         Ok((doc, received)) => {
                             self.insert_article_urls(&doc);
                             Ok((doc, received))
@@ -255,13 +812,22 @@ impl<TExtractor: Extractor> Extrablatt<TExtractor> {
         &mut self,
         items: Vec<Category>,
     ) -> Vec<std::result::Result<Category, (Category, ExtrablattError)>> {
+        let options = self.config.read_options();
         let requests = stream::iter(items.into_iter().map(|cat| {
-            self.client
-                .get(cat.url.clone())
-                .send()
-                .then(|res| async { (cat, DocumentDownloadState::from_response(res).await) })
+            let request = self.category_request(cat.url.clone());
+            let delay = self.jitter_delay();
+            async move {
+                if let Some(delay) = delay {
+                    let _ = Delay::new(delay).await;
+                }
+                let res = request.send().await;
+                (
+                    cat,
+                    DocumentDownloadState::from_response(res, options).await,
+                )
+            }
         }))
-        .buffer_unordered(10)
+        .buffer_unordered(self.config.max_concurrent_requests.max(1))
         .collect::<Vec<_>>()
         .await;
         let mut results = Vec::with_capacity(requests.len());
@@ -276,7 +842,12 @@ impl<TExtractor: Extractor> Extrablatt<TExtractor> {
                 }
                 Err((state, err)) => {
                     if !self.config.http_success_only {
-                        match DocumentDownloadState::advance_non_http_success(err).await {
+                        match DocumentDownloadState::advance_non_http_success(
+                            err,
+                            self.config.read_options(),
+                        )
+                        .await
+                        {
                             Ok((doc, received)) => {
                                 *self.categories.get_mut(&cat).unwrap() =
                                     DocumentDownloadState::Success { doc, received };
@@ -340,7 +911,7 @@ impl<TExtractor: Extractor> Extrablatt<TExtractor> {
     /// document.
     pub async fn refresh_homepage(&mut self) -> std::result::Result<Document, ExtrablattError> {
         let (main_page, _) = self
-            .get_document(self.base_url.clone())
+            .get_document(self.request(self.base_url.clone()))
             .await
             .map_err(|(_, err)| err)?;
 
@@ -350,14 +921,46 @@ impl<TExtractor: Extractor> Extrablatt<TExtractor> {
         Ok(std::mem::replace(&mut self.main_page, main_page))
     }
 
-    /// Execute a GET request and return the response wrapped in
+    /// Execute the GET `request` and return the response wrapped in
     /// [`DocumentDownloadState`].
     async fn get_document(
         &self,
-        url: Url,
+        request: RequestBuilder,
     ) -> std::result::Result<(Document, Instant), (DocumentDownloadState, ExtrablattError)> {
-        let resp = self.client.get(url).send().await;
-        DocumentDownloadState::from_response(resp).await
+        let resp = request.send().await;
+        DocumentDownloadState::from_response(resp, self.config.read_options()).await
+    }
+
+    /// A random delay before the next request, if
+    /// [`Config::request_jitter`] is set.
+    fn jitter_delay(&self) -> Option<Duration> {
+        self.config.request_jitter.map(|max| self.jitter.delay(max))
+    }
+
+    /// A GET request for the `url`, with the [`Config::headers_for`] the
+    /// `url`.
+    fn request(&self, url: Url) -> RequestBuilder {
+        let headers = self.config.headers_for(&url).cloned();
+        let request = self.client.get(url);
+        match headers {
+            Some(headers) => request.headers(headers),
+            None => request,
+        }
+    }
+
+    /// Same as [`Extrablatt::request`], but if the category's `url` hints at
+    /// a language, see [`Category::language_hint`], its identifier is sent as
+    /// `Accept-Language`, unless a [`Config::accept_language`] is configured.
+    fn category_request(&self, url: Url) -> RequestBuilder {
+        let language = match self.config.accept_language {
+            Some(_) => None,
+            None => Category::new(url.clone()).language_hint(),
+        };
+        let request = self.request(url);
+        match language {
+            Some(language) => request.header(ACCEPT_LANGUAGE, language.identifier()),
+            None => request,
+        }
     }
 
     #[cfg(feature = "archive")]
@@ -369,30 +972,43 @@ impl<TExtractor: Extractor> Extrablatt<TExtractor> {
 impl<TExtractor: Extractor + Unpin> Extrablatt<TExtractor> {
     /// Converts the newspaper into a stream, yielding all available
     /// [`crate::Article`]s.
-    pub fn into_stream(
-        mut self,
-    ) -> impl Stream<Item = std::result::Result<Article, ExtrablattError>> {
+    ///
+    /// Requests are sent lazily, at most [`Config::max_concurrent_requests`]
+    /// at a time, and at most [`Config::max_doc_cache`] articles are queued,
+    /// see [`Extrablatt::sort_by_recency`] for which are dropped. Articles
+    /// that were already downloaded are always yielded.
+    ///
+    /// If [`Config::download_media`] is enabled, the media of each article is
+    /// downloaded before it's yielded.
+    ///
+    /// See [`ArticleStream::with_cancel`] to stop the stream early and
+    /// [`ArticleStream::events`] to follow the crawl's progress.
+    pub fn into_stream(mut self) -> ArticleStream<TExtractor> {
         let mut articles = Vec::new();
-        let mut article_responses = Vec::new();
+        let mut pending_articles = VecDeque::new();
+        let mut events = VecDeque::new();
+        let mut seen_urls = FnvHashSet::default();
 
         let mut extracted = FnvHashMap::default();
         std::mem::swap(&mut extracted, &mut self.articles);
 
         for (article_url, doc) in extracted.into_iter() {
+            if !seen_urls.insert(article_url.url.clone()) {
+                continue;
+            }
             match doc {
                 DocumentDownloadState::NotRequested => {
-                    article_responses.push(self.get_response(article_url.url));
+                    pending_articles.push_back(article_url.url);
                 }
                 DocumentDownloadState::Success { doc, .. } => {
+                    let content = self.config.extract_content(
+                        &self.extractor,
+                        &doc,
+                        Some(&self.base_url),
+                        Some(self.language.clone()),
+                    );
                     let article = Article {
-                        content: self
-                            .extractor
-                            .article_content(
-                                &doc,
-                                Some(&self.base_url),
-                                Some(self.language.clone()),
-                            )
-                            .into_owned(),
+                        content: content.into_owned(),
                         url: article_url.url,
                         language: self
                             .extractor
@@ -405,16 +1021,25 @@ impl<TExtractor: Extractor + Unpin> Extrablatt<TExtractor> {
                 _ => {}
             }
         }
+        self.sort_by_recency(pending_articles.make_contiguous());
+        // the least recent articles are dropped
+        pending_articles.truncate(self.config.max_doc_cache);
+        events.extend(
+            pending_articles
+                .iter()
+                .cloned()
+                .map(CrawlEvent::ArticleQueued),
+        );
 
         let mut categories = Vec::new();
-        let mut category_responses = Vec::new();
+        let mut pending_categories = VecDeque::new();
         let mut extracted = FnvHashMap::default();
         std::mem::swap(&mut extracted, &mut self.categories);
 
         for (cat, doc) in extracted {
             match doc {
                 DocumentDownloadState::NotRequested => {
-                    category_responses.push(self.get_response(cat.url));
+                    pending_categories.push_back(cat.url);
                 }
                 DocumentDownloadState::Success { doc, .. } => {
                     categories.push((cat, doc));
@@ -425,38 +1050,108 @@ impl<TExtractor: Extractor + Unpin> Extrablatt<TExtractor> {
 
         ArticleStream {
             paper: self,
-            article_responses,
+            article_responses: Vec::new(),
+            media_downloads: Vec::new(),
             articles,
             categories,
-            category_responses,
+            category_responses: Vec::new(),
+            pending_articles,
+            pending_categories,
+            seen_urls,
+            attempts: Default::default(),
+            seen_hashes: Default::default(),
+            max_pages: 1,
+            category_pages: Default::default(),
+            cancel: None,
+            events,
+            done: false,
         }
     }
 
     fn get_response(&self, url: Url) -> PaperResponse {
-        Box::pin(
-            self.client
-                .get(url.clone())
-                .send()
-                .map_err(|error| ExtrablattError::HttpRequestFailure { error })
-                .and_then(|response| async {
-                    if !response.status().is_success() {
-                        Err(ExtrablattError::NoHttpSuccessResponse { response })
-                    } else {
-                        response
-                            .bytes()
-                            .await
-                            .map(|bytes| (url, bytes))
-                            .map_err(|error| ExtrablattError::HttpRequestFailure { error })
-                    }
-                }),
-        )
+        self.get_delayed_response(self.request(url.clone()), url, None)
+    }
+
+    /// Same as [`Extrablatt::get_response`], but for a category, see
+    /// [`Extrablatt::category_request`].
+    fn get_category_response(&self, url: Url) -> PaperResponse {
+        self.get_delayed_response(self.category_request(url.clone()), url, None)
+    }
+
+    /// Sends the `request` for the `url` after the `delay`.
+    fn get_delayed_response(
+        &self,
+        request: RequestBuilder,
+        url: Url,
+        delay: Option<Duration>,
+    ) -> PaperResponse {
+        let delay = match (delay, self.jitter_delay()) {
+            (Some(delay), Some(jitter)) => Some(delay + jitter),
+            (delay, jitter) => delay.or(jitter),
+        };
+        let limit = self.config.max_body_bytes;
+        Box::pin(async move {
+            if let Some(delay) = delay {
+                let _ = Delay::new(delay).await;
+            }
+            let response = match request.send().await {
+                Ok(response) => response,
+                Err(error) => return Err((url, ExtrablattError::HttpRequestFailure { error })),
+            };
+            if !response.status().is_success() {
+                return Err((url, ExtrablattError::NoHttpSuccessResponse { response }));
+            }
+            match read_body(response, limit).await {
+                Ok(bytes) => Ok((url, bytes)),
+                Err(error) => Err((url, error)),
+            }
+        })
+    }
+}
+
+/// Generates random delays of [`Config::request_jitter`] with a splitmix64
+/// generator.
+#[derive(Debug)]
+pub(crate) struct Jitter {
+    state: AtomicU64,
+}
+
+impl Jitter {
+    /// Seeds the generator with the `seed` or the current time.
+    pub(crate) fn new(seed: Option<u64>) -> Self {
+        let seed = seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|time| time.as_nanos() as u64)
+                .unwrap_or_default()
+        });
+        Self {
+            state: AtomicU64::new(seed),
+        }
+    }
+
+    /// A random delay in `[0, max)`.
+    fn delay(&self, max: Duration) -> Duration {
+        const GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+        let max = max.as_nanos() as u64;
+        if max == 0 {
+            return Duration::default();
+        }
+        let mut z = self
+            .state
+            .fetch_add(GAMMA, atomic::Ordering::Relaxed)
+            .wrapping_add(GAMMA);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        Duration::from_nanos(z % max)
     }
 }
 
 type PaperResponse =
-    Pin<Box<dyn Future<Output = std::result::Result<(Url, Bytes), ExtrablattError>>>>;
+    Pin<Box<dyn Future<Output = std::result::Result<(Url, Bytes), (Url, ExtrablattError)>>>>;
 
-type ReadyResponse = (usize, std::result::Result<(Url, Bytes), ExtrablattError>);
+type MediaDownload = Pin<Box<dyn Future<Output = Article>>>;
 
 /// Stream for getting a `Article` each at a time.
 #[must_use = "streams do nothing unless polled"]
@@ -467,10 +1162,48 @@ pub struct ArticleStream<TExtractor: Extractor> {
     article_responses: Vec<PaperResponse>,
     /// Pending responses for Category html.
     category_responses: Vec<PaperResponse>,
+    /// Articles whose media is being downloaded, see
+    /// [`Config::download_media`].
+    media_downloads: Vec<MediaDownload>,
+    /// Article urls that weren't requested yet.
+    pending_articles: VecDeque<Url>,
+    /// Category urls that weren't requested yet.
+    pending_categories: VecDeque<Url>,
     /// Articles already available.
     articles: Vec<Article>,
     /// Categories already available.
     categories: Vec<(Category, Document)>,
+    /// All article urls that were already queued or yielded.
+    seen_urls: FnvHashSet<Url>,
+    /// Number of requests sent for urls that failed before.
+    attempts: FnvHashMap<Url, usize>,
+    /// Content hashes of the yielded articles.
+    seen_hashes: FnvHashSet<u64>,
+    /// Max. number of pages to follow of each paginated category.
+    max_pages: usize,
+    /// The page number of all category pages that were already queued.
+    category_pages: FnvHashMap<Url, usize>,
+    /// Stops the stream once set, see [`ArticleStream::with_cancel`].
+    cancel: Option<Arc<AtomicBool>>,
+    /// Events that weren't yielded yet, see [`ArticleStream::events`].
+    events: VecDeque<CrawlEvent>,
+    /// Whether the [`CrawlEvent::Done`] was already yielded.
+    done: bool,
+}
+
+/// The progress of an [`ArticleStream`], see [`ArticleStream::events`].
+#[derive(Debug)]
+pub enum CrawlEvent {
+    /// The articles of the category, or the main page, were queued.
+    CategoryFetched(Url),
+    /// A new article url was queued for download.
+    ArticleQueued(Url),
+    /// An article was downloaded and is complete.
+    ArticleReady(Box<Article>),
+    /// A request failed or the article is incomplete.
+    Error(ExtrablattError),
+    /// The stream ended.
+    Done,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -508,47 +1241,238 @@ impl<TExtractor: Extractor + Unpin> ArticleStream<TExtractor> {
     pub async fn new_with_extractor<T: IntoUrl>(
         url: T,
         extractor: TExtractor,
+    ) -> Result<ArticleStream<TExtractor>> {
+        Self::new_paginated_with_extractor(url, 1, extractor).await
+    }
+
+    /// Same as [`ArticleStream::new_with_extractor`], but also follows the
+    /// pagination of the page and of the categories, up to `max_pages` pages
+    /// each.
+    pub(crate) async fn new_paginated_with_extractor<T: IntoUrl>(
+        url: T,
+        max_pages: usize,
+        extractor: TExtractor,
     ) -> Result<ArticleStream<TExtractor>> {
         let paper = ExtrablattBuilder::new(url)?
             .build_with_extractor(extractor)
             .await?;
 
-        let article_responses = paper
-            .extractor()
-            .article_urls(&paper.main_page, Some(&paper.base_url))
-            .into_iter()
-            .map(|article_url| paper.get_response(article_url.url))
-            .collect();
-
-        Ok(ArticleStream {
+        let main_page = paper.main_page.clone();
+        let base_url = paper.base_url.clone();
+        let mut stream = ArticleStream {
             paper,
-            article_responses,
-            articles: Default::default(),
-            categories: Default::default(),
-            category_responses: Default::default(),
-        })
+            article_responses: Vec::new(),
+            media_downloads: Vec::new(),
+            articles: Vec::new(),
+            categories: Vec::new(),
+            category_responses: Vec::new(),
+            pending_articles: VecDeque::new(),
+            pending_categories: VecDeque::new(),
+            seen_urls: Default::default(),
+            attempts: Default::default(),
+            seen_hashes: Default::default(),
+            max_pages,
+            category_pages: Default::default(),
+            cancel: None,
+            events: Default::default(),
+            done: false,
+        };
+        stream.queue_category_articles(&base_url, &main_page);
+        Ok(stream)
     }
 
-    /// Queue in new requests for articles.
-    fn queue_category_articles(&mut self, doc: &Document) {
-        for article_url in self
-            .paper
-            .extractor
-            .article_urls(&doc, Some(&self.paper.base_url))
-            .into_iter()
-        {
-            self.article_responses
-                .push(self.paper.get_response(article_url.url));
-        }
+    /// Stops the stream once the `cancel` flag is set.
+    ///
+    /// A cancelled stream sends no new requests and drops the pending ones,
+    /// only the articles that are already available are still yielded before
+    /// it ends. The flag is checked whenever the stream is polled.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::sync::atomic::{AtomicBool, Ordering};
+    /// # use std::sync::Arc;
+    /// # use extrablatt::ArticleStream;
+    /// # use futures::StreamExt;
+    /// # async fn run() -> anyhow::Result<()> {
+    /// let cancel = Arc::new(AtomicBool::new(false));
+    /// let mut stream = ArticleStream::new("https://some-news.com/")
+    ///     .await?
+    ///     .with_cancel(Arc::clone(&cancel));
+    /// while let Some(_article) = stream.next().await {
+    ///     // stop after the first article
+    ///     cancel.store(true, Ordering::SeqCst);
+    /// }
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn with_cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
     }
 
-    /// Poll each item and return the index together with the response of first
-    /// ready future.
-    fn find_ready_response(
-        items: &mut [PaperResponse],
-        cx: &mut core::task::Context<'_>,
-    ) -> Option<ReadyResponse> {
-        items
+    /// Converts the stream into a stream of all [`CrawlEvent`]s, which also
+    /// reports the fetched categories and the queued article urls.
+    ///
+    /// The last event is always a [`CrawlEvent::Done`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use extrablatt::{ArticleStream, CrawlEvent};
+    /// # use futures::StreamExt;
+    /// # async fn run() -> anyhow::Result<()> {
+    /// let mut events = ArticleStream::new("https://some-news.com/").await?.events();
+    /// while let Some(event) = events.next().await {
+    ///     match event {
+    ///         CrawlEvent::CategoryFetched(url) => println!("category {}", url),
+    ///         CrawlEvent::ArticleQueued(url) => println!("queued {}", url),
+    ///         CrawlEvent::ArticleReady(article) => println!("article {}", article.url),
+    ///         CrawlEvent::Error(err) => println!("error {}", err),
+    ///         CrawlEvent::Done => {}
+    ///     }
+    /// }
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn events(mut self) -> impl Stream<Item = CrawlEvent> {
+        stream::poll_fn(move |cx| self.poll_event(cx))
+    }
+
+    /// Whether the stream was cancelled, see [`ArticleStream::with_cancel`].
+    fn is_cancelled(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .map(|cancel| cancel.load(atomic::Ordering::SeqCst))
+            .unwrap_or_default()
+    }
+
+    /// Queue in new requests for articles of the category that weren't queued
+    /// before, and for its next page if the category is paginated.
+    fn queue_category_articles(&mut self, url: &Url, doc: &Document) {
+        self.events
+            .push_back(CrawlEvent::CategoryFetched(url.clone()));
+        let article_urls = self.paper.article_urls(doc);
+        self.queue_article_urls(article_urls);
+
+        let page = *self.category_pages.entry(url.clone()).or_insert(1);
+        if page >= self.max_pages {
+            return;
+        }
+        if let Some(next) = self.paper.extractor.next_page_url(doc, url, page) {
+            // pages that were already queued are never requested again
+            if let Entry::Vacant(entry) = self.category_pages.entry(next.clone()) {
+                entry.insert(page + 1);
+                self.pending_categories.push_back(next);
+            }
+        }
+    }
+
+    /// Queue in new article urls that weren't queued before.
+    ///
+    /// At most [`Config::max_doc_cache`] urls are pending, the urls that are
+    /// pending the longest are dropped for new ones.
+    fn queue_article_urls(&mut self, article_urls: Vec<ArticleUrl>) {
+        let max_doc_cache = self.paper.config.max_doc_cache;
+        if max_doc_cache == 0 {
+            return;
+        }
+        for article_url in article_urls {
+            if self.seen_urls.insert(article_url.url.clone()) {
+                if self.pending_articles.len() >= max_doc_cache {
+                    self.pending_articles.pop_front();
+                }
+                self.events
+                    .push_back(CrawlEvent::ArticleQueued(article_url.url.clone()));
+                self.pending_articles.push_back(article_url.url);
+            }
+        }
+    }
+
+    /// Sends requests for pending articles and categories, so that at most
+    /// [`Config::max_concurrent_requests`] are in flight, including the
+    /// articles whose media is being downloaded.
+    fn fill_requests(&mut self) {
+        let max = self.paper.config.max_concurrent_requests.max(1);
+        while self.article_responses.len()
+            + self.category_responses.len()
+            + self.media_downloads.len()
+            < max
+        {
+            if let Some(url) = self.pending_articles.pop_front() {
+                self.article_responses.push(self.paper.get_response(url));
+            } else if let Some(url) = self.pending_categories.pop_front() {
+                self.category_responses
+                    .push(self.paper.get_category_response(url));
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Whether an article with the same [`ArticleContent::content_hash`] was
+    /// already yielded, if [`Config::dedupe_by_content`] is enabled.
+    fn is_duplicate_content(&mut self, content: &ArticleContent) -> bool {
+        self.paper.config.dedupe_by_content && !self.seen_hashes.insert(content.content_hash())
+    }
+
+    /// Creates a new request for the `url` of an article or a `category` if
+    /// the `error` is retriable and the configured number of attempts isn't
+    /// exhausted yet.
+    fn retry(
+        &mut self,
+        url: &Url,
+        error: &ExtrablattError,
+        category: bool,
+    ) -> Option<PaperResponse> {
+        if !error.is_retriable() {
+            return None;
+        }
+        let attempts = self.attempts.entry(url.clone()).or_insert(1);
+        if *attempts >= self.paper.config.retry.max_attempts {
+            return None;
+        }
+        *attempts += 1;
+        let request = if category {
+            self.paper.category_request(url.clone())
+        } else {
+            self.paper.request(url.clone())
+        };
+        Some(self.paper.get_delayed_response(
+            request,
+            url.clone(),
+            Some(self.paper.config.retry.delay),
+        ))
+    }
+
+    /// Yields the `article`, or first downloads its media if
+    /// [`Config::download_media`] is enabled.
+    fn article_ready(&mut self, article: Article) -> Option<CrawlEvent> {
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.paper.config.download_media {
+            let client = self.paper.client.clone();
+            let max_concurrent_requests = self.paper.config.max_concurrent_requests;
+            let max_body_bytes = self.paper.config.max_body_bytes;
+            self.media_downloads.push(Box::pin(async move {
+                let mut article = article;
+                article
+                    .content
+                    .download_media(&client, max_concurrent_requests, max_body_bytes)
+                    .await;
+                article
+            }));
+            return None;
+        }
+        Some(CrawlEvent::ArticleReady(Box::new(article)))
+    }
+
+    /// Poll each item and return the index together with the response of first
+    /// ready future.
+    fn find_ready_response<T>(
+        items: &mut [Pin<Box<dyn Future<Output = T>>>],
+        cx: &mut core::task::Context<'_>,
+    ) -> Option<(usize, T)> {
+        items
             .iter_mut()
             .enumerate()
             .find_map(|(i, f)| match f.as_mut().poll(cx) {
@@ -556,102 +1480,183 @@ impl<TExtractor: Extractor + Unpin> ArticleStream<TExtractor> {
                 Poll::Ready(resp) => Some((i, resp)),
             })
     }
-}
-
-impl<TExtractor: Extractor + Unpin> Stream for ArticleStream<TExtractor> {
-    type Item = std::result::Result<Article, ExtrablattError>;
 
-    fn poll_next(
-        mut self: Pin<&mut Self>,
-        cx: &mut core::task::Context<'_>,
-    ) -> Poll<Option<Self::Item>> {
-        if let Some(article) = self.articles.pop() {
-            return Poll::Ready(Some(Ok(article)));
+    /// Advances the crawl and returns its next [`CrawlEvent`].
+    ///
+    /// Yields [`CrawlEvent::Done`] once, before the stream ends.
+    fn poll_event(&mut self, cx: &mut core::task::Context<'_>) -> Poll<Option<CrawlEvent>> {
+        if self.done {
+            return Poll::Ready(None);
         }
-        if self.article_responses.is_empty() {
-            if let Some((_, doc)) = self.categories.pop() {
-                // add futures to article_response
-                self.queue_category_articles(&doc);
+        loop {
+            if let Some(event) = self.events.pop_front() {
+                return Poll::Ready(Some(event));
             }
-
-            if self.category_responses.is_empty() {
-                // nothing do anymore
-                return Poll::Ready(None);
+            if let Some(article) = self.articles.pop() {
+                if !self.is_duplicate_content(&article.content) {
+                    if let Some(event) = self.article_ready(article) {
+                        return Poll::Ready(Some(event));
+                    }
+                }
+                continue;
+            }
+            if let Some((idx, article)) = Self::find_ready_response(&mut self.media_downloads, cx) {
+                drop(self.media_downloads.swap_remove(idx));
+                return Poll::Ready(Some(CrawlEvent::ArticleReady(Box::new(article))));
+            }
+            if self.is_cancelled() {
+                self.article_responses.clear();
+                self.category_responses.clear();
+                if !self.media_downloads.is_empty() {
+                    return Poll::Pending;
+                }
+                return self.finish();
             }
+            self.fill_requests();
+            if self.article_responses.is_empty() {
+                if let Some((category, doc)) = self.categories.pop() {
+                    // queue the category's articles
+                    self.queue_category_articles(&category.url, &doc);
+                    continue;
+                }
 
-            // poll pending category futures to get new article futures
-            let item = Self::find_ready_response(&mut self.category_responses, cx);
+                if self.category_responses.is_empty() {
+                    if !self.media_downloads.is_empty() {
+                        return Poll::Pending;
+                    }
+                    // nothing do anymore
+                    return self.finish();
+                }
 
-            match item {
-                Some((idx, resp)) => {
-                    let _ = self.category_responses.swap_remove(idx);
-                    match resp {
-                        Ok((_, body)) => {
-                            if let Ok(doc) = Document::from_read(&*body) {
-                                self.queue_category_articles(&doc);
-                            } else {
-                                return Poll::Ready(Some(Err(
-                                    ExtrablattError::ReadDocumentError { body },
-                                )));
+                // poll pending category futures to get new article futures
+                let item = Self::find_ready_response(&mut self.category_responses, cx);
+
+                match item {
+                    Some((idx, resp)) => {
+                        let _ = self.category_responses.swap_remove(idx);
+                        match resp {
+                            Ok((url, body)) => {
+                                let lossy = self.paper.config.lossy_decode_fallback;
+                                if let Some(doc) = read_document(&body, lossy) {
+                                    self.queue_category_articles(&url, &doc);
+                                    continue;
+                                } else {
+                                    return Poll::Ready(Some(CrawlEvent::Error(
+                                        ExtrablattError::ReadDocumentError { body },
+                                    )));
+                                }
+                            }
+                            Err((url, e)) => {
+                                if let Some(resp) = self.retry(&url, &e, true) {
+                                    self.category_responses.push(resp);
+                                    continue;
+                                }
+                                return Poll::Ready(Some(CrawlEvent::Error(e)));
                             }
-                        }
-                        Err(e) => {
-                            return Poll::Ready(Some(Err(e)));
                         }
                     }
+                    None => return Poll::Pending,
                 }
-                None => return Poll::Pending,
-            }
-        }
-
-        let item = Self::find_ready_response(&mut self.article_responses, cx);
-
-        match item {
-            Some((idx, resp)) => {
-                let _ = self.article_responses.swap_remove(idx);
-                let article = match resp {
-                    Ok((url, body)) => {
-                        if let Ok(doc) = Document::from_read(&*body) {
-                            let content = self
-                                .paper
-                                .extractor
-                                .article_content(
-                                    &doc,
-                                    Some(&self.paper.base_url),
-                                    Some(self.paper.language.clone()),
-                                )
-                                .into_owned();
-                            let language = self
-                                .paper
-                                .extractor
-                                .meta_language(&doc)
-                                .unwrap_or_else(|| self.paper.language.clone());
-
-                            if self.paper.config.is_complete(&content) {
-                                Ok(Article {
-                                    url,
-                                    doc,
-                                    content,
-                                    language,
-                                })
-                            } else {
-                                Err(ExtrablattError::IncompleteArticle {
-                                    article: Box::new(PureArticle {
+            }
+
+            let item = Self::find_ready_response(&mut self.article_responses, cx);
+
+            return match item {
+                Some((idx, resp)) => {
+                    let _ = self.article_responses.swap_remove(idx);
+                    let article = match resp {
+                        Ok((url, body)) => {
+                            let lossy = self.paper.config.lossy_decode_fallback;
+                            if let Some(doc) = read_document(&body, lossy) {
+                                let content = self
+                                    .paper
+                                    .config
+                                    .extract_content(
+                                        &self.paper.extractor,
+                                        &doc,
+                                        Some(&self.paper.base_url),
+                                        Some(self.paper.language.clone()),
+                                    )
+                                    .into_owned();
+                                let language = self
+                                    .paper
+                                    .extractor
+                                    .meta_language(&doc)
+                                    .unwrap_or_else(|| self.paper.language.clone());
+
+                                let skip = (self.paper.config.skip_web_stories
+                                    && self.paper.extractor.is_web_story(&doc))
+                                    || content.looks_like_index;
+
+                                if !skip && self.paper.config.is_complete(&content) {
+                                    if self.is_duplicate_content(&content) {
+                                        continue;
+                                    }
+                                    Ok(Article {
                                         url,
+                                        doc,
                                         content,
                                         language,
-                                    }),
-                                })
+                                    })
+                                } else {
+                                    Err(ExtrablattError::IncompleteArticle {
+                                        article: Box::new(PureArticle {
+                                            url,
+                                            content,
+                                            language,
+                                        }),
+                                    })
+                                }
+                            } else {
+                                Err(ExtrablattError::ReadDocumentError { body })
                             }
-                        } else {
-                            Err(ExtrablattError::ReadDocumentError { body })
                         }
-                    }
-                    Err(error) => Err(error),
-                };
-                Poll::Ready(Some(article))
-            }
-            None => Poll::Pending,
+                        Err((url, error)) => {
+                            if let Some(resp) = self.retry(&url, &error, false) {
+                                self.article_responses.push(resp);
+                                continue;
+                            }
+                            Err(error)
+                        }
+                    };
+                    let event = match article {
+                        Ok(article) => match self.article_ready(article) {
+                            Some(event) => event,
+                            None => continue,
+                        },
+                        Err(error) => CrawlEvent::Error(error),
+                    };
+                    Poll::Ready(Some(event))
+                }
+                None => Poll::Pending,
+            };
+        }
+    }
+
+    /// Ends the stream with a [`CrawlEvent::Done`].
+    fn finish(&mut self) -> Poll<Option<CrawlEvent>> {
+        self.done = true;
+        Poll::Ready(Some(CrawlEvent::Done))
+    }
+}
+
+impl<TExtractor: Extractor + Unpin> Stream for ArticleStream<TExtractor> {
+    type Item = std::result::Result<Article, ExtrablattError>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        loop {
+            return match self.poll_event(cx) {
+                Poll::Ready(Some(CrawlEvent::ArticleReady(article))) => {
+                    Poll::Ready(Some(Ok(*article)))
+                }
+                Poll::Ready(Some(CrawlEvent::Error(error))) => Poll::Ready(Some(Err(error))),
+                Poll::Ready(Some(CrawlEvent::Done)) | Poll::Ready(None) => Poll::Ready(None),
+                Poll::Ready(Some(_)) => continue,
+                Poll::Pending => Poll::Pending,
+            };
         }
     }
 
@@ -674,6 +1679,12 @@ pub struct ExtrablattBuilder {
     /// Whether to extract and store the categories of the news paper's main
     /// page.
     categories: bool,
+    /// Whether to download all categories while building.
+    prefetch_categories: bool,
+    /// Logs in before the main page is fetched.
+    authenticator: Option<Box<dyn Authenticator>>,
+    /// Article urls to download in addition to the discovered ones.
+    seed_article_urls: Vec<Url>,
 }
 
 impl ExtrablattBuilder {
@@ -684,9 +1695,22 @@ impl ExtrablattBuilder {
             language: None,
             headers: None,
             categories: true,
+            prefetch_categories: false,
+            authenticator: None,
+            seed_article_urls: Vec::new(),
         })
     }
 
+    /// Same as [`ExtrablattBuilder::new`], but applies the tuned [`Config`]
+    /// if the url's host is a [`KnownSite`].
+    pub fn for_known_site<T: IntoUrl>(base_url: T) -> Result<Self> {
+        let mut builder = Self::new(base_url)?;
+        if let Some(site) = builder.base_url.as_ref().and_then(KnownSite::find) {
+            builder.config = Some(site.config()?);
+        }
+        Ok(builder)
+    }
+
     pub fn language(mut self, language: Language) -> Self {
         self.language = Some(language);
         self
@@ -707,6 +1731,32 @@ impl ExtrablattBuilder {
         self
     }
 
+    /// Download all categories of the main page and store their articles
+    /// while building, instead of on demand, see
+    /// [`Extrablatt::download_all_remaining_categories`].
+    pub fn prefetch_categories(mut self, prefetch_categories: bool) -> Self {
+        self.prefetch_categories = prefetch_categories;
+        self
+    }
+
+    /// Runs the `authenticator` before the main page is fetched, so that all
+    /// requests carry the session. The client keeps cookies only if an
+    /// authenticator is set or [`ConfigBuilder::cookie_store`] is enabled.
+    pub fn authenticator<A: Authenticator + 'static>(mut self, authenticator: A) -> Self {
+        self.authenticator = Some(Box::new(authenticator));
+        self
+    }
+
+    /// Article urls, like those of a feed or a previous crawl, that are
+    /// downloaded alongside the discovered articles.
+    ///
+    /// As they are explicitly provided, they are not checked by
+    /// [`Extractor::is_article`].
+    pub fn seed_article_urls(mut self, urls: Vec<Url>) -> Self {
+        self.seed_article_urls = urls;
+        self
+    }
+
     /// Create a new builder with a specific extractor.
     pub async fn build_with_extractor<TExtractor: Extractor>(
         self,
@@ -724,32 +1774,36 @@ impl ExtrablattBuilder {
 
         // TODO headers currently not supported in reqwest wasm
         #[cfg(target_arch = "wasm32")]
-        let client = { Client::builder().build()? };
+        let client = {
+            let client = Client::builder().build()?;
+            if let Some(authenticator) = &self.authenticator {
+                // the browser keeps the session cookies
+                authenticator.authenticate(&client).await?;
+            }
+            client
+        };
 
         #[cfg(not(target_arch = "wasm32"))]
         let client = {
-            let mut headers = self.headers.unwrap_or_else(|| HeaderMap::with_capacity(1));
-
-            if !headers.contains_key(USER_AGENT) {
-                headers.insert(
-                    USER_AGENT,
-                    config.user_agent.parse().context(format!(
-                        "Failed to parse user agent header name: {}",
-                        config.user_agent
-                    ))?,
-                );
+            let headers = self.headers.unwrap_or_else(|| HeaderMap::with_capacity(1));
+            let client = build_client(&config, headers, self.authenticator.is_some())?;
+            if let Some(authenticator) = &self.authenticator {
+                authenticator
+                    .authenticate(&client)
+                    .await
+                    .context("Failed to authenticate.")?;
             }
-
-            Client::builder()
-                .default_headers(headers)
-                .timeout(config.request_timeout)
-                .build()?
+            client
         };
 
-        let resp = client.get(base_url.clone()).send().await;
+        let mut request = client.get(base_url.clone());
+        if let Some(headers) = config.headers_for(&base_url) {
+            request = request.headers(headers.clone());
+        }
+        let resp = request.send().await;
 
         // TODO fix error
-        let (main_page, _) = DocumentDownloadState::from_response(resp)
+        let (main_page, _) = DocumentDownloadState::from_response(resp, config.read_options())
             .await
             .map_err(|_| anyhow!(""))?;
         // .map_err(|(_, err)| err)?;
@@ -765,11 +1819,27 @@ impl ExtrablattBuilder {
                 config.max_doc_cache,
                 Default::default(),
             ),
+            jitter: Jitter::new(config.jitter_seed),
+            news_metadata: Default::default(),
+            media: Default::default(),
             config,
         };
 
+        for url in self.seed_article_urls {
+            paper
+                .articles
+                .entry(ArticleUrl::new(url))
+                .or_insert(DocumentDownloadState::NotRequested);
+        }
+
         if self.categories {
             paper.insert_new_categories();
+            if self.prefetch_categories {
+                paper.download_all_remaining_categories().await;
+            }
+        }
+        if paper.config.follow_sitemap_news {
+            paper.insert_news_sitemap_articles().await;
         }
 
         Ok(paper)
@@ -780,6 +1850,367 @@ impl ExtrablattBuilder {
     }
 }
 
+/// Creates the client for the `config` that sends the `headers`, plus the
+/// configured user agent and accept language unless they are already set.
+///
+/// The client keeps the cookies set by the responses if `cookie_store` is set,
+/// like for the session of an [`ExtrablattBuilder::authenticator`], or if
+/// enabled by [`ConfigBuilder::cookie_store`].
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn build_client(
+    config: &Config,
+    mut headers: HeaderMap,
+    cookie_store: bool,
+) -> Result<Client> {
+    if !headers.contains_key(USER_AGENT) {
+        headers.insert(
+            USER_AGENT,
+            config.user_agent.parse().context(format!(
+                "Failed to parse user agent header name: {}",
+                config.user_agent
+            ))?,
+        );
+    }
+
+    if let Some(accept_language) = &config.accept_language {
+        if !headers.contains_key(ACCEPT_LANGUAGE) {
+            headers.insert(
+                ACCEPT_LANGUAGE,
+                accept_language.parse().context(format!(
+                    "Failed to parse accept language header: {}",
+                    accept_language
+                ))?,
+            );
+        }
+    }
+
+    if let Some(profile) = config.browser_profile {
+        for (name, value) in profile.headers().iter() {
+            if !headers.contains_key(name) {
+                headers.insert(name.clone(), value.clone());
+            }
+        }
+    }
+
+    let mut builder = Client::builder()
+        .default_headers(headers)
+        .cookie_store(cookie_store || config.cookie_store)
+        .timeout(config.request_timeout);
+    if let Some(connect_timeout) = config.connect_timeout {
+        builder = builder.connect_timeout(connect_timeout);
+    }
+    Ok(builder.build()?)
+}
+
+/// Crawls several news sites at once and merges their articles into a single
+/// stream.
+#[derive(Debug, Default)]
+pub struct MultiSource {
+    /// Builders for all sites.
+    sources: Vec<ExtrablattBuilder>,
+    /// Config for all sources that don't have their own config.
+    config: Option<Config>,
+    /// How many sources to build concurrently.
+    concurrency: Option<usize>,
+    /// Stops the streams of all sources once set, see
+    /// [`ArticleStream::with_cancel`].
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+impl MultiSource {
+    /// Default number of sources that are built concurrently.
+    pub const DEFAULT_CONCURRENCY: usize = 10;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the news site with the `url`.
+    pub fn url<T: IntoUrl>(self, url: T) -> Result<Self> {
+        Ok(self.source(ExtrablattBuilder::new(url)?))
+    }
+
+    /// Adds a news site.
+    pub fn source(mut self, source: ExtrablattBuilder) -> Self {
+        self.sources.push(source);
+        self
+    }
+
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = Some(concurrency);
+        self
+    }
+
+    /// Stops the streams of all sources once the `cancel` flag is set, see
+    /// [`ArticleStream::with_cancel`].
+    pub fn with_cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    /// Builds all sources and merges their [`ArticleStream`]s.
+    ///
+    /// The stream of each source is merged as soon as the source is built.
+    /// The articles can be assigned to their source by the host of
+    /// [`Article::url`]. Sources that can't be built are reported as
+    /// [`ExtrablattError::SourceBuildFailure`].
+    pub fn into_stream(self) -> impl Stream<Item = std::result::Result<Article, ExtrablattError>> {
+        self.merge_article_streams(|stream| stream, Err)
+    }
+
+    /// Same as [`MultiSource::into_stream`], but yields the [`CrawlEvent`]s of
+    /// all sources, see [`ArticleStream::events`].
+    ///
+    /// The last event is a single [`CrawlEvent::Done`] once all sources are
+    /// done.
+    pub fn events(self) -> impl Stream<Item = CrawlEvent> {
+        self.merge_article_streams(ArticleStream::events, CrawlEvent::Error)
+            .filter(|event| futures::future::ready(!matches!(event, CrawlEvent::Done)))
+            .chain(stream::once(futures::future::ready(CrawlEvent::Done)))
+    }
+
+    /// Builds all sources concurrently and merges the stream that `stream`
+    /// makes of each [`ArticleStream`] as soon as its source is built.
+    ///
+    /// Sources that can't be built are yielded as the item `failure` makes of
+    /// the error.
+    fn merge_article_streams<S, F, E>(self, stream: F, failure: E) -> impl Stream<Item = S::Item>
+    where
+        S: Stream + Unpin,
+        F: Fn(ArticleStream<DefaultExtractor>) -> S,
+        E: Fn(ExtrablattError) -> S::Item,
+    {
+        let MultiSource {
+            sources,
+            config,
+            concurrency,
+            cancel,
+        } = self;
+        let concurrency = concurrency.unwrap_or(Self::DEFAULT_CONCURRENCY).max(1);
+
+        let mut builds = Box::pin(
+            stream::iter(sources.into_iter().map(move |mut source| {
+                if source.config.is_none() {
+                    source.config = config.clone();
+                }
+                let url = source.base_url.clone();
+                async move {
+                    source
+                        .build()
+                        .await
+                        .map_err(|error| ExtrablattError::SourceBuildFailure { url, error })
+                }
+            }))
+            .buffer_unordered(concurrency)
+            .fuse(),
+        );
+        let mut streams = stream::SelectAll::new();
+
+        stream::poll_fn(move |cx| {
+            loop {
+                match builds.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(Ok(paper))) => {
+                        let paper = paper.into_stream();
+                        streams.push(stream(match &cancel {
+                            Some(cancel) => paper.with_cancel(Arc::clone(cancel)),
+                            None => paper,
+                        }));
+                    }
+                    Poll::Ready(Some(Err(err))) => {
+                        log::warn!("{}", err);
+                        return Poll::Ready(Some(failure(err)));
+                    }
+                    Poll::Ready(None) | Poll::Pending => break,
+                }
+            }
+            match streams.poll_next_unpin(cx) {
+                // more sources are still being built
+                Poll::Ready(None) if !builds.is_terminated() => Poll::Pending,
+                poll => poll,
+            }
+        })
+    }
+}
+
+/// Number of documents that haven't been requested yet.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Outstanding {
+    /// Categories in [`DocumentDownloadState::NotRequested`] state.
+    pub categories: usize,
+    /// Articles in [`DocumentDownloadState::NotRequested`] state.
+    pub articles: usize,
+}
+
+/// Number of documents that were already requested.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Completed {
+    /// Successfully downloaded categories.
+    pub category_successes: usize,
+    /// Categories whose download failed.
+    pub category_failures: usize,
+    /// Successfully downloaded articles.
+    pub article_successes: usize,
+    /// Articles whose download failed.
+    pub article_failures: usize,
+}
+
+/// Parses the `body` into a [`Document`].
+///
+/// If the `body` isn't valid UTF-8 and `lossy` is set, invalid sequences are
+/// replaced and the document is parsed from that instead.
+pub(crate) fn read_document(body: &[u8], lossy: bool) -> Option<Document> {
+    if let Ok(doc) = Document::from_read(body) {
+        return Some(doc);
+    }
+    if lossy {
+        log::warn!("Decoding document with invalid UTF-8 lossily");
+        return Some(Document::from(&*String::from_utf8_lossy(body)));
+    }
+    None
+}
+
+/// Fetches and extracts the article at the `url` like the articles of a
+/// crawl: with the jitter, retries, body size limit and decoding of the
+/// `config`, failing with [`ExtrablattError::IncompleteArticle`] if the
+/// content isn't [`Config::is_complete`].
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn fetch_configured_article(
+    client: &Client,
+    config: &Config,
+    jitter: &Jitter,
+    url: Url,
+) -> Result<Article> {
+    let mut attempts = 1;
+    let (url, body) = loop {
+        let mut delay = config.request_jitter.map(|max| jitter.delay(max));
+        if attempts > 1 {
+            delay = Some(config.retry.delay + delay.unwrap_or_default());
+        }
+        if let Some(delay) = delay {
+            let _ = Delay::new(delay).await;
+        }
+        let mut request = client.get(url.clone());
+        if let Some(headers) = config.headers_for(&url) {
+            request = request.headers(headers.clone());
+        }
+        let result = match request.send().await {
+            Ok(response) if response.status().is_success() => {
+                let url = response.url().clone();
+                read_body(response, config.max_body_bytes)
+                    .await
+                    .map(|body| (url, body))
+            }
+            Ok(response) => Err(ExtrablattError::NoHttpSuccessResponse { response }),
+            Err(error) => Err(ExtrablattError::HttpRequestFailure { error }),
+        };
+        match result {
+            Ok(response) => break response,
+            Err(error) if error.is_retriable() && attempts < config.retry.max_attempts => {
+                attempts += 1
+            }
+            Err(error) => return Err(error.into()),
+        }
+    };
+
+    let doc = read_document(&body, config.lossy_decode_fallback)
+        .ok_or(ExtrablattError::ReadDocumentError { body })?;
+    let extractor = DefaultExtractor;
+    let content = config
+        .extract_content(&extractor, &doc, extractor.base_url(&doc).as_ref(), None)
+        .into_owned();
+    let language = extractor.meta_language(&doc).unwrap_or_default();
+    if !config.is_complete(&content) {
+        return Err(ExtrablattError::IncompleteArticle {
+            article: Box::new(PureArticle {
+                url,
+                content,
+                language,
+            }),
+        }
+        .into());
+    }
+
+    let mut article = Article {
+        url,
+        doc,
+        content,
+        language,
+    };
+    if config.download_media {
+        article
+            .content
+            .download_media(
+                client,
+                config.max_concurrent_requests.max(1),
+                config.max_body_bytes,
+            )
+            .await;
+    }
+    Ok(article)
+}
+
+/// Reads the body of the `response`, failing if it exceeds `limit` bytes.
+#[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
+pub(crate) async fn read_body(
+    mut response: Response,
+    limit: Option<usize>,
+) -> std::result::Result<Bytes, ExtrablattError> {
+    let limit = match limit {
+        Some(limit) => limit,
+        None => {
+            return response
+                .bytes()
+                .await
+                .map_err(|error| ExtrablattError::HttpRequestFailure { error })
+        }
+    };
+    let url = response.url().clone();
+    if response.content_length().unwrap_or_default() > limit as u64 {
+        return Err(ExtrablattError::BodyTooLarge { url, limit });
+    }
+
+    // the wasm response can't be read in chunks
+    #[cfg(target_arch = "wasm32")]
+    {
+        let body = response
+            .bytes()
+            .await
+            .map_err(|error| ExtrablattError::HttpRequestFailure { error })?;
+        if body.len() > limit {
+            return Err(ExtrablattError::BodyTooLarge { url, limit });
+        }
+        Ok(body)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let mut body = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|error| ExtrablattError::HttpRequestFailure { error })?
+        {
+            if body.len() + chunk.len() > limit {
+                return Err(ExtrablattError::BodyTooLarge { url, limit });
+            }
+            body.extend_from_slice(&chunk);
+        }
+        Ok(Bytes::from(body))
+    }
+}
+
+/// Settings for reading a response into a [`Document`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ReadOptions {
+    lossy_decode_fallback: bool,
+    max_body_bytes: Option<usize>,
+}
+
 #[derive(Debug)]
 pub enum DocumentDownloadState {
     /// No request sent yet.
@@ -807,28 +2238,168 @@ pub enum DocumentDownloadState {
         /// Timestamp the response was received.
         received: Instant,
     },
+    /// The request was redirected to the article stored under the url `to`.
+    Redirected {
+        /// The final url of the redirect.
+        to: Url,
+    },
 }
 
-impl DocumentDownloadState {
-    /// Wraps the [`hyper::Response`] into the proper state.
-    pub(crate) async fn from_response(
-        response: std::result::Result<Response, reqwest::Error>,
-    ) -> std::result::Result<(Document, Instant), (Self, ExtrablattError)> {
-        match response {
-            Ok(response) => {
-                if response.status().is_success() {
-                    Self::read_response(response).await
-                } else {
-                    Err((
-                        DocumentDownloadState::NoHttpSuccessResponse {
-                            received: Instant::now(),
-                        },
-                        ExtrablattError::NoHttpSuccessResponse { response },
-                    ))
-                }
+/// The serializable form of an [`Extrablatt`], see
+/// [`Extrablatt::save_cache`].
+#[cfg(feature = "serde0")]
+#[derive(Serialize, Deserialize)]
+struct Cache {
+    base_url: Url,
+    #[serde(default)]
+    language: Language,
+    #[serde(default)]
+    config: Config,
+    /// The html of the main page.
+    main_page: String,
+    #[serde(default)]
+    articles: Vec<(ArticleUrl, CachedState)>,
+    #[serde(default)]
+    categories: Vec<(Url, CachedState)>,
+    #[serde(default)]
+    news_metadata: Vec<(Url, NewsMetadata)>,
+}
+
+/// The serializable form of a [`DocumentDownloadState`], without the time
+/// the response was received.
+#[cfg(feature = "serde0")]
+#[derive(Serialize, Deserialize)]
+enum CachedState {
+    NotRequested,
+    Success { html: String },
+    NoHttpSuccessResponse,
+    HttpRequestFailure,
+    DocumentReadFailure,
+    Redirected { to: Url },
+}
+
+#[cfg(feature = "serde0")]
+impl From<&DocumentDownloadState> for CachedState {
+    fn from(state: &DocumentDownloadState) -> Self {
+        match state {
+            DocumentDownloadState::NotRequested => CachedState::NotRequested,
+            DocumentDownloadState::Success { doc, .. } => CachedState::Success {
+                html: document_html(doc),
+            },
+            DocumentDownloadState::NoHttpSuccessResponse { .. } => {
+                CachedState::NoHttpSuccessResponse
             }
-            Err(error) => Err((
-                DocumentDownloadState::HttpRequestFailure {
+            DocumentDownloadState::HttpRequestFailure { .. } => CachedState::HttpRequestFailure,
+            DocumentDownloadState::DocumentReadFailure { .. } => CachedState::DocumentReadFailure,
+            DocumentDownloadState::Redirected { to } => CachedState::Redirected { to: to.clone() },
+        }
+    }
+}
+
+#[cfg(feature = "serde0")]
+impl CachedState {
+    /// Parses the html again, as if the response was received now.
+    fn into_state(self) -> DocumentDownloadState {
+        let received = Instant::now();
+        match self {
+            CachedState::NotRequested => DocumentDownloadState::NotRequested,
+            CachedState::Success { html } => DocumentDownloadState::Success {
+                received,
+                doc: Document::from(html.as_str()),
+            },
+            CachedState::NoHttpSuccessResponse => {
+                DocumentDownloadState::NoHttpSuccessResponse { received }
+            }
+            CachedState::HttpRequestFailure => {
+                DocumentDownloadState::HttpRequestFailure { received }
+            }
+            CachedState::DocumentReadFailure => {
+                DocumentDownloadState::DocumentReadFailure { received }
+            }
+            CachedState::Redirected { to } => DocumentDownloadState::Redirected { to },
+        }
+    }
+}
+
+/// (De)serializes the [`Config::headers_per_host`] as the name and value
+/// pairs of each host, header values that aren't visible ASCII can't be
+/// serialized.
+#[cfg(feature = "serde0")]
+mod serde_headers_per_host {
+    use std::collections::HashMap;
+
+    use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+    use serde::de::Error as _;
+    use serde::ser::Error as _;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        headers_per_host: &HashMap<String, HeaderMap>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut pairs = HashMap::with_capacity(headers_per_host.len());
+        for (host, headers) in headers_per_host {
+            let headers = headers
+                .iter()
+                .map(|(name, value)| value.to_str().map(|value| (name.as_str(), value)))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(S::Error::custom)?;
+            pairs.insert(host.as_str(), headers);
+        }
+        pairs.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<String, HeaderMap>, D::Error> {
+        let pairs: HashMap<String, Vec<(String, String)>> = HashMap::deserialize(deserializer)?;
+        let mut headers_per_host = HashMap::with_capacity(pairs.len());
+        for (host, pairs) in pairs {
+            let mut headers = HeaderMap::with_capacity(pairs.len());
+            for (name, value) in pairs {
+                headers.append(
+                    HeaderName::from_bytes(name.as_bytes()).map_err(D::Error::custom)?,
+                    HeaderValue::from_str(&value).map_err(D::Error::custom)?,
+                );
+            }
+            headers_per_host.insert(host, headers);
+        }
+        Ok(headers_per_host)
+    }
+}
+
+/// Serializes the parsed `doc` back to html.
+#[cfg(feature = "serde0")]
+fn document_html(doc: &Document) -> String {
+    doc.nodes
+        .iter()
+        .filter(|raw| raw.parent.is_none())
+        .filter_map(|raw| doc.nth(raw.index))
+        .map(|node| node.html())
+        .collect()
+}
+
+impl DocumentDownloadState {
+    /// Wraps the [`hyper::Response`] into the proper state.
+    pub(crate) async fn from_response(
+        response: std::result::Result<Response, reqwest::Error>,
+        options: ReadOptions,
+    ) -> std::result::Result<(Document, Instant), (Self, ExtrablattError)> {
+        match response {
+            Ok(response) => {
+                if response.status().is_success() {
+                    Self::read_response(response, options).await
+                } else {
+                    Err((
+                        DocumentDownloadState::NoHttpSuccessResponse {
+                            received: Instant::now(),
+                        },
+                        ExtrablattError::NoHttpSuccessResponse { response },
+                    ))
+                }
+            }
+            Err(error) => Err((
+                DocumentDownloadState::HttpRequestFailure {
                     received: Instant::now(),
                 },
                 ExtrablattError::HttpRequestFailure { error },
@@ -838,10 +2409,11 @@ impl DocumentDownloadState {
 
     async fn read_response(
         response: Response,
+        options: ReadOptions,
     ) -> std::result::Result<(Document, Instant), (DocumentDownloadState, ExtrablattError)> {
-        match response.bytes().await {
+        match read_body(response, options.max_body_bytes).await {
             Ok(body) => {
-                if let Ok(doc) = Document::from_read(&*body) {
+                if let Some(doc) = read_document(&body, options.lossy_decode_fallback) {
                     Ok((doc, Instant::now()))
                 } else {
                     Err((
@@ -852,11 +2424,17 @@ impl DocumentDownloadState {
                     ))
                 }
             }
+            Err(error @ ExtrablattError::BodyTooLarge { .. }) => Err((
+                DocumentDownloadState::DocumentReadFailure {
+                    received: Instant::now(),
+                },
+                error,
+            )),
             Err(error) => Err((
                 DocumentDownloadState::HttpRequestFailure {
                     received: Instant::now(),
                 },
-                ExtrablattError::HttpRequestFailure { error },
+                error,
             )),
         }
     }
@@ -865,9 +2443,10 @@ impl DocumentDownloadState {
     /// [`select::document::Document`] anyway.
     async fn advance_non_http_success(
         err: ExtrablattError,
+        options: ReadOptions,
     ) -> std::result::Result<(Document, Instant), ExtrablattError> {
         if let ExtrablattError::NoHttpSuccessResponse { response } = err {
-            match DocumentDownloadState::read_response(response).await {
+            match DocumentDownloadState::read_response(response, options).await {
                 Ok((doc, received)) => Ok((doc, received)),
                 Err((_, err)) => Err(err),
             }
@@ -903,6 +2482,15 @@ impl DocumentDownloadState {
     pub fn is_success(&self) -> bool {
         matches!(self, DocumentDownloadState::Success { .. })
     }
+
+    /// The url the request was redirected to, see
+    /// [`DocumentDownloadState::Redirected`].
+    pub fn redirected_to(&self) -> Option<&Url> {
+        match self {
+            DocumentDownloadState::Redirected { to } => Some(to),
+            _ => None,
+        }
+    }
 }
 
 impl Default for DocumentDownloadState {
@@ -911,14 +2499,18 @@ impl Default for DocumentDownloadState {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde0", serde(default))]
 pub struct Config {
     /// Minimum number of word tokens in the text.
     min_word_count: Option<usize>,
     /// Maximum number of word tokens in the text.
     max_word_count: Option<usize>,
-    /// Number of sentence tokens.
+    /// Min number of sentences in the text, see [`Language::split_sentences`].
     min_sentence_count: Option<usize>,
+    /// Min number of non empty lines, i.e. paragraphs, in the text.
+    min_paragraph_count: Option<usize>,
     /// Min number of chars for the text's title.
     min_title_len: Option<usize>,
     /// Max number of chars for the text's title.
@@ -927,6 +2519,8 @@ pub struct Config {
     min_text_len: Option<usize>,
     /// Max number of chars for the text.
     max_text_len: Option<usize>,
+    /// Max number of chars the text is truncated to.
+    truncate_text_to: Option<usize>,
     /// Min number of keywords for the text.
     min_keywords: Option<usize>,
     /// Max number of keywords for the text.
@@ -937,15 +2531,85 @@ pub struct Config {
     max_authors: Option<usize>,
     /// Max. number of urls to cache for a news source.
     max_doc_cache: usize,
+    /// Max. number of requests that are sent concurrently.
+    pub(crate) max_concurrent_requests: usize,
     /// Whether to also capture non 2XX responses.
     http_success_only: bool,
     /// The user-agent used for requests.
     user_agent: String,
+    /// The `Accept-Language` header sent with requests.
+    accept_language: Option<String>,
+    /// Headers sent with the requests to a host and its subdomains.
+    #[cfg_attr(feature = "serde0", serde(with = "serde_headers_per_host"))]
+    headers_per_host: HashMap<String, HeaderMap>,
+    /// The browser whose headers are sent with requests.
+    browser_profile: Option<BrowserProfile>,
     /// Timeout for requests.
     request_timeout: Duration,
+    /// Timeout for only the connect phase of requests.
+    connect_timeout: Option<Duration>,
+    /// Whether the client keeps the cookies set by responses.
+    cookie_store: bool,
+    /// Whether to treat Google Web Stories as incomplete articles.
+    skip_web_stories: bool,
+    /// Whether to use the meta description as text if no text was found.
+    description_as_text_fallback: bool,
+    /// Whether to drop a description that only repeats the title.
+    dedupe_description: bool,
+    /// How to retry failed requests.
+    retry: RetryConfig,
+    /// Whether to decode documents with invalid UTF-8 lossily instead of
+    /// failing.
+    lossy_decode_fallback: bool,
+    /// Whether to treat articles republished from another source as
+    /// incomplete.
+    skip_syndicated: bool,
+    /// Whether to treat recipes and how-tos as incomplete.
+    skip_structured: bool,
+    /// Whether to treat e-commerce pages as incomplete.
+    skip_products: bool,
+    /// Whether to repair mojibake and compose diacritics of the title,
+    /// description and text.
+    normalize_unicode: bool,
+    /// Whether to suppress articles whose content was already yielded under
+    /// another url.
+    dedupe_by_content: bool,
+    /// Whether to extract the comments rendered on the page.
+    extract_comments: bool,
+    /// Whether to keep tracking pixels and beacons in the images.
+    keep_tracking_images: bool,
+    /// Max. number of text nodes scored to find the article's text.
+    max_nodes_to_score: Option<usize>,
+    /// Whether to download the images and video thumbnails of the articles.
+    pub(crate) download_media: bool,
+    /// Whether to remove the query of category urls.
+    strip_category_query: bool,
+    /// Whether to queue the articles of Google News sitemaps.
+    follow_sitemap_news: bool,
+    /// Max. random delay before each request.
+    request_jitter: Option<Duration>,
+    /// Seed for the random delays of `request_jitter`.
+    pub(crate) jitter_seed: Option<u64>,
+    /// Query parameters that identify an article, like `p` for `?p=12345`.
+    article_id_query_params: Vec<String>,
+    /// Max. size of a response body.
+    max_body_bytes: Option<usize>,
+    /// Leading and trailing paragraphs containing these are removed from the
+    /// text.
+    boilerplate_phrases: Vec<String>,
+    /// Max. age of an article at the time it is scraped.
+    max_article_age: Option<Duration>,
+    /// Whether to rewrite mobile and AMP article urls to their desktop
+    /// equivalent before fetching.
+    normalize_to_desktop: bool,
+    /// Max. share of affiliate links among all links of the article's text.
+    max_affiliate_ratio: Option<f64>,
 }
 
 impl Config {
+    /// Default number of concurrent requests.
+    pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 10;
+
     /// Default timeout for requests made inside `extrablatt`.
     pub const DEFAULT_REQUEST_TIMEOUT_SEC: u64 = 30;
 
@@ -961,14 +2625,111 @@ impl Config {
         ConfigBuilder::default()
     }
 
+    pub(crate) fn read_options(&self) -> ReadOptions {
+        ReadOptions {
+            lossy_decode_fallback: self.lossy_decode_fallback,
+            max_body_bytes: self.max_body_bytes,
+        }
+    }
+
+    /// The headers configured for the host of the `url`, see
+    /// [`ConfigBuilder::headers_per_host`].
+    ///
+    /// If both a domain and its subdomain are configured, the subdomain's
+    /// headers are used.
+    pub fn headers_for(&self, url: &Url) -> Option<&HeaderMap> {
+        let host = url.host_str()?.to_lowercase();
+        self.headers_per_host
+            .iter()
+            .filter(|(configured, _)| {
+                let configured = configured.trim().to_lowercase();
+                host == configured || host.ends_with(&format!(".{}", configured))
+            })
+            .max_by_key(|(configured, _)| configured.len())
+            .map(|(_, headers)| headers)
+    }
+
+    /// Extracts the content of the `doc` with the `extractor`, including the
+    /// comments if enabled, and applies [`Config::finalize_content`].
+    pub fn extract_content<'a, T: Extractor>(
+        &self,
+        extractor: &T,
+        doc: &'a Document,
+        base_url: Option<&Url>,
+        lang: Option<Language>,
+    ) -> ArticleContent<'a> {
+        let mut content = extractor.article_content_with(
+            doc,
+            base_url,
+            lang,
+            !self.keep_tracking_images,
+            self.max_nodes_to_score,
+        );
+        if self.extract_comments {
+            content.comments = extractor.comments(doc);
+        }
+        if self.normalize_unicode {
+            for txt in [
+                &mut content.title,
+                &mut content.description,
+                &mut content.text,
+            ]
+            .iter_mut()
+            .filter_map(|txt| txt.as_mut())
+            {
+                if let Cow::Owned(sanitized) = extractor.sanitize_unicode(txt) {
+                    *txt = Cow::Owned(sanitized);
+                }
+            }
+        }
+        self.finalize_content(content)
+    }
+
+    /// Applies the configured adjustments to freshly extracted content.
+    pub fn finalize_content<'a>(&self, mut content: ArticleContent<'a>) -> ArticleContent<'a> {
+        if !self.boilerplate_phrases.is_empty() {
+            if let Some(text) = &content.text {
+                let stripped = strip_boilerplate(text, &self.boilerplate_phrases);
+                if stripped.len() != text.len() {
+                    content.text = Some(stripped.into());
+                }
+            }
+        }
+        if self.description_as_text_fallback && !content.has_text() {
+            if let Some(description) = content.description.clone() {
+                content.text = Some(description);
+                content.text_source = Some(TextSource::Description);
+            }
+        }
+        if let (Some(max_chars), Some(text)) = (self.truncate_text_to, &content.text) {
+            let truncated = truncate_words(text, max_chars);
+            if truncated.len() != text.len() {
+                content.text = Some(truncated.to_string().into());
+                content.text_truncated = true;
+            }
+        }
+        if self.dedupe_description {
+            if let (Some(title), Some(description)) = (&content.title, &content.description) {
+                let title = title.trim().to_lowercase();
+                let description = description.trim().to_lowercase();
+                if title.starts_with(&description) || title.ends_with(&description) {
+                    content.description = None;
+                }
+            }
+        }
+        content
+    }
+
     /// Checks that the article fulfills the configured restrictions.
     pub fn is_complete<'a>(&self, article: &ArticleContent<'a>) -> bool {
         macro_rules! range_check {
             ($($el:expr => ($min:expr ; $max:expr)),*) => {
                 $(
+                // lengths are measured in unicode scalar values, not bytes
+                let len = $el.as_ref().map(|txt| txt.chars().count());
                  if let Some(min) = $min {
-                    if let Some(txt) = &$el {
-                        if min > txt.len() {
+                    if let Some(len) = len {
+                        if min > len {
                             return false
                         }
                     } else {
@@ -977,8 +2738,8 @@ impl Config {
                  }
 
                 if let Some(max) = $max {
-                    if let Some(txt) = &$el {
-                        if max < txt.len() {
+                    if let Some(len) = len {
+                        if max < len {
                             return false
                         }
                     }
@@ -1011,6 +2772,36 @@ impl Config {
             }
         }
 
+        if let Some(min_sentence_count) = self.min_sentence_count {
+            let language = article.language.clone().unwrap_or_default();
+            let sentences = article
+                .text
+                .as_ref()
+                .map(|txt| language.split_sentences(txt).len())
+                .unwrap_or_default();
+            if min_sentence_count > sentences {
+                return false;
+            }
+        }
+
+        if let Some(min_paragraph_count) = self.min_paragraph_count {
+            let paragraphs = article
+                .text
+                .as_ref()
+                .map(|txt| txt.lines().filter(|line| !line.trim().is_empty()).count())
+                .unwrap_or_default();
+            if min_paragraph_count > paragraphs {
+                return false;
+            }
+        }
+
+        // whitespace only doesn't satisfy a min. length
+        if (self.min_text_len.is_some() && !article.has_text())
+            || (self.min_title_len.is_some() && !article.has_title())
+        {
+            return false;
+        }
+
         range_check!(
             article.text => (self.min_text_len ; self.max_text_len),
             article.title => (self.min_title_len ; self.max_title_len)
@@ -1040,6 +2831,32 @@ impl Config {
             }
         }
 
+        if self.skip_syndicated && article.syndication.is_some() {
+            return false;
+        }
+
+        if self.skip_structured && article.structured.is_some() {
+            return false;
+        }
+
+        if self.skip_products && article.product.is_some() {
+            return false;
+        }
+
+        if let Some(max_affiliate_ratio) = self.max_affiliate_ratio {
+            if article.link_stats.affiliate_ratio() > max_affiliate_ratio {
+                return false;
+            }
+        }
+
+        if let Some(max_article_age) = self.max_article_age {
+            if let Some(age) = article.age(Utc::now()) {
+                if age > max_article_age {
+                    return false;
+                }
+            }
+        }
+
         true
     }
 }
@@ -1056,8 +2873,10 @@ pub struct ConfigBuilder {
     min_word_count: Option<usize>,
     /// Maximum number of word tokens in the text.
     max_word_count: Option<usize>,
-    /// Number of sentence tokens.
+    /// Min number of sentences in the text, see [`Language::split_sentences`].
     min_sentence_count: Option<usize>,
+    /// Min number of non empty lines, i.e. paragraphs, in the text.
+    min_paragraph_count: Option<usize>,
     /// Min number of chars for the text's title.
     min_title_len: Option<usize>,
     /// Max number of chars for the text's title.
@@ -1066,6 +2885,8 @@ pub struct ConfigBuilder {
     min_text_len: Option<usize>,
     /// Max number of chars for the text.
     max_text_len: Option<usize>,
+    /// Max number of chars the text is truncated to.
+    truncate_text_to: Option<usize>,
     /// Min number of keywords for the text.
     min_keywords: Option<usize>,
     /// Max number of keywords for the text.
@@ -1076,12 +2897,78 @@ pub struct ConfigBuilder {
     max_authors: Option<usize>,
     /// Max. number of urls to cache for each news source.
     max_doc_cache: Option<usize>,
+    /// Max. number of requests that are sent concurrently.
+    max_concurrent_requests: Option<usize>,
     /// Whether to also capture non 2XX responses.
     http_success_only: Option<bool>,
     /// The user-agent used for requests.
     user_agent: Option<String>,
+    /// The `Accept-Language` header sent with requests.
+    accept_language: Option<String>,
+    /// Headers sent with the requests to a host and its subdomains.
+    headers_per_host: Option<HashMap<String, HeaderMap>>,
+    /// The browser whose headers are sent with requests.
+    browser_profile: Option<BrowserProfile>,
     /// Timeout for requests.
     request_timeout: Option<Duration>,
+    /// Timeout for only the connect phase of requests.
+    connect_timeout: Option<Duration>,
+    /// Whether the client keeps the cookies set by responses.
+    cookie_store: Option<bool>,
+    /// Whether to treat Google Web Stories as incomplete articles.
+    skip_web_stories: Option<bool>,
+    /// Whether to use the meta description as text if no text was found.
+    description_as_text_fallback: Option<bool>,
+    /// Whether to drop a description that only repeats the title.
+    dedupe_description: Option<bool>,
+    /// How to retry failed requests.
+    retry: Option<RetryConfig>,
+    /// Whether to decode documents with invalid UTF-8 lossily instead of
+    /// failing.
+    lossy_decode_fallback: Option<bool>,
+    /// Whether to treat articles republished from another source as
+    /// incomplete.
+    skip_syndicated: Option<bool>,
+    /// Whether to treat recipes and how-tos as incomplete.
+    skip_structured: Option<bool>,
+    /// Whether to treat e-commerce pages as incomplete.
+    skip_products: Option<bool>,
+    /// Whether to repair mojibake and compose diacritics of the title,
+    /// description and text.
+    normalize_unicode: Option<bool>,
+    /// Whether to suppress articles whose content was already yielded under
+    /// another url.
+    dedupe_by_content: Option<bool>,
+    /// Whether to extract the comments rendered on the page.
+    extract_comments: Option<bool>,
+    /// Whether to keep tracking pixels and beacons in the images.
+    keep_tracking_images: Option<bool>,
+    /// Max. number of text nodes scored to find the article's text.
+    max_nodes_to_score: Option<usize>,
+    /// Whether to download the images and video thumbnails of the articles.
+    download_media: Option<bool>,
+    /// Whether to remove the query of category urls.
+    strip_category_query: Option<bool>,
+    /// Whether to queue the articles of Google News sitemaps.
+    follow_sitemap_news: Option<bool>,
+    /// Max. random delay before each request.
+    request_jitter: Option<Duration>,
+    /// Seed for the random delays of `request_jitter`.
+    jitter_seed: Option<u64>,
+    /// Query parameters that identify an article, like `p` for `?p=12345`.
+    article_id_query_params: Option<Vec<String>>,
+    /// Max. size of a response body.
+    max_body_bytes: Option<usize>,
+    /// Leading and trailing paragraphs containing these are removed from the
+    /// text.
+    boilerplate_phrases: Option<Vec<String>>,
+    /// Max. age of an article at the time it is scraped.
+    max_article_age: Option<Duration>,
+    /// Whether to rewrite mobile and AMP article urls to their desktop
+    /// equivalent before fetching.
+    normalize_to_desktop: Option<bool>,
+    /// Max. share of affiliate links among all links of the article's text.
+    max_affiliate_ratio: Option<f64>,
 }
 
 impl ConfigBuilder {
@@ -1100,6 +2987,13 @@ impl ConfigBuilder {
         self
     }
 
+    /// Treat articles whose text has less than `min_paragraph_count` non
+    /// empty lines as incomplete, like photo pages with a few captions.
+    pub fn min_paragraph_count(mut self, min_paragraph_count: usize) -> Self {
+        self.min_paragraph_count = Some(min_paragraph_count);
+        self
+    }
+
     pub fn min_title_len(mut self, min_title_len: usize) -> Self {
         self.min_title_len = Some(min_title_len);
         self
@@ -1120,6 +3014,16 @@ impl ConfigBuilder {
         self
     }
 
+    /// Truncate the text of articles to at most `truncate_text_to` chars, at
+    /// a word boundary, instead of rejecting them like
+    /// [`ConfigBuilder::max_text_len`] does.
+    ///
+    /// Truncated articles are flagged by [`ArticleContent::text_truncated`].
+    pub fn truncate_text_to(mut self, truncate_text_to: usize) -> Self {
+        self.truncate_text_to = Some(truncate_text_to);
+        self
+    }
+
     pub fn min_keywords(mut self, min_keywords: usize) -> Self {
         self.min_keywords = Some(min_keywords);
         self
@@ -1145,13 +3049,55 @@ impl ConfigBuilder {
         self
     }
 
+    pub fn max_concurrent_requests(mut self, max_concurrent_requests: usize) -> Self {
+        self.max_concurrent_requests = Some(max_concurrent_requests);
+        self
+    }
+
     pub fn http_success_only(mut self, keep_article_html: bool) -> Self {
         self.http_success_only = Some(keep_article_html);
         self
     }
 
-    pub fn user_agent<T: ToString>(mut self, user_agent: T) -> Self {
-        self.user_agent = Some(user_agent.to_string());
+    /// Fails if the `user_agent` isn't a valid header value, like one with a
+    /// newline.
+    pub fn user_agent<T: ToString>(mut self, user_agent: T) -> Result<Self> {
+        let user_agent = user_agent.to_string();
+        validate_header_value("user agent", &user_agent)?;
+        self.user_agent = Some(user_agent);
+        Ok(self)
+    }
+
+    /// The `Accept-Language` header sent with requests, like `de-DE`.
+    pub fn accept_language<T: ToString>(mut self, accept_language: T) -> Self {
+        self.accept_language = Some(accept_language.to_string());
+        self
+    }
+
+    /// Headers, like cookies or a referer, that are sent with the requests to
+    /// a host, like `some-news.com`, and its subdomains on top of the default
+    /// headers.
+    pub fn headers_per_host(mut self, headers_per_host: HashMap<String, HeaderMap>) -> Self {
+        self.headers_per_host = Some(headers_per_host);
+        self
+    }
+
+    /// Send the headers of a real browser, like `Accept` and `Sec-Fetch-*`,
+    /// including its user agent unless [`ConfigBuilder::user_agent`] is set.
+    ///
+    /// This helps with sites that reject requests that don't look like they
+    /// come from a browser.
+    pub fn browser_profile(mut self, browser_profile: BrowserProfile) -> Self {
+        self.browser_profile = Some(browser_profile);
+        self
+    }
+
+    /// Adds the `headers` for the `host` to the
+    /// [`ConfigBuilder::headers_per_host`].
+    pub fn host_headers<T: ToString>(mut self, host: T, headers: HeaderMap) -> Self {
+        self.headers_per_host
+            .get_or_insert_with(Default::default)
+            .insert(host.to_string(), headers);
         self
     }
 
@@ -1160,25 +3106,266 @@ impl ConfigBuilder {
         self
     }
 
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Keep the cookies set by responses and send them with later requests.
+    /// The cookies are always kept for the session of an
+    /// [`ExtrablattBuilder::authenticator`]. Not supported on wasm, where the
+    /// browser handles the cookies.
+    ///
+    /// Disabled by default.
+    pub fn cookie_store(mut self, cookie_store: bool) -> Self {
+        self.cookie_store = Some(cookie_store);
+        self
+    }
+
+    pub fn skip_web_stories(mut self, skip_web_stories: bool) -> Self {
+        self.skip_web_stories = Some(skip_web_stories);
+        self
+    }
+
+    pub fn description_as_text_fallback(mut self, description_as_text_fallback: bool) -> Self {
+        self.description_as_text_fallback = Some(description_as_text_fallback);
+        self
+    }
+
+    /// Drop the description if it equals the title, or is a prefix or suffix
+    /// of it, ignoring case and surrounding whitespace.
+    pub fn dedupe_description(mut self, dedupe_description: bool) -> Self {
+        self.dedupe_description = Some(dedupe_description);
+        self
+    }
+
+    pub fn retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    pub fn lossy_decode_fallback(mut self, lossy_decode_fallback: bool) -> Self {
+        self.lossy_decode_fallback = Some(lossy_decode_fallback);
+        self
+    }
+
+    pub fn skip_syndicated(mut self, skip_syndicated: bool) -> Self {
+        self.skip_syndicated = Some(skip_syndicated);
+        self
+    }
+
+    /// Treat pages with [`crate::article::StructuredContent`], like recipes,
+    /// as incomplete.
+    pub fn skip_structured(mut self, skip_structured: bool) -> Self {
+        self.skip_structured = Some(skip_structured);
+        self
+    }
+
+    /// Repair mojibake, like `donâ€™t`, and compose the diacritics of the
+    /// title, description and text, see [`Extractor::sanitize_unicode`].
+    pub fn normalize_unicode(mut self, normalize_unicode: bool) -> Self {
+        self.normalize_unicode = Some(normalize_unicode);
+        self
+    }
+
+    /// Treat product pages, see [`crate::article::Product`], as incomplete.
+    pub fn skip_products(mut self, skip_products: bool) -> Self {
+        self.skip_products = Some(skip_products);
+        self
+    }
+
+    /// Suppress articles with the same [`ArticleContent::content_hash`] as an
+    /// article that was already yielded, like syndicated copies.
+    pub fn dedupe_by_content(mut self, dedupe_by_content: bool) -> Self {
+        self.dedupe_by_content = Some(dedupe_by_content);
+        self
+    }
+
+    /// Extract the comments rendered on the page into
+    /// [`ArticleContent::comments`], see [`Extractor::comments`].
+    ///
+    /// Disabled by default.
+    pub fn extract_comments(mut self, extract_comments: bool) -> Self {
+        self.extract_comments = Some(extract_comments);
+        self
+    }
+
+    /// Keep the tracking pixels and beacons in [`ArticleContent::images`],
+    /// that are removed by [`Extractor::strip_tracking_pixels_and_beacons`]
+    /// otherwise.
+    ///
+    /// Disabled by default.
+    pub fn keep_tracking_images(mut self, keep_tracking_images: bool) -> Self {
+        self.keep_tracking_images = Some(keep_tracking_images);
+        self
+    }
+
+    /// Caps the number of text nodes that are scored to find the article's
+    /// text, so that a single huge page can't stall the crawl, see
+    /// [`Extractor::article_node_with`].
+    ///
+    /// Defaults to the [`Extractor::max_nodes_to_score`] of the extractor.
+    pub fn max_nodes_to_score(mut self, max_nodes_to_score: usize) -> Self {
+        self.max_nodes_to_score = Some(max_nodes_to_score);
+        self
+    }
+
+    /// Download the media of the articles of [`Extrablatt::into_stream`],
+    /// [`Extrablatt::download_articles`] and [`Article::get_all`], see
+    /// [`ArticleContent::download_media`]. Files larger than
+    /// [`ConfigBuilder::max_body_bytes`] are skipped. Not supported on wasm.
+    ///
+    /// Disabled by default.
+    pub fn download_media(mut self, download_media: bool) -> Self {
+        self.download_media = Some(download_media);
+        self
+    }
+
+    /// Remove the query of category urls, enabled by default. Disable this
+    /// for sites with query driven sections, like `/?section=sports`.
+    pub fn strip_category_query(mut self, strip_category_query: bool) -> Self {
+        self.strip_category_query = Some(strip_category_query);
+        self
+    }
+
+    /// Queue the articles of the Google News sitemaps listed in the site's
+    /// `robots.txt` while building, see
+    /// [`Extrablatt::insert_news_sitemap_articles`].
+    pub fn follow_sitemap_news(mut self, follow_sitemap_news: bool) -> Self {
+        self.follow_sitemap_news = Some(follow_sitemap_news);
+        self
+    }
+
+    /// Wait a random delay in `[0, request_jitter)` before each request, so
+    /// that concurrent requests aren't sent in bursts.
+    pub fn request_jitter(mut self, request_jitter: Duration) -> Self {
+        self.request_jitter = Some(request_jitter);
+        self
+    }
+
+    /// Seed for the random delays of [`ConfigBuilder::request_jitter`].
+    ///
+    /// Default is the current time.
+    pub fn jitter_seed(mut self, jitter_seed: u64) -> Self {
+        self.jitter_seed = Some(jitter_seed);
+        self
+    }
+
+    /// Rewrite mobile and AMP article urls to their desktop equivalent before
+    /// fetching, see [`crate::extract::desktop_variant`].
+    pub fn normalize_to_desktop(mut self, normalize_to_desktop: bool) -> Self {
+        self.normalize_to_desktop = Some(normalize_to_desktop);
+        self
+    }
+
+    /// Treat articles whose share of affiliate links exceeds
+    /// `max_affiliate_ratio` (e.g. `0.5` for half of the links) as
+    /// incomplete, see [`crate::article::LinkStats::affiliate_ratio`].
+    pub fn max_affiliate_ratio(mut self, max_affiliate_ratio: f64) -> Self {
+        self.max_affiliate_ratio = Some(max_affiliate_ratio);
+        self
+    }
+
+    /// Treat articles published longer ago than `max_article_age` as
+    /// incomplete. Articles without a publishing date are kept.
+    pub fn max_article_age(mut self, max_article_age: Duration) -> Self {
+        self.max_article_age = Some(max_article_age);
+        self
+    }
+
+    /// Replaces the default [`ARTICLE_ID_QUERY_PARAMS`].
+    pub fn article_id_query_params<I, T>(mut self, params: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: ToString,
+    {
+        self.article_id_query_params = Some(params.into_iter().map(|p| p.to_string()).collect());
+        self
+    }
+
+    /// Responses with a larger body fail with
+    /// [`ExtrablattError::BodyTooLarge`].
+    pub fn max_body_bytes(mut self, max_body_bytes: usize) -> Self {
+        self.max_body_bytes = Some(max_body_bytes);
+        self
+    }
+
+    /// Removes the leading and trailing paragraphs of the text that contain
+    /// one of the `phrases`, like the
+    /// [`crate::clean::DEFAULT_BOILERPLATE_PHRASES`].
+    ///
+    /// Empty by default.
+    pub fn boilerplate_phrases<I, T>(mut self, phrases: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: ToString,
+    {
+        self.boilerplate_phrases = Some(phrases.into_iter().map(|p| p.to_string()).collect());
+        self
+    }
+
     pub fn build(self) -> Config {
+        let browser_profile = self.browser_profile;
         Config {
             min_word_count: self.min_word_count,
             max_word_count: self.max_word_count,
             min_sentence_count: self.min_sentence_count,
+            min_paragraph_count: self.min_paragraph_count,
             min_title_len: self.min_title_len,
             max_title_len: self.max_title_len,
             min_text_len: self.min_text_len,
             max_text_len: self.max_text_len,
+            truncate_text_to: self.truncate_text_to,
             min_keywords: self.min_keywords,
             max_keywords: self.max_keywords,
             min_authors: self.min_authors,
             max_authors: self.max_authors,
             max_doc_cache: self.max_doc_cache.unwrap_or(2_0000),
+            max_concurrent_requests: self
+                .max_concurrent_requests
+                .unwrap_or(Config::DEFAULT_MAX_CONCURRENT_REQUESTS),
             http_success_only: self.http_success_only.unwrap_or(true),
-            user_agent: self.user_agent.unwrap_or_else(Config::user_agent),
+            user_agent: self
+                .user_agent
+                .or_else(|| browser_profile.map(|profile| profile.user_agent().to_string()))
+                .unwrap_or_else(Config::user_agent),
+            accept_language: self.accept_language,
+            headers_per_host: self.headers_per_host.unwrap_or_default(),
+            browser_profile,
             request_timeout: self
                 .request_timeout
                 .unwrap_or_else(|| Duration::from_secs(Config::DEFAULT_REQUEST_TIMEOUT_SEC)),
+            connect_timeout: self.connect_timeout,
+            cookie_store: self.cookie_store.unwrap_or_default(),
+            skip_web_stories: self.skip_web_stories.unwrap_or_default(),
+            description_as_text_fallback: self.description_as_text_fallback.unwrap_or_default(),
+            dedupe_description: self.dedupe_description.unwrap_or_default(),
+            retry: self.retry.unwrap_or_default(),
+            lossy_decode_fallback: self.lossy_decode_fallback.unwrap_or(true),
+            skip_syndicated: self.skip_syndicated.unwrap_or_default(),
+            skip_structured: self.skip_structured.unwrap_or_default(),
+            skip_products: self.skip_products.unwrap_or_default(),
+            normalize_unicode: self.normalize_unicode.unwrap_or_default(),
+            dedupe_by_content: self.dedupe_by_content.unwrap_or_default(),
+            extract_comments: self.extract_comments.unwrap_or_default(),
+            keep_tracking_images: self.keep_tracking_images.unwrap_or_default(),
+            max_nodes_to_score: self.max_nodes_to_score,
+            download_media: self.download_media.unwrap_or_default(),
+            strip_category_query: self.strip_category_query.unwrap_or(true),
+            follow_sitemap_news: self.follow_sitemap_news.unwrap_or_default(),
+            request_jitter: self.request_jitter,
+            jitter_seed: self.jitter_seed,
+            article_id_query_params: self.article_id_query_params.unwrap_or_else(|| {
+                ARTICLE_ID_QUERY_PARAMS
+                    .iter()
+                    .map(|p| p.to_string())
+                    .collect()
+            }),
+            max_body_bytes: self.max_body_bytes,
+            boilerplate_phrases: self.boilerplate_phrases.unwrap_or_default(),
+            max_article_age: self.max_article_age,
+            normalize_to_desktop: self.normalize_to_desktop.unwrap_or_default(),
+            max_affiliate_ratio: self.max_affiliate_ratio,
         }
     }
 
@@ -1189,20 +3376,130 @@ impl ConfigBuilder {
     pub fn with_restrictions() -> Self {
         Self {
             min_word_count: Some(300),
-            max_word_count: None,
             min_sentence_count: Some(7),
-            min_title_len: None,
             max_title_len: Some(200),
-            min_text_len: None,
             max_text_len: Some(100_000),
-            min_keywords: None,
-            max_keywords: None,
-            min_authors: None,
-            max_authors: None,
             max_doc_cache: Some(2_0000),
-            http_success_only: None,
-            user_agent: None,
-            request_timeout: None,
+            ..Default::default()
+        }
+    }
+}
+
+/// Checks that the `value` only consists of visible ASCII characters, spaces
+/// and tabs, so it can be sent as a header, and names the offending
+/// characters otherwise.
+pub(crate) fn validate_header_value(name: &str, value: &str) -> Result<()> {
+    let mut invalid: Vec<_> = value
+        .chars()
+        .filter(|c| !matches!(c, ' '..='~' | '\t'))
+        .collect();
+    if invalid.is_empty() {
+        return Ok(());
+    }
+    invalid.dedup();
+    Err(anyhow!(
+        "Invalid {} {:?}: contains {}",
+        name,
+        value,
+        invalid
+            .iter()
+            .map(|c| format!("{:?}", c))
+            .collect::<Vec<_>>()
+            .join(", ")
+    ))
+}
+
+/// Browsers whose headers are sent with requests, see
+/// [`ConfigBuilder::browser_profile`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub enum BrowserProfile {
+    /// Chrome on Windows.
+    Chrome,
+    /// Firefox on Windows.
+    Firefox,
+    /// Safari on macOS.
+    Safari,
+}
+
+impl BrowserProfile {
+    /// The `User-Agent` of the browser.
+    pub fn user_agent(&self) -> &'static str {
+        match self {
+            BrowserProfile::Chrome => "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36",
+            BrowserProfile::Firefox => "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:125.0) Gecko/20100101 Firefox/125.0",
+            BrowserProfile::Safari => "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.4 Safari/605.1.15",
+        }
+    }
+
+    /// The headers the browser sends when navigating to a page, including the
+    /// [`BrowserProfile::user_agent`].
+    pub fn headers(&self) -> HeaderMap {
+        let (accept, accept_language) = match self {
+            BrowserProfile::Chrome => (
+                "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,image/apng,*/*;q=0.8,application/signed-exchange;v=b3;q=0.7",
+                "en-US,en;q=0.9",
+            ),
+            BrowserProfile::Firefox => (
+                "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,*/*;q=0.8",
+                "en-US,en;q=0.5",
+            ),
+            BrowserProfile::Safari => (
+                "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8",
+                "en-US,en;q=0.9",
+            ),
+        };
+        let mut headers = vec![
+            ("user-agent", self.user_agent()),
+            ("accept", accept),
+            ("accept-language", accept_language),
+            ("upgrade-insecure-requests", "1"),
+            ("sec-fetch-dest", "document"),
+            ("sec-fetch-mode", "navigate"),
+            ("sec-fetch-site", "none"),
+        ];
+        match self {
+            BrowserProfile::Chrome => headers.extend([
+                ("sec-fetch-user", "?1"),
+                (
+                    "sec-ch-ua",
+                    r#""Chromium";v="124", "Google Chrome";v="124", "Not-A.Brand";v="99""#,
+                ),
+                ("sec-ch-ua-mobile", "?0"),
+                ("sec-ch-ua-platform", r#""Windows""#),
+            ]),
+            BrowserProfile::Firefox => headers.push(("sec-fetch-user", "?1")),
+            BrowserProfile::Safari => {}
+        }
+        headers
+            .into_iter()
+            .map(|(name, value)| {
+                (
+                    HeaderName::from_static(name),
+                    HeaderValue::from_static(value),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Configuration for repeating requests that failed with a retriable error,
+/// see [`ExtrablattError::is_retriable`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct RetryConfig {
+    /// Max. number of attempts for a single request, including the first one.
+    pub max_attempts: usize,
+    /// Delay before a failed request is sent again.
+    pub delay: Duration,
+}
+
+impl Default for RetryConfig {
+    /// Only a single attempt per request.
+    fn default() -> Self {
+        Self {
+            max_attempts: 1,
+            delay: Duration::from_secs(1),
         }
     }
 }
@@ -1217,6 +3514,10 @@ pub struct ArticleDownloadIter<'a, T: Extractor> {
     language: Language,
     /// Base url of the news source.
     base_url: &'a Url,
+    /// The configuration of the news source.
+    config: &'a Config,
+    /// The downloaded media of the articles, see [`Config::download_media`].
+    media: &'a HashMap<Url, Bytes>,
 }
 
 impl<'a, T: Extractor> ArticleDownloadIter<'a, T> {
@@ -1225,21 +3526,56 @@ impl<'a, T: Extractor> ArticleDownloadIter<'a, T> {
         let extractor = self.extractor;
         let language = self.language;
         let base_url = self.base_url;
+        let config = self.config;
+        let media = self.media;
+        let mut seen_hashes = FnvHashSet::default();
         self.inner.filter_map(move |(url, doc)| {
             if let DocumentDownloadState::Success { doc, .. } = doc {
-                Some((
-                    url,
-                    extractor.article_content(doc, Some(base_url), Some(language.clone())),
-                ))
+                let mut content =
+                    config.extract_content(extractor, doc, Some(base_url), Some(language.clone()));
+                if config.download_media {
+                    content.set_media(media);
+                }
+                if config.dedupe_by_content && !seen_hashes.insert(content.content_hash()) {
+                    return None;
+                }
+                Some((url, content))
             } else {
                 None
             }
         })
     }
-}
 
-impl<'a, T: Extractor> Deref for ArticleDownloadIter<'a, T> {
-    type Target = std::collections::hash_map::Iter<'a, ArticleUrl, DocumentDownloadState>;
+    /// All successfully retrieved articles whose content fulfills the
+    /// restrictions of the news source's [`Config`], see
+    /// [`Config::is_complete`].
+    pub fn successes_complete(
+        self,
+    ) -> impl Iterator<Item = (&'a ArticleUrl, ArticleContent<'a>)> + 'a {
+        let config = self.config;
+        self.successes()
+            .filter(move |(_, content)| config.is_complete(content))
+    }
+
+    /// All articles whose request or parsing failed, together with their
+    /// state.
+    pub fn failures(self) -> impl Iterator<Item = (&'a ArticleUrl, &'a DocumentDownloadState)> {
+        self.inner.filter(|(_, state)| {
+            !state.is_success() && !state.is_not_requested() && state.redirected_to().is_none()
+        })
+    }
+
+    /// All successfully retrieved articles whose content doesn't fulfill the
+    /// restrictions of the news source's [`Config`].
+    pub fn incomplete(self) -> impl Iterator<Item = (&'a ArticleUrl, ArticleContent<'a>)> + 'a {
+        let config = self.config;
+        self.successes()
+            .filter(move |(_, content)| !config.is_complete(content))
+    }
+}
+
+impl<'a, T: Extractor> Deref for ArticleDownloadIter<'a, T> {
+    type Target = std::collections::hash_map::Iter<'a, ArticleUrl, DocumentDownloadState>;
 
     fn deref(&self) -> &Self::Target {
         &self.inner
@@ -1251,3 +3587,1937 @@ impl<'a, T: Extractor> DerefMut for ArticleDownloadIter<'a, T> {
         &mut self.inner
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::article::{LinkStats, StructuredContent};
+    use crate::auth::FormLoginAuthenticator;
+    use crate::date::{ArticleDate, Date};
+    use crate::extract::ConfigurableExtractor;
+    use crate::testutil::{MockResponse, MockServer};
+    use select::predicate::Name;
+    use std::borrow::Cow;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Creates a new newspaper from the `html` main page without any requests.
+    fn paper(base_url: &str, html: &str) -> Extrablatt {
+        Extrablatt {
+            client: Client::new(),
+            language: Language::English,
+            main_page: Document::from(html),
+            base_url: Url::parse(base_url).unwrap(),
+            extractor: DefaultExtractor,
+            articles: Default::default(),
+            categories: Default::default(),
+            config: Config::default(),
+            jitter: Jitter::new(None),
+            news_metadata: Default::default(),
+            media: Default::default(),
+        }
+    }
+
+    #[test]
+    fn stream_dedupes_article_urls() {
+        let html = r#"<html><body>
+            <a href="https://extrablatt.com/politics/some-longer-title-with-more-dashes">Title</a>
+        </body></html>"#;
+        let paper = paper("https://extrablatt.com", html);
+        let main_page = Document::from(html);
+        let category = Document::from(html);
+
+        let mut stream = paper.into_stream();
+        let url = Url::parse("https://extrablatt.com/politics").unwrap();
+        stream.queue_category_articles(&url, &main_page);
+        stream.queue_category_articles(&url, &category);
+
+        assert_eq!(stream.pending_articles.len(), 1);
+    }
+
+    #[test]
+    fn stream_bounds_requests() {
+        let mut paper = paper("https://extrablatt.com", "<html></html>");
+        paper.config = Config::builder()
+            .max_doc_cache(1000)
+            .max_concurrent_requests(10)
+            .build();
+        for i in 0..5000 {
+            let url = Url::parse(&format!("https://extrablatt.com/article-{}", i)).unwrap();
+            paper
+                .articles
+                .insert(ArticleUrl::new(url), DocumentDownloadState::NotRequested);
+        }
+
+        let mut stream = paper.into_stream();
+        assert!(stream.article_responses.is_empty());
+        assert_eq!(stream.pending_articles.len(), 1000);
+
+        stream.fill_requests();
+        assert_eq!(stream.article_responses.len(), 10);
+        assert_eq!(stream.pending_articles.len(), 990);
+    }
+
+    #[test]
+    fn stream_cache_keeps_downloaded_articles() {
+        let url = |path: &str| Url::parse(&format!("https://extrablatt.com/{}", path)).unwrap();
+        let mut paper = paper("https://extrablatt.com", "<html></html>");
+        paper.config = Config::builder().max_doc_cache(2).build();
+        for i in 0..3 {
+            paper.articles.insert(
+                ArticleUrl::new(url(&format!("downloaded-{}", i))),
+                DocumentDownloadState::Success {
+                    received: Instant::now(),
+                    doc: Document::from(
+                        format!("<html><head><title>{}</title></head></html>", i).as_str(),
+                    ),
+                },
+            );
+        }
+        for i in 0..4 {
+            paper.articles.insert(
+                ArticleUrl::new(url(&format!("pending-{}", i))),
+                DocumentDownloadState::NotRequested,
+            );
+        }
+        for (i, day) in [(2, 1), (3, 2)].iter() {
+            paper.news_metadata.insert(
+                url(&format!("pending-{}", i)),
+                NewsMetadata {
+                    title: None,
+                    publication_date: chrono::NaiveDate::from_ymd_opt(2020, 5, *day)
+                        .and_then(|date| date.and_hms_opt(0, 0, 0)),
+                    keywords: Vec::new(),
+                },
+            );
+        }
+
+        let mut stream = paper.into_stream();
+        assert_eq!(stream.articles.len(), 3);
+        // the most recent articles are queued
+        assert_eq!(
+            stream.pending_articles,
+            vec![url("pending-3"), url("pending-2")]
+        );
+
+        stream.queue_article_urls(vec![ArticleUrl::new(url("new"))]);
+        assert_eq!(stream.pending_articles, vec![url("pending-2"), url("new")]);
+    }
+
+    #[test]
+    fn invalid_user_agent() {
+        let err = Config::builder()
+            .user_agent("extrablatt/0.1\nX-Injected: 1")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"Invalid user agent "extrablatt/0.1\nX-Injected: 1": contains '\n'"#
+        );
+        assert!(Config::builder().user_agent("extrablatt\u{7}").is_err());
+
+        let config = Config::builder()
+            .user_agent("Mozilla/5.0 (X11; Linux x86_64)")
+            .unwrap()
+            .build();
+        assert_eq!(config.user_agent, "Mozilla/5.0 (X11; Linux x86_64)");
+    }
+
+    #[test]
+    fn whitespace_text_is_incomplete() {
+        let config = Config::builder().min_text_len(1).min_title_len(1).build();
+        let content = ArticleContent::builder()
+            .title("Title".into())
+            .text("   \n ".into())
+            .build();
+        assert!(!config.is_complete(&content));
+
+        let content = ArticleContent::builder()
+            .title("Title".into())
+            .text("Text".into())
+            .build();
+        assert!(config.is_complete(&content));
+
+        let config = Config::builder().description_as_text_fallback(true).build();
+        let content = config.finalize_content(
+            ArticleContent::builder()
+                .text(" ".into())
+                .description("The description.".into())
+                .build(),
+        );
+        assert_eq!(content.text.as_deref(), Some("The description."));
+    }
+
+    #[test]
+    fn skips_old_articles() {
+        let config = Config::builder()
+            .max_article_age(Duration::from_secs(24 * 3600))
+            .build();
+        let published = |age: chrono::Duration| ArticleDate {
+            published: Date::DateTime((Utc::now() - age).naive_utc()),
+            last_updated: None,
+            published_from_modified: false,
+        };
+
+        let content = ArticleContent::builder()
+            .publishing_date(published(chrono::Duration::days(2)))
+            .build();
+        assert!(!config.is_complete(&content));
+
+        let content = ArticleContent::builder()
+            .publishing_date(published(chrono::Duration::hours(2)))
+            .build();
+        assert!(config.is_complete(&content));
+
+        assert!(config.is_complete(&ArticleContent::builder().build()));
+    }
+
+    #[test]
+    fn skips_structured_content() {
+        let recipe = ArticleContent::builder()
+            .structured(StructuredContent::Recipe {
+                name: None,
+                ingredients: vec!["2 eggs".to_string()],
+                steps: vec!["Boil the eggs.".to_string()],
+            })
+            .build();
+        assert!(Config::default().is_complete(&recipe));
+        assert!(!Config::builder()
+            .skip_structured(true)
+            .build()
+            .is_complete(&recipe));
+    }
+
+    #[test]
+    fn normalize_unicode() {
+        let doc = Document::from(concat!(
+            r#"<html><head><meta property="og:title" content="We donâ€™t know"></head>"#,
+            r#"<body><div itemprop="articleBody"><p>The cafe"#,
+            "\u{301}",
+            r#" in MÃ¼nchen.</p></div></body></html>"#
+        ));
+        let extractor = DefaultExtractor;
+        let content = Config::default().extract_content(&extractor, &doc, None, None);
+        assert_eq!(content.title.as_deref(), Some("We donâ€™t know"));
+
+        let content = Config::builder()
+            .normalize_unicode(true)
+            .build()
+            .extract_content(&extractor, &doc, None, None);
+        assert_eq!(content.title.as_deref(), Some("We don’t know"));
+        assert_eq!(content.text.as_deref(), Some("The café in München."));
+    }
+
+    #[test]
+    #[cfg(feature = "stopwords")]
+    fn max_nodes_to_score() {
+        let html = format!(
+            r#"<html><body><div>{}</div><div>{}</div></body></html>"#,
+            "<p>This is the teaser of the article and it has some words in it.</p>".repeat(2),
+            "<p>This is a paragraph of the article and it has some words in it.</p>".repeat(30)
+        );
+        let doc = Document::from(html.as_str());
+        let extractor = DefaultExtractor;
+        let content = Config::default().extract_content(&extractor, &doc, None, None);
+        assert!(content.text.unwrap().starts_with("This is a paragraph"));
+
+        let content = Config::builder()
+            .max_nodes_to_score(2)
+            .build()
+            .extract_content(&extractor, &doc, None, None);
+        assert!(content.text.unwrap().starts_with("This is the teaser"));
+    }
+
+    #[test]
+    fn skips_products() {
+        let doc = Document::from(
+            r#"<html><head><script type="application/ld+json">
+            {"@type": "Product", "name": "Headphones", "offers": {"price": "199.99"}}
+            </script></head><body><p>Our favorite headphones are on sale.</p></body></html>"#,
+        );
+        let extractor = DefaultExtractor;
+        let config = Config::default();
+        let content = config.extract_content(&extractor, &doc, None, None);
+        assert!(content.product.is_some());
+        assert!(config.is_complete(&content));
+        assert!(!Config::builder()
+            .skip_products(true)
+            .build()
+            .is_complete(&content));
+    }
+
+    #[test]
+    fn extract_comments_flag() {
+        let doc = Document::from(
+            r#"<html><body><div itemscope itemtype="https://schema.org/Comment">
+            <p itemprop="text">First!</p></div></body></html>"#,
+        );
+        let extractor = DefaultExtractor;
+        assert!(Config::default()
+            .extract_content(&extractor, &doc, None, None)
+            .comments
+            .is_empty());
+        let content = Config::builder()
+            .extract_comments(true)
+            .build()
+            .extract_content(&extractor, &doc, None, None);
+        assert_eq!(content.comments.len(), 1);
+        assert_eq!(content.comments[0].text, "First!");
+    }
+
+    #[test]
+    fn truncate_text() {
+        let config = Config::builder().truncate_text_to(30).build();
+        let content = config.finalize_content(
+            ArticleContent::builder()
+                .text("The council approved the new budget for the schools.".into())
+                .build(),
+        );
+        assert_eq!(
+            content.text.as_deref(),
+            Some("The council approved the new")
+        );
+        assert!(content.text_truncated);
+
+        let content = config.finalize_content(
+            ArticleContent::builder()
+                .text("The council approved it.".into())
+                .build(),
+        );
+        assert_eq!(content.text.as_deref(), Some("The council approved it."));
+        assert!(!content.text_truncated);
+    }
+
+    #[test]
+    fn min_paragraph_count() {
+        let config = Config::builder().min_paragraph_count(3).build();
+        let content = |text: &str| {
+            ArticleContent::builder()
+                .text(text.to_string().into())
+                .build()
+        };
+
+        assert!(!config.is_complete(&content(
+            "The council approved the new budget. The schools get more money."
+        )));
+        assert!(!config.is_complete(&content("First caption.\n\n \nSecond caption.")));
+        assert!(!config.is_complete(&ArticleContent::builder().build()));
+        assert!(config.is_complete(&content(
+            "The council approved the budget.\nThe schools get more money.\n\nTeachers welcomed it.\nParents did too."
+        )));
+    }
+
+    #[test]
+    fn min_sentence_count() {
+        let config = Config::builder().min_sentence_count(2).build();
+        let content = |text: &str, language| {
+            ArticleContent::builder()
+                .text(text.to_string().into())
+                .language(language)
+                .build()
+        };
+
+        assert!(!config.is_complete(&content("Dr. Smith went home.", Language::English)));
+        assert!(config.is_complete(&content(
+            "Dr. Smith went home. He was tired.",
+            Language::English
+        )));
+        assert!(config.is_complete(&content("他回家了。他很累。", Language::Chinese)));
+        assert!(!config.is_complete(&ArticleContent::builder().build()));
+    }
+
+    #[test]
+    fn skips_affiliate_heavy_articles() {
+        let config = Config::builder().max_affiliate_ratio(0.5).build();
+        let content = |affiliate| {
+            ArticleContent::builder()
+                .link_stats(LinkStats {
+                    internal: 1,
+                    outbound: 3,
+                    affiliate,
+                })
+                .build()
+        };
+        assert!(config.is_complete(&content(2)));
+        assert!(!config.is_complete(&content(3)));
+    }
+
+    #[test]
+    fn strips_boilerplate_paragraphs() {
+        let content = || {
+            ArticleContent::builder()
+                .text("The article's text.\n\nSign up for our newsletter!".into())
+                .build()
+        };
+        let config = Config::builder()
+            .boilerplate_phrases(crate::clean::DEFAULT_BOILERPLATE_PHRASES.iter())
+            .build();
+        let stripped = config.finalize_content(content());
+        assert_eq!(stripped.text.as_deref(), Some("The article's text."));
+
+        let kept = Config::default().finalize_content(content());
+        assert_eq!(kept.text, content().text);
+    }
+
+    #[test]
+    fn description_text_fallback() {
+        let doc = Document::from(
+            r#"<html><head><meta property="og:description" content="A short teaser."></head><body></body></html>"#,
+        );
+        let content = DefaultExtractor.article_content(&doc, None, None);
+        assert!(content.text.is_none());
+
+        let config = Config::builder().description_as_text_fallback(true).build();
+        let content = config.finalize_content(content);
+        assert_eq!(content.text.as_deref(), Some("A short teaser."));
+        assert_eq!(content.text_source, Some(TextSource::Description));
+    }
+
+    #[test]
+    fn dedupe_description() {
+        let content = |title: &'static str, description: &'static str| {
+            ArticleContent::builder()
+                .title(title.into())
+                .description(description.into())
+                .build()
+        };
+        let config = Config::builder().dedupe_description(true).build();
+
+        let same = content("Council Approves Budget", " council approves budget ");
+        assert!(Config::default()
+            .finalize_content(same.clone())
+            .description
+            .is_some());
+        assert!(config.finalize_content(same).description.is_none());
+
+        let suffix = content(
+            "Extrablatt | Council Approves Budget",
+            "Council Approves Budget",
+        );
+        assert!(config.finalize_content(suffix).description.is_none());
+
+        let teaser = content(
+            "Council Approves Budget",
+            "Council Approves Budget after a long debate on Monday.",
+        );
+        assert!(config.finalize_content(teaser).description.is_some());
+    }
+
+    #[tokio::test]
+    async fn authenticated_session() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/login" if req.body == "user=reader&password=secret" => MockResponse {
+                headers: vec![(
+                    "Set-Cookie".to_string(),
+                    "session=abc; Path=/; HttpOnly".to_string(),
+                )],
+                ..MockResponse::ok("")
+            },
+            "/login" => MockResponse::status(401),
+            _ if req.header("cookie") == Some("session=abc") => {
+                MockResponse::ok("<html><body>Subscriber content</body></html>")
+            }
+            _ => MockResponse::status(403),
+        });
+
+        let paper = Extrablatt::builder(server.url("/")).unwrap().build().await;
+        assert!(paper.is_err());
+
+        let auth = FormLoginAuthenticator::new(
+            server.url("/login"),
+            vec![("user", "reader"), ("password", "secret")],
+        );
+        let paper = Extrablatt::builder(server.url("/"))
+            .unwrap()
+            .authenticator(auth)
+            .build()
+            .await
+            .unwrap();
+        assert!(paper.get_response(server.url("/article")).await.is_ok());
+
+        let auth = FormLoginAuthenticator::new(server.url("/login"), vec![("user", "reader")]);
+        let paper = Extrablatt::builder(server.url("/"))
+            .unwrap()
+            .authenticator(auth)
+            .build()
+            .await;
+        assert!(paper.is_err());
+    }
+
+    #[tokio::test]
+    async fn cookie_store_is_opt_in() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/" => MockResponse {
+                headers: vec![("Set-Cookie".to_string(), "visitor=1; Path=/".to_string())],
+                ..MockResponse::ok("<html></html>")
+            },
+            _ if req.header("cookie").is_some() => MockResponse::status(409),
+            _ => MockResponse::ok("<html></html>"),
+        });
+
+        let paper = Extrablatt::builder(server.url("/"))
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+        assert!(paper.get_response(server.url("/article")).await.is_ok());
+
+        let paper = Extrablatt::builder(server.url("/"))
+            .unwrap()
+            .config(Config::builder().cookie_store(true).build())
+            .build()
+            .await
+            .unwrap();
+        assert!(paper.get_response(server.url("/article")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn follow_sitemap_news() {
+        let index_requests = Arc::new(AtomicUsize::new(0));
+        let skipped_requests = Arc::new(AtomicUsize::new(0));
+        let (index_counter, skipped_counter) =
+            (Arc::clone(&index_requests), Arc::clone(&skipped_requests));
+        let server = MockServer::start(move |req| match req.path.as_str() {
+            "/" => MockResponse::ok("<html></html>"),
+            "/robots.txt" => MockResponse::ok(
+                "User-agent: *\nSitemap: /sitemap_index.xml\nSitemap: /sitemap_index.xml\n",
+            ),
+            "/sitemap_index.xml" => {
+                index_counter.fetch_add(1, Ordering::SeqCst);
+                MockResponse::ok(
+                    r#"<sitemapindex>
+                    <sitemap><loc>/news-sitemap.xml</loc></sitemap>
+                    <sitemap><loc>/news-index.xml</loc></sitemap>
+                    <sitemap><loc>/archive-2019.xml</loc></sitemap>
+                    </sitemapindex>"#,
+                )
+            }
+            "/news-index.xml" => MockResponse::ok(
+                r#"<sitemapindex><sitemap><loc>/news-2019.xml</loc></sitemap></sitemapindex>"#,
+            ),
+            "/news-sitemap.xml" => {
+                let base = format!("http://{}", req.header("host").unwrap());
+                MockResponse::ok(
+                    include_str!("../fixtures/news_sitemap.xml")
+                        .replace("https://extrablatt.com", &base),
+                )
+            }
+            "/archive-2019.xml" | "/news-2019.xml" => {
+                skipped_counter.fetch_add(1, Ordering::SeqCst);
+                MockResponse::status(404)
+            }
+            _ => MockResponse::ok("<html><body>Article</body></html>"),
+        });
+
+        let mut paper = Extrablatt::builder(server.url("/"))
+            .unwrap()
+            .config(
+                Config::builder()
+                    .follow_sitemap_news(true)
+                    .max_concurrent_requests(1)
+                    .build(),
+            )
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(paper.articles.len(), 3);
+        assert_eq!(index_requests.load(Ordering::SeqCst), 1);
+        assert_eq!(skipped_requests.load(Ordering::SeqCst), 0);
+
+        let latest =
+            server.url("/politics/2020/06/03/storm-causes-power-outages-across-the-region");
+        let metadata = paper.news_metadata(&latest).unwrap();
+        assert_eq!(
+            metadata.title.as_deref(),
+            Some("Storm causes power outages across the region")
+        );
+        assert_eq!(metadata.keywords, vec!["Weather", "Storm", "Region"]);
+
+        let mut order = Vec::new();
+        paper
+            .download_articles_with(|url, _| order.push(url.path().to_string()))
+            .await;
+        assert_eq!(
+            order,
+            vec![
+                "/politics/2020/06/03/storm-causes-power-outages-across-the-region",
+                "/politics/2020/06/02/local-team-wins-championship-after-extra-time",
+                "/politics/2020/06/01/city-council-approves-new-budget-for-schools",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn prefetch_categories() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/" => {
+                MockResponse::ok(r#"<html><body><a href="/politics">Politics</a></body></html>"#)
+            }
+            "/politics" => MockResponse::ok(
+                r#"<html><body>
+                <a href="/politics/2020/06/01/city-council-approves-new-budget-for-schools">Title</a>
+                </body></html>"#,
+            ),
+            _ => MockResponse::status(404),
+        });
+
+        let paper = Extrablatt::builder(server.url("/"))
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+        assert!(paper.articles.is_empty());
+
+        let paper = Extrablatt::builder(server.url("/"))
+            .unwrap()
+            .prefetch_categories(true)
+            .build()
+            .await
+            .unwrap();
+        assert!(paper
+            .categories
+            .values()
+            .all(|state| !state.is_not_requested()));
+        assert_eq!(paper.articles.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn accept_language_header() {
+        let headers = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let captured = Arc::clone(&headers);
+        let server = MockServer::start(move |req| {
+            captured.lock().unwrap().push((
+                req.path.clone(),
+                req.header("accept-language").map(str::to_string),
+            ));
+            MockResponse::ok("<html></html>")
+        });
+
+        let mut paper = Extrablatt::builder(server.url("/"))
+            .unwrap()
+            .config(Config::builder().accept_language("en-GB").build())
+            .build()
+            .await
+            .unwrap();
+        paper
+            .download_category(Category::new(server.url("/german/")))
+            .await
+            .unwrap();
+
+        let mut paper = Extrablatt::builder(server.url("/"))
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+        paper
+            .download_category(Category::new(server.url("/german/")))
+            .await
+            .unwrap();
+        paper.articles.insert(
+            ArticleUrl::new(server.url("/german/some-article")),
+            DocumentDownloadState::NotRequested,
+        );
+        paper
+            .download_article_urls(vec![server.url("/german/some-article")], &mut |_, _| {})
+            .await;
+        assert!(paper
+            .get_response(server.url("/german/other-article"))
+            .await
+            .is_ok());
+
+        let headers = headers.lock().unwrap();
+        assert_eq!(
+            *headers,
+            vec![
+                ("/".to_string(), Some("en-GB".to_string())),
+                // an explicit `Accept-Language` is never overridden
+                ("/german".to_string(), Some("en-GB".to_string())),
+                ("/".to_string(), None),
+                ("/german".to_string(), Some("de".to_string())),
+                // only categories get the hint
+                ("/german/some-article".to_string(), None),
+                ("/german/other-article".to_string(), None),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn headers_per_host() {
+        let headers = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let captured = Arc::clone(&headers);
+        let server = MockServer::start(move |req| {
+            captured
+                .lock()
+                .unwrap()
+                .push(req.header("x-outlet").map(str::to_string));
+            MockResponse::ok("<html></html>")
+        });
+        let outlet = |name: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert("x-outlet", reqwest::header::HeaderValue::from_static(name));
+            headers
+        };
+        let config = Config::builder()
+            .host_headers("127.0.0.1", outlet("a"))
+            .host_headers("localhost", outlet("b"))
+            .build();
+
+        let url_a = server.url("/");
+        let mut url_b = url_a.clone();
+        url_b.set_host(Some("localhost")).unwrap();
+        let mut paper = Extrablatt::builder(url_a.clone())
+            .unwrap()
+            .config(config)
+            .build()
+            .await
+            .unwrap();
+        paper.get_text(url_a).await.unwrap();
+        paper.get_text(url_b.clone()).await.unwrap();
+        paper.articles.insert(
+            ArticleUrl::new(url_b.join("/article").unwrap()),
+            DocumentDownloadState::NotRequested,
+        );
+        paper.download_articles().await;
+
+        assert_eq!(
+            *headers.lock().unwrap(),
+            vec![
+                Some("a".to_string()),
+                Some("a".to_string()),
+                Some("b".to_string()),
+                Some("b".to_string())
+            ]
+        );
+
+        let config = Config::builder()
+            .host_headers("news.com", outlet("news"))
+            .host_headers("sports.news.com", outlet("sports"))
+            .build();
+        let outlet_for = |url: &str| {
+            config
+                .headers_for(&Url::parse(url).unwrap())
+                .map(|headers| headers["x-outlet"].clone())
+        };
+        assert_eq!(
+            outlet_for("https://www.news.com/"),
+            Some(outlet("news")["x-outlet"].clone())
+        );
+        assert_eq!(
+            outlet_for("https://sports.news.com/"),
+            Some(outlet("sports")["x-outlet"].clone())
+        );
+        assert_eq!(outlet_for("https://othernews.com/"), None);
+    }
+
+    #[tokio::test]
+    async fn seed_article_urls() {
+        let server = MockServer::start(|req| {
+            if req.path == "/" {
+                MockResponse::ok("<html></html>")
+            } else {
+                MockResponse::ok(format!(
+                    r#"<html><head><meta property="og:title" content="Seeded {}"></head></html>"#,
+                    req.path
+                ))
+            }
+        });
+        let seed = server.url("/about");
+        assert!(!DefaultExtractor::is_article(
+            &ArticleUrl::new(seed.clone()),
+            &server.url("/")
+        ));
+
+        let mut paper = Extrablatt::builder(server.url("/"))
+            .unwrap()
+            .seed_article_urls(vec![seed.clone()])
+            .build()
+            .await
+            .unwrap();
+        let successes: Vec<_> = paper
+            .download_articles()
+            .await
+            .successes()
+            .map(|(url, content)| (url.url.clone(), content.title.clone()))
+            .collect();
+        assert_eq!(successes, vec![(seed, Some("Seeded /about".into()))]);
+    }
+
+    #[tokio::test]
+    async fn browser_profile() {
+        let headers = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let captured = Arc::clone(&headers);
+        let server = MockServer::start(move |req| {
+            let header = |name| req.header(name).unwrap_or_default().to_string();
+            captured.lock().unwrap().extend([
+                header("user-agent"),
+                header("accept"),
+                header("accept-language"),
+                header("sec-fetch-mode"),
+                header("upgrade-insecure-requests"),
+                header("sec-ch-ua"),
+            ]);
+            MockResponse::ok("<html></html>")
+        });
+
+        let config = Config::builder()
+            .browser_profile(BrowserProfile::Chrome)
+            .accept_language("de-DE")
+            .build();
+        assert_eq!(config.user_agent, BrowserProfile::Chrome.user_agent());
+        Extrablatt::builder(server.url("/"))
+            .unwrap()
+            .config(config)
+            .build()
+            .await
+            .unwrap();
+
+        let headers = headers.lock().unwrap();
+        assert!(headers[0].contains("Chrome/"));
+        assert!(headers[1].starts_with("text/html"));
+        // explicitly configured headers take precedence
+        assert_eq!(headers[2], "de-DE");
+        assert_eq!(headers[3], "navigate");
+        assert_eq!(headers[4], "1");
+        assert!(headers[5].contains("Google Chrome"));
+
+        let config = Config::builder()
+            .user_agent("extrablatt-test")
+            .unwrap()
+            .browser_profile(BrowserProfile::Firefox)
+            .build();
+        assert_eq!(config.user_agent, "extrablatt-test");
+    }
+
+    #[tokio::test]
+    async fn redirected_articles() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/old-slug" | "/short-link" => MockResponse {
+                status: 301,
+                headers: vec![("Location".to_string(), "/final".to_string())],
+                body: Vec::new(),
+            },
+            _ => MockResponse::ok("<html></html>"),
+        });
+        let mut paper = paper(server.url("/").as_str(), "<html></html>");
+        for path in &["/old-slug", "/short-link", "/other"] {
+            paper.articles.insert(
+                ArticleUrl::new(server.url(path)),
+                DocumentDownloadState::NotRequested,
+            );
+        }
+
+        let mut successes: Vec<_> = paper
+            .download_articles()
+            .await
+            .successes()
+            .map(|(url, _)| url.url.path().to_string())
+            .collect();
+        successes.sort();
+        assert_eq!(successes, vec!["/final", "/other"]);
+        assert_eq!(paper.articles.len(), 4);
+        assert_eq!(paper.iter_articles().failures().count(), 0);
+        for path in &["/old-slug", "/short-link"] {
+            assert_eq!(
+                paper.articles[&server.url(path)].redirected_to(),
+                Some(&server.url("/final"))
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn spider_redirected_articles() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&requests);
+        let server = MockServer::start(move |req| {
+            if req.path.starts_with("/politics/") {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+            match req.path.as_str() {
+                "/politics/2020/05/01/old-slug-of-the-budget-article" => MockResponse {
+                    status: 301,
+                    headers: vec![(
+                        "Location".to_string(),
+                        "/politics/2020/05/01/council-approves-the-budget-for-schools".to_string(),
+                    )],
+                    body: Vec::new(),
+                },
+                "/politics/2020/05/01/council-approves-the-budget-for-schools" => MockResponse::ok(
+                    r#"<html><body>
+                    <a href="/politics/2020/05/01/old-slug-of-the-budget-article">Budget</a>
+                    <a href="/politics/2020/05/02/teachers-welcome-the-decision-of-the-council">Teachers</a>
+                    </body></html>"#,
+                ),
+                _ => MockResponse::ok("<html></html>"),
+            }
+        });
+        let main_page = r#"<html><body>
+            <a href="/politics/2020/05/01/old-slug-of-the-budget-article">Budget</a>
+            </body></html>"#;
+        let mut spider = paper(server.url("/").as_str(), main_page);
+        assert_eq!(spider.spider(2, 10).await, 2);
+
+        let teachers =
+            server.url("/politics/2020/05/02/teachers-welcome-the-decision-of-the-council");
+        assert!(spider.articles[&teachers].is_success());
+        // the redirect, its target and the linked article are requested once each
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn download_progress_callback() {
+        let server = MockServer::start(|req| {
+            if req.path == "/missing" {
+                MockResponse::status(404)
+            } else {
+                MockResponse::ok("<html></html>")
+            }
+        });
+        let mut paper = paper(server.url("/").as_ref(), "<html></html>");
+        for path in &["/first", "/second", "/missing"] {
+            paper.articles.insert(
+                ArticleUrl::new(server.url(path)),
+                DocumentDownloadState::NotRequested,
+            );
+        }
+
+        let mut progress = Vec::new();
+        paper
+            .download_articles_with(|url, state| {
+                progress.push((url.path().to_string(), state.is_success()))
+            })
+            .await;
+        progress.sort();
+        assert!(paper
+            .articles
+            .values()
+            .all(|state| !state.is_not_requested()));
+        assert_eq!(
+            progress,
+            vec![
+                ("/first".to_string(), true),
+                ("/missing".to_string(), false),
+                ("/second".to_string(), true),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn stream_retries_failed_requests() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&requests);
+        let server = MockServer::start(move |req| {
+            assert_eq!(req.path, "/article");
+            if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                MockResponse::status(503)
+            } else {
+                MockResponse::ok("<html><head><title>Retried</title></head></html>")
+            }
+        });
+
+        let mut paper = paper(server.url("/").as_str(), "<html></html>");
+        paper.config = Config::builder()
+            .retry(RetryConfig {
+                max_attempts: 2,
+                delay: Duration::from_millis(10),
+            })
+            .build();
+        paper.articles.insert(
+            ArticleUrl::new(server.url("/article")),
+            DocumentDownloadState::NotRequested,
+        );
+        let mut stream = paper.into_stream();
+
+        let article = stream.next().await.unwrap().unwrap();
+        assert_eq!(article.url, server.url("/article"));
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn stream_cancellation() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&requests);
+        let server = MockServer::start(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            MockResponse::ok("<html><head><title>Article</title></head></html>")
+        });
+
+        let mut paper = paper(server.url("/").as_str(), "<html></html>");
+        paper.config = Config::builder().max_concurrent_requests(1).build();
+        for i in 0..10 {
+            paper.articles.insert(
+                ArticleUrl::new(server.url(&format!("/article-{}", i))),
+                DocumentDownloadState::NotRequested,
+            );
+        }
+        let cancel = Arc::new(AtomicBool::new(false));
+        let mut stream = paper.into_stream().with_cancel(Arc::clone(&cancel));
+
+        assert!(stream.next().await.unwrap().is_ok());
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        cancel.store(true, Ordering::SeqCst);
+        assert!(stream.next().await.is_none());
+        assert!(stream.article_responses.is_empty());
+        assert_eq!(stream.pending_articles.len(), 9);
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn stream_events() {
+        let article = "/food/2020/06/01/the-best-pizza-places-in-town";
+        let server = MockServer::start(move |req| match req.path.as_str() {
+            "/food" => MockResponse::ok(format!(
+                r#"<html><body><a href="{}">Pizza</a></body></html>"#,
+                article
+            )),
+            path if path == article => MockResponse::ok(include_str!("../fixtures/listicle.html")),
+            _ => MockResponse::status(404),
+        });
+        let mut paper = paper(server.url("/").as_str(), "<html></html>");
+        paper.categories.insert(
+            Category::new(server.url("/food")),
+            DocumentDownloadState::NotRequested,
+        );
+
+        let events: Vec<_> = paper.into_stream().events().collect().await;
+        let events: Vec<_> = events
+            .iter()
+            .map(|event| match event {
+                CrawlEvent::CategoryFetched(url) => format!("category {}", url.path()),
+                CrawlEvent::ArticleQueued(url) => format!("queued {}", url.path()),
+                CrawlEvent::ArticleReady(article) => format!("ready {}", article.url.path()),
+                CrawlEvent::Error(err) => format!("error {}", err),
+                CrawlEvent::Done => "done".to_string(),
+            })
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                "category /food".to_string(),
+                format!("queued {}", article),
+                format!("ready {}", article),
+                "done".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn stream_skips_index_pages() {
+        let server = MockServer::start(|_| {
+            MockResponse::ok(include_str!("../fixtures/category_index.html"))
+        });
+        let mut paper = paper(server.url("/").as_str(), "<html></html>");
+        paper.articles.insert(
+            ArticleUrl::new(server.url("/politics/latest-news-from-the-city-and-the-region")),
+            DocumentDownloadState::NotRequested,
+        );
+        let mut stream = paper.into_stream();
+
+        match stream.next().await.unwrap() {
+            Err(ExtrablattError::IncompleteArticle { .. }) => {}
+            other => panic!(
+                "expected an incomplete article, got {:?}",
+                other.map(|a| a.url)
+            ),
+        }
+        assert!(stream.next().await.is_none());
+    }
+
+    #[test]
+    fn strip_category_query() {
+        let html = r#"<html><body><nav>
+            <a href="/?section=sports">Sports</a>
+            <a href="/politics">Politics</a>
+            </nav></body></html>"#;
+        let categories = |paper: &Extrablatt| {
+            let mut urls: Vec<_> = paper.categories.keys().map(|c| c.url.to_string()).collect();
+            urls.sort();
+            urls
+        };
+
+        let mut paper = paper("https://extrablatt.com", html);
+        paper.insert_new_categories();
+        assert_eq!(
+            categories(&paper),
+            vec!["https://extrablatt.com/", "https://extrablatt.com/politics"]
+        );
+
+        paper.config = Config::builder().strip_category_query(false).build();
+        paper.clear();
+        paper.insert_new_categories();
+        assert_eq!(
+            categories(&paper),
+            vec![
+                "https://extrablatt.com/?section=sports",
+                "https://extrablatt.com/politics"
+            ]
+        );
+    }
+
+    #[test]
+    fn overridden_categories() {
+        #[derive(Debug)]
+        struct Politics;
+
+        impl Extractor for Politics {
+            fn categories(&self, _: &Document, base_url: &Url) -> Vec<Category> {
+                vec![Category::new(base_url.join("/politics").unwrap())]
+            }
+        }
+
+        let html = r#"<html><body><a href="/sports">Sports</a></body></html>"#;
+        let mut paper = paper("https://extrablatt.com", html).map_extractor(Politics);
+        paper.insert_new_categories();
+        let urls: Vec<_> = paper.categories.keys().map(|c| c.url.to_string()).collect();
+        assert_eq!(urls, vec!["https://extrablatt.com/politics"]);
+    }
+
+    #[test]
+    fn category_graph_edges() {
+        let mut paper = paper("https://extrablatt.com", "<html></html>");
+        let category = |path: &str| {
+            Category::new(
+                Url::parse("https://extrablatt.com")
+                    .unwrap()
+                    .join(path)
+                    .unwrap(),
+            )
+        };
+        paper.categories.insert(
+            category("/world"),
+            DocumentDownloadState::Success {
+                received: Instant::now(),
+                doc: Document::from(
+                    r#"<html><body><nav>
+                    <a href="/world">World</a>
+                    <a href="/sports/">Sports</a>
+                    <a href="/world/europe">Europe</a>
+                    <a href="/world/asia/">Asia</a>
+                    <a href="/world/europe/berlin">Berlin</a>
+                    <a href="/world/2020/05/01/some-long-article-title-about-the-world">Article</a>
+                    <a href="https://other.com/world/africa">Other site</a>
+                    </nav></body></html>"#,
+                ),
+            },
+        );
+        paper
+            .categories
+            .insert(category("/sports"), DocumentDownloadState::NotRequested);
+
+        let graph = paper.category_graph();
+        assert_eq!(
+            graph,
+            vec![(
+                category("/world"),
+                vec![
+                    category("/sports"),
+                    category("/world/asia"),
+                    category("/world/europe")
+                ]
+            )]
+        );
+    }
+
+    #[test]
+    fn seeded_jitter() {
+        let max = Duration::from_millis(100);
+        let delays = |seed| {
+            let jitter = Jitter::new(Some(seed));
+            (0..10).map(|_| jitter.delay(max)).collect::<Vec<_>>()
+        };
+        assert_eq!(delays(42), delays(42));
+        assert_ne!(delays(42), delays(43));
+        assert!(delays(42).iter().all(|delay| *delay < max));
+        assert_eq!(
+            Jitter::new(None).delay(Duration::default()),
+            Duration::default()
+        );
+    }
+
+    #[tokio::test]
+    async fn jitter_spreads_requests() {
+        let started = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let captured = Arc::clone(&started);
+        let server = MockServer::start(move |_| {
+            captured.lock().unwrap().push(Instant::now());
+            MockResponse::ok("<html></html>")
+        });
+
+        let max = Duration::from_millis(400);
+        let seed = 7;
+        let expected: Vec<_> = {
+            let jitter = Jitter::new(Some(seed));
+            (0..4).map(|_| jitter.delay(max)).collect()
+        };
+        let expected_spread = *expected.iter().max().unwrap() - *expected.iter().min().unwrap();
+        assert!(expected_spread > Duration::from_millis(100));
+
+        let mut paper = paper(server.url("/").as_str(), "<html></html>");
+        paper.apply_config(
+            Config::builder()
+                .max_concurrent_requests(4)
+                .request_jitter(max)
+                .jitter_seed(seed)
+                .build(),
+        );
+        for i in 0..4 {
+            paper.articles.insert(
+                ArticleUrl::new(server.url(&format!("/article-{}", i))),
+                DocumentDownloadState::NotRequested,
+            );
+        }
+        paper.download_articles().await;
+
+        let started = started.lock().unwrap();
+        assert_eq!(started.len(), 4);
+        let spread = *started.iter().max().unwrap() - *started.iter().min().unwrap();
+        assert!(spread + Duration::from_millis(50) >= expected_spread);
+    }
+
+    #[tokio::test]
+    async fn stream_dedupes_by_content() {
+        let server = MockServer::start(|req| {
+            let (title, text) = match req.path.as_str() {
+                "/original" => ("Council Votes", "The council approved the budget."),
+                "/mirror" => ("council votes", "The council   approved\nthe BUDGET."),
+                _ => ("Council Votes", "The council rejected the budget."),
+            };
+            MockResponse::ok(format!(
+                r#"<html><head><meta property="og:title" content="{}"></head>
+                <body><div itemprop="articleBody"><p>{}</p></div></body></html>"#,
+                title, text
+            ))
+        });
+
+        let mut paper = paper(server.url("/").as_str(), "<html></html>");
+        paper.config = Config::builder().dedupe_by_content(true).build();
+        for path in &["/original", "/mirror", "/other"] {
+            paper.articles.insert(
+                ArticleUrl::new(server.url(path)),
+                DocumentDownloadState::NotRequested,
+            );
+        }
+        let stream = paper.into_stream();
+
+        let articles: Vec<_> = stream.collect().await;
+        assert_eq!(articles.len(), 2);
+        let paths: Vec<_> = articles
+            .into_iter()
+            .map(|article| article.unwrap().url.path().to_string())
+            .collect();
+        assert!(paths.contains(&"/other".to_string()));
+        assert!(
+            !(paths.contains(&"/original".to_string()) && paths.contains(&"/mirror".to_string()))
+        );
+    }
+
+    #[test]
+    fn text_len_counts_chars() {
+        // 13 characters, but 25 bytes
+        let text = "مرحبا بالعالم";
+        let chars = text.chars().count();
+        assert!(text.len() > chars);
+
+        let content = ArticleContent::builder().text(text.into()).build();
+        let config = Config::builder()
+            .min_text_len(chars)
+            .max_text_len(chars)
+            .build();
+        assert!(config.is_complete(&content));
+
+        let config = Config::builder().max_text_len(chars - 1).build();
+        assert!(!config.is_complete(&content));
+    }
+
+    #[test]
+    fn lossy_document_fallback() {
+        let mut body = b"<html><head><title>Caf".to_vec();
+        body.push(0xe9);
+        body.extend_from_slice(b"</title></head><body><p>Text</p></body></html>");
+
+        assert!(read_document(&body, false).is_none());
+        let doc = read_document(&body, true).unwrap();
+        let title = doc.find(Name("title")).next().unwrap().text();
+        assert_eq!(title, "Caf\u{fffd}");
+    }
+
+    #[tokio::test]
+    async fn build_with_connect_timeout() {
+        let server = MockServer::start(|_| MockResponse::ok("<html></html>"));
+        let config = Config::builder()
+            .connect_timeout(Duration::from_secs(1))
+            .build();
+        assert_eq!(config.connect_timeout, Some(Duration::from_secs(1)));
+
+        let paper = Extrablatt::builder(server.url("/"))
+            .unwrap()
+            .config(config)
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(paper.config.connect_timeout, Some(Duration::from_secs(1)));
+    }
+
+    #[tokio::test]
+    async fn multi_source_stream() {
+        fn site() -> MockServer {
+            MockServer::start(|req| match req.path.as_str() {
+                "/" => MockResponse::ok(r#"<html><a href="/politics">Politics</a></html>"#),
+                "/politics" => MockResponse::ok(
+                    r#"<html><a href="/politics/some-longer-title-with-more-dashes">Article</a></html>"#,
+                ),
+                "/politics/some-longer-title-with-more-dashes" => {
+                    MockResponse::ok("<html><head><title>Article</title></head></html>")
+                }
+                _ => MockResponse::status(404),
+            })
+        }
+        let (first, second) = (site(), site());
+
+        let mut urls = MultiSource::new()
+            .url(first.url("/"))
+            .unwrap()
+            .url(second.url("/"))
+            .unwrap()
+            .into_stream()
+            .filter_map(|article| async move { article.ok().map(|article| article.url) })
+            .collect::<Vec<_>>()
+            .await;
+        urls.sort();
+
+        let mut expected = vec![
+            first.url("/politics/some-longer-title-with-more-dashes"),
+            second.url("/politics/some-longer-title-with-more-dashes"),
+        ];
+        expected.sort();
+        assert_eq!(urls, expected);
+
+        let events: Vec<_> = MultiSource::new()
+            .url(first.url("/"))
+            .unwrap()
+            .url(second.url("/"))
+            .unwrap()
+            .events()
+            .collect()
+            .await;
+        let articles = events
+            .iter()
+            .filter(|event| matches!(event, CrawlEvent::ArticleReady(_)))
+            .count();
+        assert_eq!(articles, 2);
+        let done = events
+            .iter()
+            .filter(|event| matches!(event, CrawlEvent::Done))
+            .count();
+        assert_eq!(done, 1);
+        assert!(matches!(events.last(), Some(CrawlEvent::Done)));
+
+        let cancel = Arc::new(AtomicBool::new(true));
+        let articles = MultiSource::new()
+            .url(first.url("/"))
+            .unwrap()
+            .with_cancel(cancel)
+            .into_stream()
+            .collect::<Vec<_>>()
+            .await;
+        assert!(articles.is_empty());
+
+        // the main page of the slow site only responds once released
+        let released = Arc::new(AtomicBool::new(false));
+        let slow = {
+            let released = Arc::clone(&released);
+            MockServer::start(move |_| {
+                for _ in 0..100 {
+                    if released.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    std::thread::sleep(Duration::from_millis(50));
+                }
+                MockResponse::ok("<html></html>")
+            })
+        };
+        let failing = MockServer::start(|_| MockResponse::status(500));
+        let mut stream = Box::pin(
+            MultiSource::new()
+                .url(slow.url("/"))
+                .unwrap()
+                .url(first.url("/"))
+                .unwrap()
+                .url(failing.url("/"))
+                .unwrap()
+                .into_stream(),
+        );
+        let start = std::time::Instant::now();
+        let mut items = [stream.next().await.unwrap(), stream.next().await.unwrap()];
+        assert!(start.elapsed() < Duration::from_secs(4));
+        released.store(true, Ordering::SeqCst);
+        assert!(stream.next().await.is_none());
+
+        items.sort_by_key(Result::is_ok);
+        assert!(matches!(
+            &items[0],
+            Err(ExtrablattError::SourceBuildFailure { url: Some(url), .. }) if *url == failing.url("/")
+        ));
+        assert_eq!(
+            items[1].as_ref().unwrap().url,
+            first.url("/politics/some-longer-title-with-more-dashes")
+        );
+    }
+
+    #[tokio::test]
+    async fn map_extractor_keeps_cache() {
+        struct TitleExtractor;
+
+        impl Extractor for TitleExtractor {
+            fn title<'a>(&self, _: &'a Document) -> Option<Cow<'a, str>> {
+                Some("Custom".into())
+            }
+        }
+
+        let mut paper = paper("https://extrablatt.com", "<html></html>");
+        paper.articles.insert(
+            ArticleUrl::new(
+                Url::parse("https://extrablatt.com/politics/some-longer-title-with-more-dashes")
+                    .unwrap(),
+            ),
+            DocumentDownloadState::Success {
+                received: Instant::now(),
+                doc: Document::from("<html><head><title>Default</title></head></html>"),
+            },
+        );
+
+        let paper = paper.map_extractor(TitleExtractor);
+        let article = paper.into_stream().next().await.unwrap().unwrap();
+        assert_eq!(article.content.title.as_deref(), Some("Custom"));
+    }
+
+    #[test]
+    fn extractor_mut() {
+        let mut paper = paper("https://extrablatt.com", "<html></html>");
+        paper.articles.insert(
+            ArticleUrl::new(
+                Url::parse("https://extrablatt.com/politics/some-longer-title-with-more-dashes")
+                    .unwrap(),
+            ),
+            DocumentDownloadState::Success {
+                received: Instant::now(),
+                doc: Document::from(
+                    r#"<html><head><title>Document Title</title>
+                    <meta property="og:title" content="Open Graph Title"></head></html>"#,
+                ),
+            },
+        );
+        let mut paper = paper.map_extractor(ConfigurableExtractor::default());
+        let title = |paper: &Extrablatt<ConfigurableExtractor>| {
+            let (_, content) = paper.iter_articles().successes().next().unwrap();
+            content.title.map(Cow::into_owned)
+        };
+        assert_eq!(title(&paper).as_deref(), Some("Open Graph Title"));
+
+        paper.extractor_mut().config_mut().title_strategy = crate::extract::TitleStrategy::Document;
+        assert_eq!(title(&paper).as_deref(), Some("Document Title"));
+    }
+
+    #[tokio::test]
+    async fn download_media() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/article" => MockResponse::ok(
+                r#"<html><head><title>Article</title></head><body>
+                <div itemprop="articleBody">
+                <img src="/photo.jpg" alt="The new bridge">
+                <img src="/missing.jpg" alt="Missing">
+                <img src="/large.jpg" alt="Large">
+                <p>The text of the article.</p>
+                </div></body></html>"#,
+            ),
+            "/photo.jpg" => MockResponse::ok(&b"\xFF\xD8 photo"[..]),
+            "/large.jpg" => MockResponse::ok(vec![0u8; 4096]),
+            _ => MockResponse::status(404),
+        });
+        let paper = |config: ConfigBuilder| {
+            let mut paper = paper(server.url("/").as_str(), "<html></html>");
+            paper.config = config.build();
+            paper.articles.insert(
+                ArticleUrl::new(server.url("/article")),
+                DocumentDownloadState::NotRequested,
+            );
+            paper
+        };
+        let article = |config: ConfigBuilder| {
+            let paper = paper(config);
+            async move { paper.into_stream().next().await.unwrap().unwrap() }
+        };
+
+        let images = article(Config::builder().download_media(true))
+            .await
+            .content
+            .images;
+        assert_eq!(images.len(), 3);
+        assert_eq!(images[0].bytes.as_deref(), Some(&b"\xFF\xD8 photo"[..]));
+        assert_eq!(images[1].bytes, None);
+        assert_eq!(images[2].bytes.as_ref().map(Bytes::len), Some(4096));
+        #[cfg(feature = "serde0")]
+        {
+            let json = serde_json::to_string(&images[0]).unwrap();
+            let image: crate::image::Image = serde_json::from_str(&json).unwrap();
+            assert_eq!(image.bytes, images[0].bytes);
+        }
+
+        let images = article(Config::builder()).await.content.images;
+        assert!(images.iter().all(|image| image.bytes.is_none()));
+
+        // the large image exceeds the max. body size
+        let config = Config::builder().download_media(true).max_body_bytes(1024);
+        let images = article(config).await.content.images;
+        assert!(images[0].bytes.is_some());
+        assert_eq!(images[2].bytes, None);
+
+        let mut paper = paper(Config::builder().download_media(true));
+        let (_, content) = paper.download_articles().await.successes().next().unwrap();
+        assert_eq!(
+            content.images[0].bytes.as_deref(),
+            Some(&b"\xFF\xD8 photo"[..])
+        );
+    }
+
+    #[test]
+    fn query_article_urls() {
+        let main_page = Document::from(
+            r#"<html><a href="/?p=12345">Article</a><a href="/?utm_source=x">Other</a></html>"#,
+        );
+        let mut paper = paper("https://extrablatt.com", "<html></html>");
+        let urls: Vec<_> = paper
+            .article_urls(&main_page)
+            .into_iter()
+            .map(|a| a.url.to_string())
+            .collect();
+        assert_eq!(urls, vec!["https://extrablatt.com/?p=12345"]);
+
+        paper.config = Config::builder()
+            .article_id_query_params(vec!["story"])
+            .build();
+        assert!(paper.article_urls(&main_page).is_empty());
+    }
+
+    #[test]
+    fn desktop_article_urls() {
+        let main_page = Document::from(
+            r#"<html><a href="/2020/05/01/some-long-article-title/amp/">Article</a></html>"#,
+        );
+        let mut paper = paper("https://extrablatt.com", "<html></html>");
+        let urls = |paper: &Extrablatt| -> Vec<_> {
+            paper
+                .article_urls(&main_page)
+                .into_iter()
+                .map(|a| a.url.to_string())
+                .collect()
+        };
+        assert_eq!(
+            urls(&paper),
+            vec!["https://extrablatt.com/2020/05/01/some-long-article-title/amp/"]
+        );
+
+        paper.config = Config::builder().normalize_to_desktop(true).build();
+        assert_eq!(
+            urls(&paper),
+            vec!["https://extrablatt.com/2020/05/01/some-long-article-title/"]
+        );
+    }
+
+    #[test]
+    fn apply_config_refilters_articles() {
+        let main_page = Document::from(
+            r#"<html>
+            <a href="/?p=12345">Query Article</a>
+            <a href="/2020/05/01/some-long-article-title/">Article</a>
+            </html>"#,
+        );
+        let mut paper = paper("https://extrablatt.com", "<html></html>");
+        paper.insert_article_urls(&main_page);
+        paper.articles.insert(
+            ArticleUrl::new(Url::parse("https://extrablatt.com/?p=1").unwrap()),
+            DocumentDownloadState::Success {
+                received: Instant::now(),
+                doc: Document::from("<html></html>"),
+            },
+        );
+        assert_eq!(paper.articles.len(), 3);
+
+        paper.apply_config(
+            Config::builder()
+                .article_id_query_params(vec!["story"])
+                .build(),
+        );
+        let mut urls: Vec<_> = paper.articles.keys().map(|a| a.url.to_string()).collect();
+        urls.sort();
+        assert_eq!(
+            urls,
+            vec![
+                "https://extrablatt.com/2020/05/01/some-long-article-title/",
+                "https://extrablatt.com/?p=1"
+            ]
+        );
+
+        paper.apply_config(Config::builder().max_doc_cache(1).build());
+        assert_eq!(paper.articles.len(), 1);
+        assert!(paper.articles.values().all(|state| state.is_success()));
+    }
+
+    #[test]
+    fn download_iter_outcomes() {
+        let mut paper = paper("https://extrablatt.com", "<html></html>");
+        paper.config = Config::builder().min_title_len(5).build();
+        let article = |path: &str| {
+            ArticleUrl::new(
+                Url::parse("https://extrablatt.com")
+                    .unwrap()
+                    .join(path)
+                    .unwrap(),
+            )
+        };
+        let success = |title: &str| DocumentDownloadState::Success {
+            received: Instant::now(),
+            doc: Document::from(
+                format!(
+                    r#"<html><head><meta property="og:title" content="{}"></head></html>"#,
+                    title
+                )
+                .as_str(),
+            ),
+        };
+        paper
+            .articles
+            .insert(article("/complete"), success("Complete"));
+        paper.articles.insert(article("/short"), success("Tiny"));
+        paper.articles.insert(
+            article("/failed"),
+            DocumentDownloadState::HttpRequestFailure {
+                received: Instant::now(),
+            },
+        );
+        paper
+            .articles
+            .insert(article("/pending"), DocumentDownloadState::NotRequested);
+
+        let iter = || ArticleDownloadIter {
+            inner: paper.articles.iter(),
+            extractor: &paper.extractor,
+            language: paper.language.clone(),
+            base_url: &paper.base_url,
+            config: &paper.config,
+            media: &paper.media,
+        };
+
+        let paths = |urls: Vec<&ArticleUrl>| {
+            let mut paths: Vec<_> = urls.into_iter().map(|a| a.url.path().to_string()).collect();
+            paths.sort();
+            paths
+        };
+        assert_eq!(
+            paths(iter().successes().map(|(url, _)| url).collect()),
+            vec!["/complete", "/short"]
+        );
+        assert_eq!(
+            paths(iter().failures().map(|(url, _)| url).collect()),
+            vec!["/failed"]
+        );
+        assert_eq!(
+            paths(iter().incomplete().map(|(url, _)| url).collect()),
+            vec!["/short"]
+        );
+    }
+
+    #[tokio::test]
+    async fn spider_follows_article_links() {
+        let article = |n: usize| {
+            format!(
+                "/politics/2020/06/0{}/article-number-{}-about-the-city",
+                n, n
+            )
+        };
+        let server = MockServer::start(move |req| {
+            let n = (1..=4).find(|n| req.path == article(*n));
+            match n {
+                Some(n) => MockResponse::ok(format!(
+                    r#"<html><body><p>Article {}</p><a href="{}">Next</a><a href="/">Home</a></body></html>"#,
+                    n,
+                    article(n + 1)
+                )),
+                None => MockResponse::status(404),
+            }
+        });
+        let main_page = format!(
+            r#"<html><body><a href="{}">First</a></body></html>"#,
+            article(1)
+        );
+
+        let mut spider = paper(server.url("/").as_str(), &main_page);
+        assert_eq!(spider.spider(2, 100).await, 3);
+        let downloaded = |paper: &Extrablatt| {
+            let mut paths: Vec<_> = paper
+                .articles
+                .iter()
+                .filter(|(_, state)| state.is_success())
+                .map(|(url, _)| url.url.path().to_string())
+                .collect();
+            paths.sort();
+            paths
+        };
+        assert_eq!(
+            downloaded(&spider),
+            vec![article(1), article(2), article(3)]
+        );
+        // beyond the depth limit
+        assert!(!spider.articles.contains_key(&server.url(&article(4))));
+
+        let mut spider = paper(server.url("/").as_str(), &main_page);
+        assert_eq!(spider.spider(10, 2).await, 2);
+        assert_eq!(downloaded(&spider), vec![article(1), article(2)]);
+    }
+
+    #[cfg(feature = "serde0")]
+    #[test]
+    fn cache_round_trip() {
+        let mut paper = paper(
+            "https://extrablatt.com",
+            r#"<html><body><a href="/politics">Politics</a></body></html>"#,
+        );
+        let mut headers = HeaderMap::new();
+        headers.insert("x-outlet", HeaderValue::from_static("extrablatt"));
+        paper.config = Config::builder()
+            .min_word_count(10)
+            .host_headers("extrablatt.com", headers)
+            .build();
+        paper.insert_new_categories();
+        for (path, title) in &[
+            ("/politics/budget", "Budget"),
+            ("/politics/schools", "Schools"),
+        ] {
+            paper.articles.insert(
+                ArticleUrl::new_with_title(paper.base_url.join(path).unwrap(), Some(title)),
+                DocumentDownloadState::Success {
+                    received: Instant::now(),
+                    doc: Document::from(
+                        format!(
+                            r#"<html><head><meta property="og:title" content="{}"></head><body><p>Text &amp; more</p></body></html>"#,
+                            title
+                        )
+                        .as_str(),
+                    ),
+                },
+            );
+        }
+        paper.articles.insert(
+            ArticleUrl::new(paper.base_url.join("/politics/pending").unwrap()),
+            DocumentDownloadState::NotRequested,
+        );
+        paper.articles.insert(
+            ArticleUrl::new(paper.base_url.join("/politics/missing").unwrap()),
+            DocumentDownloadState::NoHttpSuccessResponse {
+                received: Instant::now(),
+            },
+        );
+
+        let path =
+            std::env::temp_dir().join(format!("extrablatt-cache-{}.json", std::process::id()));
+        paper.save_cache(&path).unwrap();
+        let loaded = Extrablatt::load_cache(&path, DefaultExtractor).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.base_url, paper.base_url);
+        assert_eq!(loaded.language, paper.language);
+        assert_eq!(loaded.config, paper.config);
+
+        // fields of an older cache that are missing are set to their default
+        let config: Config = serde_json::from_str(r#"{"min_word_count": 10}"#).unwrap();
+        assert_eq!(config, Config::builder().min_word_count(10).build());
+        assert_eq!(loaded.main_page.find(Name("a")).count(), 1);
+        assert_eq!(
+            loaded.categories.keys().collect::<Vec<_>>(),
+            paper.categories.keys().collect::<Vec<_>>()
+        );
+        assert_eq!(loaded.articles.len(), 4);
+        assert!(loaded
+            .articles
+            .iter()
+            .any(|(url, state)| { url.url.path() == "/politics/missing" && !state.is_success() }));
+
+        let contents = |paper: &Extrablatt| {
+            let mut contents: Vec<_> = paper
+                .iter_articles()
+                .successes()
+                .map(|(url, content)| {
+                    (
+                        url.title.clone(),
+                        content.title.map(Cow::into_owned),
+                        content.text.map(Cow::into_owned),
+                    )
+                })
+                .collect();
+            contents.sort();
+            contents
+        };
+        assert_eq!(contents(&loaded), contents(&paper));
+        assert_eq!(contents(&loaded).len(), 2);
+    }
+
+    #[test]
+    fn articles_matching_keywords() {
+        let mut paper = paper("https://extrablatt.com", "<html></html>");
+        for (path, keywords) in &[
+            ("/politics/budget", "Politics, Budget"),
+            ("/sports/final", "Sports, Football"),
+            ("/politics/schools", "Politics, Schools, Budget"),
+        ] {
+            paper.articles.insert(
+                ArticleUrl::new(paper.base_url.join(path).unwrap()),
+                DocumentDownloadState::Success {
+                    received: Instant::now(),
+                    doc: Document::from(
+                        format!(
+                            r#"<html><head><meta name="keywords" content="{}"></head></html>"#,
+                            keywords
+                        )
+                        .as_str(),
+                    ),
+                },
+            );
+        }
+        paper.articles.insert(
+            ArticleUrl::new(paper.base_url.join("/politics/pending").unwrap()),
+            DocumentDownloadState::NotRequested,
+        );
+
+        let mut paths: Vec<_> = paper
+            .articles_matching(|content| content.keywords.iter().any(|keyword| keyword == "Budget"))
+            .into_iter()
+            .map(|(url, _)| url.url.path().to_string())
+            .collect();
+        paths.sort();
+        assert_eq!(paths, vec!["/politics/budget", "/politics/schools"]);
+        assert!(paper.articles_matching(|_| false).is_empty());
+    }
+
+    #[tokio::test]
+    async fn download_successes_complete() {
+        let server = MockServer::start(|req| {
+            let text = if req.path == "/long" {
+                "The council approved the new budget for the schools today. ".repeat(40)
+            } else {
+                "The council approved the budget.".to_string()
+            };
+            MockResponse::ok(format!(
+                r#"<html><head><meta property="og:title" content="Council"></head>
+                <body><div itemprop="articleBody"><p>{}</p></div></body></html>"#,
+                text
+            ))
+        });
+        let mut paper = paper(server.url("/").as_str(), "<html></html>");
+        paper.config = ConfigBuilder::with_restrictions().build();
+        for path in &["/long", "/short"] {
+            paper.articles.insert(
+                ArticleUrl::new(server.url(path)),
+                DocumentDownloadState::NotRequested,
+            );
+        }
+
+        let mut successes: Vec<_> = paper
+            .download_articles()
+            .await
+            .successes()
+            .map(|(url, _)| url.url.path().to_string())
+            .collect();
+        successes.sort();
+        assert_eq!(successes, vec!["/long", "/short"]);
+
+        let complete: Vec<_> = paper
+            .iter_articles()
+            .successes_complete()
+            .map(|(url, _)| url.url.path().to_string())
+            .collect();
+        assert_eq!(complete, vec!["/long"]);
+    }
+
+    #[tokio::test]
+    async fn body_size_limit() {
+        let server = MockServer::start(|_| MockResponse::ok(vec![b'a'; 1024]));
+        let mut paper = paper(server.url("/").as_str(), "<html></html>");
+
+        paper.config = Config::builder().max_body_bytes(1024).build();
+        assert!(paper.get_response(server.url("/article")).await.is_ok());
+
+        paper.config = Config::builder().max_body_bytes(1000).build();
+        match paper.get_response(server.url("/article")).await {
+            Err((_, ExtrablattError::BodyTooLarge { url, limit })) => {
+                assert_eq!(url, server.url("/article"));
+                assert_eq!(limit, 1000);
+            }
+            _ => panic!("expected BodyTooLarge error"),
+        }
+    }
+
+    #[test]
+    fn known_site_config() {
+        let builder = ExtrablattBuilder::for_known_site("https://www.reuters.com/").unwrap();
+        assert_eq!(
+            builder.config.unwrap().user_agent,
+            crate::sites::BROWSER_USER_AGENT
+        );
+
+        let builder = ExtrablattBuilder::for_known_site("https://extrablatt.com/").unwrap();
+        assert!(builder.config.is_none());
+    }
+
+    #[test]
+    fn progress_counts() {
+        let mut paper = paper("https://extrablatt.com", "<html></html>");
+        let url = |path: &str| {
+            Url::parse("https://extrablatt.com/")
+                .unwrap()
+                .join(path)
+                .unwrap()
+        };
+
+        paper.categories.insert(
+            Category::new(url("/sports")),
+            DocumentDownloadState::NotRequested,
+        );
+        paper.categories.insert(
+            Category::new(url("/world")),
+            DocumentDownloadState::Success {
+                received: Instant::now(),
+                doc: Document::from(""),
+            },
+        );
+        paper.articles.insert(
+            ArticleUrl::new(url("/a")),
+            DocumentDownloadState::NotRequested,
+        );
+        paper.articles.insert(
+            ArticleUrl::new(url("/b")),
+            DocumentDownloadState::NotRequested,
+        );
+        paper.articles.insert(
+            ArticleUrl::new(url("/c")),
+            DocumentDownloadState::HttpRequestFailure {
+                received: Instant::now(),
+            },
+        );
+
+        assert_eq!(
+            paper.outstanding(),
+            Outstanding {
+                categories: 1,
+                articles: 2
+            }
+        );
+        assert_eq!(
+            paper.completed(),
+            Completed {
+                category_successes: 1,
+                category_failures: 0,
+                article_successes: 0,
+                article_failures: 1,
+            }
+        );
+    }
+}
@@ -10,20 +10,35 @@ use reqwest::Url;
 use select::document::Document;
 use select::node::Node;
 use select::predicate::{Attr, Class, Name, Predicate};
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use url::Host;
 
 use lazy_static::lazy_static;
 
 use crate::article::{
-    ArticleContent, ArticleUrl, ALLOWED_FILE_EXT, BAD_DOMAINS, BAD_SEGMENTS, GOOD_SEGMENTS,
+    ArticleContent, ArticleUrl, Attribution, Author, Comment, ContentTier, GeoLocation,
+    LinkConsistency, LinkStats, ListItem, Product, SectionSource, StructuredContent, Syndication,
+    TextSource, ALLOWED_FILE_EXT, BAD_DOMAINS, BAD_SEGMENTS, GOOD_SEGMENTS, TRACKING_DOMAINS,
 };
-use crate::clean::{DefaultDocumentCleaner, DocumentCleaner};
-use crate::date::{ArticleDate, DateExtractor, RE_DATE_SEGMENTS_M_D_Y, RE_DATE_SEGMENTS_Y_M_D};
+use crate::clean::{
+    compose_diacritics, has_bad_attr, normalize_whitespace, repair_mojibake, CommonCleaner,
+    DefaultDocumentCleaner, DocumentCleaner,
+};
+use crate::date::{
+    ArticleDate, Date, DateExtractor, RE_DATE_SEGMENTS_M_D_Y, RE_DATE_SEGMENTS_Y_M_D, RE_YEAR,
+};
+use crate::embed::{self, SocialEmbed};
+use crate::image::Image;
+use crate::jsonld;
 
 use crate::category::Category;
 use crate::nlp::CATEGORY_STOPWORDS;
-use crate::text::{author_text, ArticleTextNode, ArticleTextNodeExtractor};
-use crate::video::VideoNode;
+use crate::text::{
+    author_text, img_node_image, ArticleTextNode, ArticleTextNodeExtractor, ARTICLE_BODY_ATTR,
+};
+use crate::video::{self, Video, VideoNode};
 use crate::Language;
 
 lazy_static! {
@@ -31,7 +46,33 @@ lazy_static! {
     ///
     /// This strips leading "By " and also potential profile links.
     static ref RE_AUTHOR_NAME: Regex =
-        Regex::new(r"(?mi)(By)?\s*((<|(&lt;))a([^>]*)(>|(&gt;)))?(?P<name>[a-z ,.'-]+)((<|(&lt;))\\/a(>|(&gt;)))?").unwrap();
+        Regex::new(r"(?mi)(By)?\s*((<|(&lt;))a([^>]*)(>|(&gt;)))?(?P<name>[\p{L}\p{M} ,.'’-]+)((<|(&lt;))\\/a(>|(&gt;)))?").unwrap();
+
+    /// Regex for the `id` or `class` of a table of contents.
+    static ref RE_TOC: Regex =
+        Regex::new(r"(?i)(^|[\s_-])toc($|[\s_-])|table-of-contents").unwrap();
+
+    /// Regex for the text, `id` or `class` of links to the next page of a
+    /// paginated listing, like `Next page` or `load-more`.
+    static ref RE_NEXT_PAGE: Regex =
+        Regex::new(r"(?i)^\s*(next(\s+page)?|older\s+(posts|stories)|load\s+more|more\s+stories)\s*[›»→>]*\s*$|(^|[\s_-])(load-?more|next-?page|pagination-next)($|[\s_-])").unwrap();
+
+    /// Regex for a credit of an attribution line, like `Editing by John Smith`.
+    static ref RE_ATTRIBUTION: Regex = Regex::new(
+        r"(?i)^(?P<role>(?:additional )?(?:reporting|writing|editing|research|graphics)) by (?P<names>\S.*)$"
+    )
+    .unwrap();
+
+    /// Regex for the dateline at the start of an article's text, like
+    /// `BERLIN (Reuters) -` or `SAN FRANCISCO, Calif. —`.
+    static ref RE_DATELINE: Regex = Regex::new(
+        r"^\s*(?P<place>[A-Z][A-Z.'-]+(?: [A-Z][A-Z.'-]+)*(?:, [A-Z][A-Za-z.]+(?: [A-Z][A-Za-z.]+)*)?)\s*(?:\([^)]{1,40}\))?\s*[-—–]\s"
+    )
+    .unwrap();
+
+    /// Regex for numbered headings of listicles, like `3. Title` or `#3 Title`.
+    static ref RE_LISTICLE_HEADING: Regex =
+        Regex::new(r"^\s*(?:#|No\.\s*)?(?P<num>\d{1,3})\s*[.):-]?\s+\S").unwrap();
 }
 
 pub(crate) struct NodeValueQuery<'a> {
@@ -134,32 +175,31 @@ pub trait Extractor {
     ///    1.  `<h1>` takes precedent over `og:title`
     ///    2. `og:title` takes precedent over `<title>`
     fn title<'a>(&self, doc: &'a Document) -> Option<Cow<'a, str>> {
-        if let Some(title) = doc
-            .find(Name("h1"))
-            .filter_map(|node| node.as_text().map(str::trim))
-            .next()
-        {
-            return Some(Cow::Borrowed(title));
-        }
-
-        if let Some(title) = self.meta_content(doc, Attr("property", "og:title")) {
-            return Some(title);
-        }
-
-        if let Some(title) = self.meta_content(doc, Attr("name", "og:title")) {
-            return Some(title);
-        }
-
-        if let Some(title) = doc.find(Name("title")).next() {
-            return title.as_text().map(str::trim).map(Cow::Borrowed);
-        }
-        None
+        default_title(self, doc)
     }
 
     /// Extract all the listed authors for the article.
+    ///
+    /// Authors linked by `rel="author"` come first.
     fn authors<'a>(&self, doc: &'a Document) -> Vec<Cow<'a, str>> {
+        self.authors_with(doc, &self.json_ld(doc))
+    }
+
+    /// Like [`Extractor::authors`], but with the already parsed JSON-LD
+    /// objects of the `doc`, see [`Extractor::json_ld`].
+    fn authors_with<'a>(&self, doc: &'a Document, json_ld: &[Value]) -> Vec<Cow<'a, str>> {
+        let mut authors: Vec<String> = Vec::new();
+        let mut insert = |author: String| {
+            let author = self.normalize_author_name(&author);
+            if !author.is_empty() && !authors.contains(&author) {
+                authors.push(author);
+            }
+        };
+        for author in self.rel_authors(doc, None) {
+            insert(author.name);
+        }
+
         // look for author data in attributes
-        let mut authors = HashSet::new();
         for &key in &["name", "rel", "itemprop", "id"] {
             for &value in &["author", "byline", "dc.creator", "byl"] {
                 for node in doc.find(Attr(key, value).or(Class(value))) {
@@ -168,10 +208,10 @@ pub trait Extractor {
                     if t.is_empty() {
                         continue;
                     }
-                    if let Some(cap) = RE_AUTHOR_NAME.captures(t) {
+                    if let Some(cap) = self.author_name_regex().captures(t) {
                         if let Some(m) = cap.name("name") {
                             for author in m.as_str().trim().split(" and ") {
-                                authors.insert(author.to_string());
+                                insert(author.to_string());
                             }
                         }
                     }
@@ -179,15 +219,523 @@ pub trait Extractor {
             }
         }
 
+        for author in self.json_ld_authors(json_ld) {
+            insert(author.name);
+        }
+
         authors.into_iter().map(Cow::Owned).collect()
     }
 
+    /// The regex that extracts the author names of byline nodes in
+    /// [`Extractor::authors`] from its `name` capture group.
+    ///
+    /// The default strips a leading `By` and profile links and accepts
+    /// letters of all scripts.
+    fn author_name_regex(&self) -> &Regex {
+        &RE_AUTHOR_NAME
+    }
+
+    /// The authors linked by `<a rel="author">` or `<link rel="author">`, with
+    /// the link's text, or `title` of a `<link>`, as name and the `href` as
+    /// profile url.
+    fn rel_authors(&self, doc: &Document, base_url: Option<&Url>) -> Vec<Author> {
+        let options = Url::options().base_url(base_url);
+        let mut authors: Vec<Author> = Vec::new();
+        for node in doc.find(Name("a").or(Name("link"))).filter(|node| {
+            node.attr("rel")
+                .map(|rel| {
+                    rel.split_whitespace()
+                        .any(|rel| rel.eq_ignore_ascii_case("author"))
+                })
+                .unwrap_or_default()
+        }) {
+            let name = if node.is(Name("link")) {
+                node.attr("title").unwrap_or_default().to_string()
+            } else {
+                author_text(node)
+            };
+            let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+            if name.is_empty() || authors.iter().any(|a| a.name == name) {
+                continue;
+            }
+            let mut author = Author::new(name);
+            author.url = node
+                .attr("href")
+                .and_then(|href| options.parse(href.trim()).ok());
+            authors.push(author);
+        }
+        authors
+    }
+
+    /// Details about the authors, those linked by `rel="author"` first,
+    /// completed by the JSON-LD, see [`Extractor::rel_authors`] and
+    /// [`Extractor::json_ld_authors`].
+    fn author_details(
+        &self,
+        doc: &Document,
+        json_ld: &[Value],
+        base_url: Option<&Url>,
+    ) -> Vec<Author> {
+        let mut authors = self.rel_authors(doc, base_url);
+        for json_ld in self.json_ld_authors(json_ld) {
+            if let Some(author) = authors.iter_mut().find(|a| a.name == json_ld.name) {
+                if author.url.is_none() {
+                    author.url = json_ld.url;
+                }
+                author.same_as = json_ld.same_as;
+                author.job_title = json_ld.job_title;
+            } else {
+                authors.push(json_ld);
+            }
+        }
+        authors
+    }
+
+    /// All the JSON-LD objects of the document.
+    ///
+    /// See [`crate::jsonld::objects`]. [`Extractor::article_content`] parses
+    /// them once and hands them to the helpers that read JSON-LD.
+    fn json_ld(&self, doc: &Document) -> Vec<Value> {
+        jsonld::objects(doc)
+    }
+
+    /// Extract the `author`s of the JSON-LD objects.
+    ///
+    /// An `author` can either be a plain name, a `Person`/`Organization` object
+    /// or an array of both.
+    fn json_ld_authors(&self, json_ld: &[Value]) -> Vec<Author> {
+        let mut authors: Vec<Author> = Vec::new();
+        for obj in json_ld {
+            for value in obj.get("author").into_iter().flat_map(jsonld::one_or_many) {
+                let author = if let Some(name) = value.as_str().map(str::trim) {
+                    Author::new(name)
+                } else if let Some(name) = jsonld::str_value(value, "name") {
+                    Author {
+                        name: name.to_string(),
+                        url: jsonld::str_value(value, "url").and_then(|url| Url::parse(url).ok()),
+                        same_as: jsonld::str_values(value, "sameAs")
+                            .into_iter()
+                            .filter_map(|url| Url::parse(url).ok())
+                            .collect(),
+                        job_title: jsonld::str_value(value, "jobTitle").map(str::to_string),
+                    }
+                } else {
+                    continue;
+                };
+                if !author.name.is_empty() && authors.iter().all(|a| a.name != author.name) {
+                    authors.push(author);
+                }
+            }
+        }
+        authors
+    }
+
+    /// Extract the `VideoObject`s of the JSON-LD objects, including those
+    /// nested as `video` of an article.
+    fn json_ld_videos(&self, json_ld: &[Value], base_url: Option<&Url>) -> Vec<Video> {
+        let options = Url::options().base_url(base_url);
+        let parse = |url: &str| options.parse(url).ok();
+
+        let mut videos: Vec<Video> = Vec::new();
+        for obj in json_ld {
+            let nested = obj.get("video").into_iter().flat_map(jsonld::one_or_many);
+            for value in std::iter::once(obj).chain(nested) {
+                if !jsonld::is_type(value, "VideoObject") {
+                    continue;
+                }
+                let url = ["contentUrl", "embedUrl", "url"]
+                    .iter()
+                    .filter_map(|key| jsonld::str_value(value, key))
+                    .find_map(parse);
+                let mut video = if let Some(url) = url {
+                    Video::new(url)
+                } else {
+                    continue;
+                };
+                video.thumbnail = jsonld::str_values(value, "thumbnailUrl")
+                    .into_iter()
+                    .find_map(parse)
+                    .or_else(|| {
+                        value
+                            .get("thumbnail")
+                            .and_then(|thumb| jsonld::str_value(thumb, "url"))
+                            .and_then(parse)
+                    });
+                video.duration_secs =
+                    jsonld::str_value(value, "duration").and_then(video::parse_iso8601_duration);
+                video.upload_date =
+                    jsonld::str_value(value, "uploadDate").and_then(DateExtractor::fuzzy_dtparse);
+                if videos.iter().all(|v| v.url != video.url) {
+                    videos.push(video);
+                }
+            }
+        }
+        videos
+    }
+
+    /// The comments rendered on the page.
+    ///
+    /// Comments annotated with the microdata `Comment` type are preferred,
+    /// otherwise nodes with a `comment` class are used. Nodes that contain the
+    /// article's body are skipped.
+    fn comments(&self, doc: &Document) -> Vec<Comment> {
+        let microdata: Vec<_> = doc
+            .find(Attr("itemtype", ()))
+            .filter(|node| {
+                node.attr("itemtype")
+                    .map(|ty| ty.trim_end_matches('/').ends_with("schema.org/Comment"))
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        if microdata.is_empty() {
+            let selectors = self.article_body_selectors();
+            doc.find(Class("comment"))
+                .filter(|node| {
+                    selectors
+                        .iter()
+                        .all(|(k, v)| node.find(Attr(*k, *v)).next().is_none())
+                })
+                .filter_map(|node| {
+                    let author = node
+                        .find(Class("comment-author").or(Class("author")))
+                        .next()
+                        .map(|author| author.find(Class("fn")).next().unwrap_or(author).text());
+                    let text = node
+                        .find(
+                            Class("comment-content")
+                                .or(Class("comment-body"))
+                                .or(Class("comment-text")),
+                        )
+                        .next()
+                        .map(|txt| txt.text());
+                    let date = node.find(Name("time")).next().map(|time| {
+                        time.attr("datetime")
+                            .map(str::to_string)
+                            .unwrap_or_else(|| time.text())
+                    });
+                    comment(author, text?, date)
+                })
+                .collect()
+        } else {
+            microdata
+                .into_iter()
+                .filter_map(|node| {
+                    let prop = |name: &'static str| node.find(Attr("itemprop", name)).next();
+                    let author = prop("author").map(|author| {
+                        author
+                            .find(Attr("itemprop", "name"))
+                            .next()
+                            .unwrap_or(author)
+                            .text()
+                    });
+                    let text = prop("text").or_else(|| prop("commentText"))?.text();
+                    let date = prop("dateCreated")
+                        .or_else(|| prop("datePublished"))
+                        .map(|date| {
+                            date.attr("datetime")
+                                .or_else(|| date.attr("content"))
+                                .map(str::to_string)
+                                .unwrap_or_else(|| date.text())
+                        });
+                    comment(author, text, date)
+                })
+                .collect()
+        }
+    }
+
+    /// The entries of the article's table of contents with the anchor they
+    /// link to, like `("Background", Some("background"))`.
+    ///
+    /// The table of contents is the first node whose `id` or `class` matches
+    /// `toc` or `table-of-contents`, otherwise the first `<nav>` that only
+    /// links to anchors within the page.
+    fn table_of_contents(&self, doc: &Document) -> Vec<(String, Option<String>)> {
+        let is_toc = |node: &Node| {
+            node.attr("id")
+                .into_iter()
+                .chain(node.attr("class"))
+                .any(|value| RE_TOC.is_match(value))
+        };
+        let is_anchor_nav = |node: &Node| {
+            let mut hrefs = node
+                .find(Name("a"))
+                .filter_map(|a| a.attr("href"))
+                .peekable();
+            hrefs.peek().is_some() && hrefs.all(|href| href.trim().starts_with('#'))
+        };
+        let container = doc
+            .find(is_toc)
+            .next()
+            .or_else(|| doc.find(Name("nav")).find(is_anchor_nav));
+
+        container
+            .into_iter()
+            .flat_map(|node| node.find(Name("a")))
+            .filter_map(|a| {
+                let title = a.text().split_whitespace().collect::<Vec<_>>().join(" ");
+                if title.is_empty() {
+                    return None;
+                }
+                let anchor = a
+                    .attr("href")
+                    .and_then(|href| href.find('#').map(|idx| &href[idx + 1..]))
+                    .filter(|anchor| !anchor.is_empty())
+                    .map(str::to_string);
+                Some((title, anchor))
+            })
+            .collect()
+    }
+
+    /// The items of a listicle, if the document is one.
+    ///
+    /// A listicle has at least [`LISTICLE_MIN_ITEMS`] `<h2>` or `<h3>`
+    /// headings that are numbered consecutively, or at least as many
+    /// headings of any kind if the `og:type` hints at a list. Each item
+    /// consists of the paragraphs and the first image up to the next heading.
+    /// The headings are searched in the article's text `node` first, then in
+    /// the whole document.
+    fn listicle_items(
+        &self,
+        doc: &Document,
+        node: Option<&ArticleTextNode>,
+        base_url: Option<&Url>,
+    ) -> Option<Vec<ListItem>> {
+        let is_list_type = self
+            .meta_type(doc)
+            .map(|ty| ty.to_lowercase().contains("list"))
+            .unwrap_or_default();
+
+        let mut item_headings = node
+            .map(|node| listicle_headings(**node, is_list_type))
+            .unwrap_or_default();
+        if item_headings.is_empty() {
+            item_headings = doc
+                .find(Name("body"))
+                .next()
+                .map(|body| listicle_headings(body, is_list_type))
+                .unwrap_or_default();
+        }
+        if item_headings.is_empty() {
+            return None;
+        }
+
+        let collapse = |s: &str| s.split_whitespace().collect::<Vec<_>>().join(" ");
+        let is_heading = Name("h1").or(Name("h2")).or(Name("h3"));
+        let items = item_headings
+            .into_iter()
+            .map(|heading| {
+                let mut paragraphs = Vec::new();
+                let mut image = None;
+                for node in std::iter::successors(heading.next(), |n| n.next())
+                    .take_while(|n| !is_heading.matches(n))
+                {
+                    if Name("p").matches(&node) {
+                        paragraphs.push(collapse(&node.text()));
+                    } else {
+                        paragraphs.extend(node.find(Name("p")).map(|p| collapse(&p.text())));
+                    }
+                    if image.is_none() {
+                        image = if Name("img").matches(&node) {
+                            img_node_image(node, base_url)
+                        } else {
+                            node.find(Name("img"))
+                                .find_map(|img| img_node_image(img, base_url))
+                        };
+                    }
+                }
+                paragraphs.retain(|p| !p.is_empty());
+                ListItem {
+                    heading: collapse(&heading.text()),
+                    body: paragraphs.join("\n\n"),
+                    image,
+                }
+            })
+            .collect();
+        Some(items)
+    }
+
+    /// The first JSON-LD `Recipe` or `HowTo` of the document.
+    fn structured_content(&self, json_ld: &[Value]) -> Option<StructuredContent> {
+        let name = |obj: &Value| jsonld::str_value(obj, "name").map(str::to_string);
+        json_ld.iter().find_map(|obj| {
+            if jsonld::is_type(obj, "Recipe") {
+                let ingredients = if obj.get("recipeIngredient").is_some() {
+                    jsonld::str_values(obj, "recipeIngredient")
+                } else {
+                    jsonld::str_values(obj, "ingredients")
+                };
+                Some(StructuredContent::Recipe {
+                    name: name(obj),
+                    ingredients: ingredients.into_iter().map(str::to_string).collect(),
+                    steps: json_ld_steps(obj.get("recipeInstructions")),
+                })
+            } else if jsonld::is_type(obj, "HowTo") {
+                Some(StructuredContent::HowTo {
+                    name: name(obj),
+                    steps: json_ld_steps(obj.get("step")),
+                })
+            } else {
+                None
+            }
+        })
+    }
+
+    /// The product offered on an e-commerce page.
+    ///
+    /// A page is a product page if its JSON-LD declares a `Product` or an
+    /// `Offer`, or its `og:type` is `product`. The details are taken from the
+    /// JSON-LD `offers`, with the `product:` meta tags as fallback.
+    fn product(&self, doc: &Document, json_ld: &[Value]) -> Option<Product> {
+        let product = json_ld.iter().find(|obj| jsonld::is_type(obj, "Product"));
+        let offer = product
+            .and_then(|product| product.get("offers"))
+            .and_then(|offers| jsonld::one_or_many(offers).next())
+            .or_else(|| {
+                json_ld.iter().find(|obj| {
+                    jsonld::is_type(obj, "Offer") || jsonld::is_type(obj, "AggregateOffer")
+                })
+            });
+        let is_og_product = self
+            .meta_type(doc)
+            .map(|ty| ty.trim().to_lowercase().starts_with("product"))
+            .unwrap_or_default();
+        if product.is_none() && offer.is_none() && !is_og_product {
+            return None;
+        }
+
+        // prices are declared as strings or numbers
+        let offer_value = |key: &str| {
+            offer.and_then(|offer| match offer.get(key)? {
+                Value::String(s) => Some(s.trim().to_string()).filter(|s| !s.is_empty()),
+                Value::Number(n) => Some(n.to_string()),
+                _ => None,
+            })
+        };
+        let meta = |property: &str| {
+            self.meta_content(doc, Attr("property", property))
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        Some(Product {
+            name: product
+                .and_then(|product| jsonld::str_value(product, "name"))
+                .map(str::to_string)
+                .or_else(|| meta("og:title")),
+            price: offer_value("price")
+                .or_else(|| offer_value("lowPrice"))
+                .or_else(|| meta("product:price:amount")),
+            currency: offer_value("priceCurrency").or_else(|| meta("product:price:currency")),
+            availability: offer_value("availability")
+                .or_else(|| meta("product:availability"))
+                .map(|availability| {
+                    availability
+                        .rsplit('/')
+                        .next()
+                        .unwrap_or_default()
+                        .to_string()
+                }),
+        })
+    }
+
+    /// The location of the article.
+    ///
+    /// Coordinates are taken from the `geo.position`, `ICBM` or
+    /// `place:location:*` meta tags, otherwise from the JSON-LD
+    /// `contentLocation` or `spatialCoverage`. The name is taken from the
+    /// `geo.placename` meta tag or the JSON-LD place.
+    fn geo(&self, doc: &Document, json_ld: &[Value]) -> Option<GeoLocation> {
+        let meta = |attr| {
+            self.meta_content(doc, attr)
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        let places: Vec<_> = json_ld
+            .iter()
+            .flat_map(|obj| {
+                ["contentLocation", "spatialCoverage"]
+                    .iter()
+                    .flat_map(|key| obj.get(*key).into_iter().flat_map(jsonld::one_or_many))
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        let coordinates = meta(Attr("name", "geo.position"))
+            .or_else(|| meta(Attr("name", "ICBM")))
+            .and_then(|position| {
+                let mut parts = position.split([';', ',']);
+                parse_coordinates(parts.next()?, parts.next()?)
+            })
+            .or_else(|| {
+                parse_coordinates(
+                    &meta(Attr("property", "place:location:latitude"))?,
+                    &meta(Attr("property", "place:location:longitude"))?,
+                )
+            })
+            .or_else(|| {
+                places.iter().find_map(|place| {
+                    let geo = place.get("geo")?;
+                    let coordinate = |key: &str| match geo.get(key)? {
+                        Value::Number(n) => Some(n.to_string()),
+                        Value::String(s) => Some(s.clone()),
+                        _ => None,
+                    };
+                    parse_coordinates(&coordinate("latitude")?, &coordinate("longitude")?)
+                })
+            });
+        let name = meta(Attr("name", "geo.placename")).or_else(|| {
+            places.iter().find_map(|place| {
+                place
+                    .as_str()
+                    .or_else(|| jsonld::str_value(place, "name"))
+                    .map(str::to_string)
+            })
+        });
+
+        if coordinates.is_none() && name.is_none() {
+            return None;
+        }
+        Some(GeoLocation {
+            lat: coordinates.map(|(lat, _)| lat),
+            lon: coordinates.map(|(_, lon)| lon),
+            name,
+        })
+    }
+
+    /// The place of the dateline the text starts with, like `Berlin` for
+    /// `BERLIN (Reuters) - ...`.
+    fn dateline(&self, txt: &str) -> Option<String> {
+        let place = RE_DATELINE.captures(txt)?.name("place")?.as_str();
+        // only the city is written in uppercase, like `SAN FRANCISCO, Calif.`
+        let (city, region) = match place.find(", ") {
+            Some(idx) => (&place[..idx], &place[idx..]),
+            None => (place, ""),
+        };
+        let city = city
+            .split(' ')
+            .map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_string() + &chars.as_str().to_lowercase())
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        Some(city + region)
+    }
+
     /// When the article was published (and last updated).
     fn publishing_date(&self, doc: &Document, base_url: Option<&Url>) -> Option<ArticleDate> {
         if let Some(date) = DateExtractor::extract_from_doc(doc) {
             return Some(date);
         }
 
+        if let Some(date) = self.byline_date(doc) {
+            return Some(date);
+        }
+
         if let Some(url) = base_url {
             return DateExtractor::extract_from_str(url.path());
         }
@@ -195,6 +743,45 @@ pub trait Extractor {
         None
     }
 
+    /// Parses the date printed next to the byline, like `Published: January 2,
+    /// 2020 3:04 PM` or `Updated 2 hours ago`.
+    fn byline_date(&self, doc: &Document) -> Option<ArticleDate> {
+        let now = chrono::Utc::now().naive_utc();
+        doc.find(Name("time").or(|node: &Node| {
+            node.attr("class")
+                .map(|class| {
+                    // whole words of the class names, like `date` of `post-date`
+                    class
+                        .to_lowercase()
+                        .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+                        .any(|word| {
+                            ["byline", "date", "timestamp", "published", "posted"].contains(&word)
+                        })
+                })
+                .unwrap_or_default()
+        }))
+        .filter_map(|node| {
+            let txt = normalize_whitespace(&node.text());
+            // skip containers that hold more than a date
+            if txt.is_empty() || txt.len() > 100 {
+                return None;
+            }
+            DateExtractor::extract_relative(&txt, now).or_else(|| {
+                if RE_YEAR.is_match(&txt) {
+                    DateExtractor::fuzzy_dtparse(&txt)
+                } else {
+                    None
+                }
+            })
+        })
+        .map(|published| ArticleDate {
+            published: Date::DateTime(published),
+            last_updated: None,
+            published_from_modified: false,
+        })
+        .next()
+    }
+
     /// Extract the favicon from a website.
     fn favicon(&self, doc: &Document, base_url: &Url) -> Option<Url> {
         let options = Url::options().base_url(Some(base_url));
@@ -213,10 +800,96 @@ pub trait Extractor {
             .next()
     }
 
+    /// Detects the language of the `txt`, see [`Language::detect`].
+    fn detect_language(&self, txt: &str) -> Option<Language> {
+        Language::detect(txt)
+    }
+
+    /// Whether the language the article's `url` hints at, see
+    /// [`Category::language_hint`], or else the `lang` of its `<html>`, agrees
+    /// with the [`Extractor::detect_language`] of its `txt`.
+    ///
+    /// [`Extractor::article_content`] passes the canonical link of the `doc`,
+    /// or the `base_url` it was called with if there is none.
+    ///
+    /// Consistent if either language is unknown.
+    fn section_language_consistency(&self, doc: &Document, url: Option<&Url>, txt: &str) -> bool {
+        let declared = url
+            .and_then(|url| Category::new(url.clone()).language_hint())
+            .or_else(|| self.lang_from_html_tag(doc).and_then(Result::ok));
+        match declared {
+            Some(declared) => self
+                .detect_language(txt)
+                .map(|detected| detected == declared)
+                .unwrap_or(true),
+            None => true,
+        }
+    }
+
+    /// Parses the attribution lines at the end of the `text`, like
+    /// `Reporting by Jane Doe; Editing by John Smith`, into one
+    /// [`Attribution`] per credited name.
+    ///
+    /// A trailing place like `Jane Doe in Washington` is dropped from the
+    /// names, as is editorial text following the credits like
+    /// `Editing by John Smith, writing for the news desk` or
+    /// `Reporting by Jane Doe; Compiled by the news desk`.
+    fn attributions(&self, text: &str) -> Vec<Attribution> {
+        let mut lines: Vec<Vec<Attribution>> = Vec::new();
+        for line in text
+            .lines()
+            .rev()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+        {
+            let line = line.trim_start_matches('(').trim_end_matches(['.', ')']);
+            let mut parts = line.split(';').map(str::trim);
+            // the line must lead with a credit, any following part that isn't
+            // one is editorial text
+            let credits = match parts.next().and_then(|part| RE_ATTRIBUTION.captures(part)) {
+                Some(first) => std::iter::once(first)
+                    .chain(parts.filter_map(|part| RE_ATTRIBUTION.captures(part))),
+                None => break,
+            };
+            let mut attributions = Vec::new();
+            for credit in credits {
+                let role = &credit["role"];
+                for name in credit["names"]
+                    .split([',', '&'])
+                    .flat_map(|name| name.split(" and "))
+                {
+                    let name = name.trim();
+                    // names are capitalized, so this is editorial text
+                    if !name.starts_with(char::is_uppercase) {
+                        continue;
+                    }
+                    let name = name
+                        .find(" in ")
+                        .map_or(name, |place| &name[..place])
+                        .trim_end_matches('.');
+                    if !name.is_empty() {
+                        attributions.push(Attribution {
+                            role: role.to_string(),
+                            name: name.to_string(),
+                        });
+                    }
+                }
+            }
+            lines.push(attributions);
+        }
+        lines.into_iter().rev().flatten().collect()
+    }
+
     /// Extract content language from meta tag.
     fn meta_language(&self, doc: &Document) -> Option<Language> {
         let mut unknown_lang = None;
 
+        match self.lang_from_html_tag(doc) {
+            Some(Ok(lang)) => return Some(lang),
+            Some(Err(lang)) => unknown_lang = Some(lang),
+            None => {}
+        }
+
         if let Some(meta) = self.meta_content(doc, Attr("http-equiv", "Content-Language")) {
             match Language::from_str(&*meta) {
                 Ok(lang) => return Some(lang),
@@ -237,6 +910,18 @@ pub trait Extractor {
         unknown_lang
     }
 
+    /// The language declared by the `lang` or `xml:lang` attribute of the
+    /// `<html>` node.
+    ///
+    /// An unsupported language is returned as error.
+    fn lang_from_html_tag(&self, doc: &Document) -> Option<Result<Language, Language>> {
+        doc.find(Name("html"))
+            .filter_map(|html| html.attr("lang").or_else(|| html.attr("xml:lang")))
+            .map(str::trim)
+            .find(|lang| !lang.is_empty())
+            .map(Language::from_str)
+    }
+
     /// Finds all `<meta>` nodes in the document.
     fn meta_data<'a>(&self, doc: &'a Document) -> Vec<MetaNode<'a>> {
         doc.find(Name("head").descendant(Name("meta")))
@@ -261,6 +946,64 @@ pub trait Extractor {
             .next()
     }
 
+    /// Whether the article is free, metered or locked.
+    ///
+    /// Looks for the `article:content_tier` meta tag first and then for the
+    /// `isAccessibleForFree` flags of the JSON-LD objects and their `hasPart`s.
+    /// A part that isn't free is considered metered if its `cssSelector`
+    /// hints at a meter, like `.meteredContent`.
+    fn content_tier(&self, doc: &Document, json_ld: &[Value]) -> ContentTier {
+        let tier = self
+            .meta_content(doc, Attr("property", "article:content_tier"))
+            .or_else(|| self.meta_content(doc, Attr("name", "article:content_tier")));
+        if let Some(tier) = tier {
+            match tier.to_lowercase().as_str() {
+                "free" => return ContentTier::Free,
+                "metered" => return ContentTier::Metered,
+                "locked" => return ContentTier::Locked,
+                _ => {}
+            }
+        }
+
+        fn is_free(value: &Value) -> Option<bool> {
+            match value.get("isAccessibleForFree")? {
+                Value::Bool(free) => Some(*free),
+                Value::String(free) => match free.trim().to_lowercase().as_str() {
+                    "true" => Some(true),
+                    "false" => Some(false),
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+
+        let mut tier = ContentTier::Unknown;
+        for obj in json_ld {
+            let locked_parts: Vec<_> = obj
+                .get("hasPart")
+                .into_iter()
+                .flat_map(jsonld::one_or_many)
+                .filter(|part| is_free(part) == Some(false))
+                .collect();
+            let metered = !locked_parts.is_empty()
+                && locked_parts.iter().all(|part| {
+                    jsonld::str_value(part, "cssSelector")
+                        .map(|selector| selector.to_lowercase().contains("meter"))
+                        .unwrap_or_default()
+                });
+            if metered {
+                return ContentTier::Metered;
+            }
+            if !locked_parts.is_empty() || is_free(obj) == Some(false) {
+                return ContentTier::Locked;
+            }
+            if is_free(obj) == Some(true) {
+                tier = ContentTier::Free;
+            }
+        }
+        tier
+    }
+
     /// Extract the thumbnail for the article.
     fn meta_thumbnail_url(&self, doc: &Document, base_url: Option<&Url>) -> Option<Url> {
         let options = Url::options().base_url(base_url);
@@ -293,6 +1036,52 @@ pub trait Extractor {
         .next()
     }
 
+    /// The declared `og:image:width` and `og:image:height` of the
+    /// `og:image`.
+    fn og_image_dimensions(&self, doc: &Document) -> (Option<u32>, Option<u32>) {
+        let dimension = |property: &str| {
+            self.meta_content(doc, Attr("property", property))
+                .and_then(|value| value.trim().parse::<u32>().ok())
+        };
+        (dimension("og:image:width"), dimension("og:image:height"))
+    }
+
+    /// The [`Extractor::meta_img_url`] as [`Image`] with the
+    /// [`Extractor::og_image_dimensions`].
+    ///
+    /// If the `og:image` declares both dimensions, an image inside the
+    /// article's text `node` that declares a larger area is preferred.
+    fn top_image(
+        &self,
+        doc: &Document,
+        node: Option<&ArticleTextNode>,
+        base_url: Option<&Url>,
+    ) -> Option<Image> {
+        let url = self.meta_img_url(doc, base_url)?;
+        let mut image = Image::new(url);
+        let is_og_image = self
+            .meta_content(doc, Attr("property", "og:image"))
+            .and_then(|meta| Url::options().base_url(base_url).parse(&meta).ok())
+            .map(|og| og == image.url)
+            .unwrap_or_default();
+        if !is_og_image {
+            return Some(image);
+        }
+
+        let (width, height) = self.og_image_dimensions(doc);
+        image.width = width;
+        image.height = height;
+        let area = match image.declared_area() {
+            Some(area) => area,
+            None => return Some(image),
+        };
+        node.into_iter()
+            .flat_map(|node| node.images(base_url))
+            .filter(|img| img.declared_area().map(|a| a > area).unwrap_or_default())
+            .max_by_key(|img| img.declared_area())
+            .or(Some(image))
+    }
+
     /// Returns meta type of article, open graph protocol
     fn meta_type<'a>(&self, doc: &'a Document) -> Option<Cow<'a, str>> {
         self.meta_content(doc, Attr("property", "og:type"))
@@ -305,10 +1094,14 @@ pub trait Extractor {
 
     /// If the article has meta description set in the source, use that
     fn meta_description<'a>(&self, doc: &'a Document) -> Option<Cow<'a, str>> {
-        [("property", "description"), ("name", "description")]
-            .iter()
-            .filter_map(|(k, v)| self.meta_content(doc, Attr(k, v)))
-            .next()
+        [
+            ("property", "description"),
+            ("name", "description"),
+            ("property", "og:description"),
+        ]
+        .iter()
+        .filter_map(|(k, v)| self.meta_content(doc, Attr(k, v)))
+        .next()
     }
 
     /// If the article has meta keywords set in the source, use that.
@@ -333,9 +1126,84 @@ pub trait Extractor {
         Vec::new()
     }
 
+    /// Whether the document looks like a section or index page rather than an
+    /// article, because most of its text are headline links and the article's
+    /// text `node` isn't dominant.
+    fn looks_like_index(&self, doc: &Document, node: Option<&ArticleTextNode>) -> bool {
+        let words = |txt: &str| txt.split_whitespace().count();
+        let link_words = |node: Node| {
+            node.find(Name("a"))
+                .map(|a| words(&a.text()))
+                .sum::<usize>()
+        };
+        let body = match doc.find(Name("body")).next() {
+            Some(body) => body,
+            None => return false,
+        };
+
+        let headlines = body
+            .find(Name("a"))
+            .filter(|a| words(&a.text()) >= INDEX_HEADLINE_MIN_WORDS)
+            .count();
+        if headlines < INDEX_MIN_HEADLINES {
+            return false;
+        }
+        let body_words = words(&body.text());
+        if body_words == 0 || (link_words(body) as f64 / body_words as f64) < INDEX_MIN_LINK_RATIO {
+            return false;
+        }
+
+        // a dominant text node has plenty of text that isn't linked
+        !node
+            .map(|node| {
+                words(&node.text()).saturating_sub(link_words(**node)) >= INDEX_MAX_UNLINKED_WORDS
+            })
+            .unwrap_or_default()
+    }
+
+    /// Whether the document is a Google Web Story (AMP Story).
+    ///
+    /// Web stories are slideshows made of `<amp-story-page>`s and have no
+    /// conventional article body.
+    fn is_web_story(&self, doc: &Document) -> bool {
+        if doc.find(Name("amp-story")).next().is_some() {
+            return true;
+        }
+        self.meta_type(doc).as_deref() == Some("website")
+            && doc
+                .find(Name("script").and(Attr("custom-element", "amp-story")))
+                .next()
+                .is_some()
+    }
+
+    /// Extract the text of every `<amp-story-page>` of a web story, one page
+    /// per line.
+    fn web_story_text<'a>(&self, doc: &'a Document) -> Option<Cow<'a, str>> {
+        let pages: Vec<_> = doc
+            .find(Name("amp-story-page"))
+            .map(|page| DefaultDocumentCleaner.clean_node_text(page))
+            .map(|txt| txt.trim().to_string())
+            .filter(|txt| !txt.is_empty())
+            .collect();
+        if pages.is_empty() {
+            None
+        } else {
+            Some(Cow::Owned(pages.join("\n")))
+        }
+    }
+
     /// Get the full text of the article.
     fn text<'a>(&self, doc: &'a Document, lang: Language) -> Option<Cow<'a, str>> {
-        self.text_with_cleaner(doc, lang, DefaultDocumentCleaner)
+        self.article_node(doc, lang)
+            .map(|node| self.clean_text(*node).into())
+    }
+
+    /// The cleaned text of the `node` holding the article's text, see
+    /// [`Extractor::article_node`].
+    ///
+    /// Uses the [`DefaultDocumentCleaner`] by default.
+    fn clean_text(&self, node: Node) -> String {
+        DefaultDocumentCleaner.clean_node_text_normalized(node)
     }
 
     /// Get the full text of the article with a designated `DocumentCleaner`
@@ -346,36 +1214,225 @@ pub trait Extractor {
         cleaner: T,
     ) -> Option<Cow<'a, str>> {
         self.article_node(doc, lang)
-            .map(|n| cleaner.clean_node_text(*n).into())
+            .map(|n| cleaner.clean_node_text_normalized(*n).into())
+    }
+
+    /// Attribute key-value pairs that identify the node holding the article's
+    /// text, see [`Extractor::article_node`].
+    ///
+    /// Default are the [`crate::text::ARTICLE_BODY_ATTR`].
+    fn article_body_selectors(&self) -> Vec<(&str, &str)> {
+        ARTICLE_BODY_ATTR.to_vec()
+    }
+
+    /// Whether [`Extractor::article_node`] checks the
+    /// [`Extractor::article_body_selectors`] one after another and picks the
+    /// node of the first one that matches exactly one node, instead of
+    /// requiring exactly one node to match any of them.
+    ///
+    /// Disabled by default.
+    fn prefer_first_body_selector(&self) -> bool {
+        false
     }
 
     /// Detect the [`select::node::Node`] that contains the article's text.
     ///
-    /// If the `doc`'s body contains a node that matches the
-    /// [`crate::text::ARTICLE_BODY_ATTR`] attribute selectors, this node will
-    /// be selected. Otherwise the article node will be calculated by analysing
+    /// If the `doc`'s body contains exactly one node that matches any of the
+    /// [`Extractor::article_body_selectors`], or, if
+    /// [`Extractor::prefer_first_body_selector`] is enabled, the first selector
+    /// that matches exactly one node, this node will be selected. Otherwise the article node will be calculated by analysing
     /// and scoring the textual content of text nodes.
     fn article_node<'a>(&self, doc: &'a Document, lang: Language) -> Option<ArticleTextNode<'a>> {
-        let mut iter =
-            doc.find(Name("body").descendant(ArticleTextNodeExtractor::article_body_predicate()));
-        if let Some(node) = iter.next() {
-            if iter.next().is_none() {
-                return Some(ArticleTextNode::new(node));
+        self.article_node_with(doc, lang, self.max_nodes_to_score())
+    }
+
+    /// Like [`Extractor::article_node`], but scores at most `max_nodes` text
+    /// nodes instead of the [`Extractor::max_nodes_to_score`].
+    fn article_node_with<'a>(
+        &self,
+        doc: &'a Document,
+        lang: Language,
+        max_nodes: usize,
+    ) -> Option<ArticleTextNode<'a>> {
+        let selectors = self.article_body_selectors();
+        if self.prefer_first_body_selector() {
+            for (k, v) in selectors {
+                let mut iter = doc.find(Name("body").descendant(Attr(k, v)));
+                if let Some(node) = iter.next() {
+                    if iter.next().is_none() {
+                        return Some(ArticleTextNode::new(node));
+                    }
+                }
+            }
+        } else {
+            let mut iter = doc.find(Name("body").descendant(|node: &Node| {
+                selectors.iter().any(|(k, v)| Attr(*k, *v).matches(node))
+            }));
+            if let Some(node) = iter.next() {
+                if iter.next().is_none() {
+                    return Some(ArticleTextNode::new(node));
+                }
             }
         }
-        ArticleTextNodeExtractor::calculate_best_node(doc, lang)
+        ArticleTextNodeExtractor::calculate_best_node_with_stopwords(
+            doc,
+            &lang.with_extra_stopwords(self.extra_stopwords()),
+            max_nodes,
+        )
     }
 
-    /// Extract the `href` attribute for all `<a>` tags of the document.
-    fn all_urls<'a>(&self, doc: &'a Document) -> Vec<Cow<'a, str>> {
-        let mut uniques = HashSet::new();
-        doc.find(Name("a"))
+    /// Words that are treated as stopwords in addition to those of the
+    /// article's language, when scoring text nodes and generating keywords.
+    fn extra_stopwords(&self) -> &[String] {
+        &[]
+    }
+
+    /// Max. number of keywords generated from the text if no keywords were
+    /// extracted from the document's metadata. `0` disables generating
+    /// keywords from the text.
+    fn max_text_keywords(&self) -> usize {
+        0
+    }
+
+    /// Leading words that name the author's role, like `Reporter`, which are
+    /// stripped from author names.
+    fn author_role_prefixes(&self) -> &[String] {
+        &[]
+    }
+
+    /// Whether to strip leading [`AUTHOR_HONORIFICS`], like `Dr.`, from
+    /// author names.
+    fn strip_author_honorifics(&self) -> bool {
+        false
+    }
+
+    /// Normalizes the name of an author of [`Extractor::authors`].
+    ///
+    /// Strips the [`Extractor::author_role_prefixes`] and, if enabled,
+    /// honorifics, and title-cases names that are all uppercase or all
+    /// lowercase, like `JOHN SMITH` or `mary o'neil-jones`.
+    fn normalize_author_name(&self, name: &str) -> String {
+        let mut words: Vec<_> = name.split_whitespace().collect();
+        let is_prefix = |word: &str| {
+            self.author_role_prefixes()
+                .iter()
+                .any(|role| role.eq_ignore_ascii_case(word))
+                || (self.strip_author_honorifics()
+                    && AUTHOR_HONORIFICS
+                        .iter()
+                        .any(|honorific| honorific.eq_ignore_ascii_case(word)))
+        };
+        while words.len() > 1 && is_prefix(words[0]) {
+            words.remove(0);
+        }
+        let name = words.join(" ");
+
+        let has_upper = name.chars().any(char::is_uppercase);
+        let has_lower = name.chars().any(char::is_lowercase);
+        if has_upper && has_lower {
+            return name;
+        }
+        let mut title_cased = String::with_capacity(name.len());
+        let mut word_start = true;
+        for c in name.chars() {
+            if word_start {
+                title_cased.extend(c.to_uppercase());
+            } else {
+                title_cased.extend(c.to_lowercase());
+            }
+            word_start = c.is_whitespace() || c == '-' || c == '\'';
+        }
+        title_cased
+    }
+
+    /// Max. number of text nodes that are scored to find the
+    /// [`Extractor::article_node`].
+    ///
+    /// Default is [`ArticleTextNodeExtractor::DEFAULT_MAX_NODES_TO_SCORE`].
+    fn max_nodes_to_score(&self) -> usize {
+        ArticleTextNodeExtractor::DEFAULT_MAX_NODES_TO_SCORE
+    }
+
+    /// Extract the `href` attribute for all `<a>` tags of the document.
+    fn all_urls<'a>(&self, doc: &'a Document) -> Vec<Cow<'a, str>> {
+        let mut uniques = HashSet::new();
+        doc.find(Name("a"))
             .filter_map(|n| n.attr("href").map(str::trim))
             .filter(|href| uniques.insert(*href))
             .map(Cow::Borrowed)
             .collect()
     }
 
+    /// The `href` of all `<a>` tags of the document resolved against the
+    /// `base_url`, deduped by the absolute url without its fragment.
+    ///
+    /// Anchor-only links and links that aren't `http` or `https`, like
+    /// `mailto:` or `javascript:`, are skipped.
+    fn all_links(&self, doc: &Document, base_url: Option<&Url>) -> Vec<Url> {
+        let options = Url::options().base_url(base_url);
+        let mut uniques = HashSet::new();
+        self.all_urls(doc)
+            .into_iter()
+            .filter(|href| !href.is_empty() && !href.starts_with('#'))
+            .filter_map(|href| options.parse(&href).ok())
+            .filter(|url| matches!(url.scheme(), "http" | "https"))
+            .map(|mut url| {
+                url.set_fragment(None);
+                url
+            })
+            .filter(|url| uniques.insert(url.clone()))
+            .collect()
+    }
+
+    /// The url of the page that follows the `page`th page of a paginated
+    /// listing, like a category, resolved against the `url` of the current
+    /// page.
+    ///
+    /// Checks `rel="next"` links first, then links numbered `page + 1` and
+    /// finally links like `Next` or `Load more`. Links to other hosts or to
+    /// the current page are ignored.
+    fn next_page_url(&self, doc: &Document, url: &Url, page: usize) -> Option<Url> {
+        let options = Url::options().base_url(Some(url));
+        let resolve = |node: Node| {
+            let mut next = options.parse(node.attr("href")?.trim()).ok()?;
+            next.set_fragment(None);
+            if next.host() == url.host() && next.as_str() != url.as_str() {
+                Some(next)
+            } else {
+                None
+            }
+        };
+        let is_rel_next = |node: &Node| {
+            node.attr("rel")
+                .map(|rel| {
+                    rel.split_whitespace()
+                        .any(|rel| rel.eq_ignore_ascii_case("next"))
+                })
+                .unwrap_or_default()
+        };
+        let next_number = (page + 1).to_string();
+
+        doc.find(Name("link").or(Name("a")))
+            .filter(is_rel_next)
+            .find_map(resolve)
+            .or_else(|| {
+                doc.find(Name("a"))
+                    .filter(|a| a.text().trim() == next_number)
+                    .find_map(resolve)
+            })
+            .or_else(|| {
+                doc.find(Name("a"))
+                    .filter(|a| {
+                        RE_NEXT_PAGE.is_match(&a.text())
+                            || a.attr("id")
+                                .into_iter()
+                                .chain(a.attr("class"))
+                                .any(|value| RE_NEXT_PAGE.is_match(value))
+                    })
+                    .find_map(resolve)
+            })
+    }
+
     /// Finds all urls from the document that might point to an article.
     fn article_urls(&self, doc: &Document, base_url: Option<&Url>) -> Vec<ArticleUrl> {
         let options = Url::options().base_url(base_url);
@@ -404,16 +1461,56 @@ pub trait Extractor {
         }
     }
 
-    /// Extract all of the images of the document.
+    /// Same as [`Extractor::article_urls`], but also includes urls that carry
+    /// the article's id in one of the query `params`.
+    fn article_urls_with_query_params<T: AsRef<str>>(
+        &self,
+        doc: &Document,
+        base_url: Option<&Url>,
+        params: &[T],
+    ) -> Vec<ArticleUrl> {
+        let mut articles = self.article_urls(doc, base_url);
+        let base_url = match base_url {
+            Some(base_url) if !params.is_empty() => base_url,
+            _ => return articles,
+        };
+        let options = Url::options().base_url(Some(base_url));
+        let mut uniques: HashSet<_> = articles.iter().map(|a| a.url.clone()).collect();
+        for node in doc.find(Name("a")) {
+            if let Some(url) = node
+                .attr("href")
+                .and_then(|href| options.parse(href.trim()).ok())
+            {
+                let article = ArticleUrl::new_with_title(url, node.as_text().map(str::trim));
+                if Self::is_query_article(&article, base_url, params)
+                    && uniques.insert(article.url.clone())
+                {
+                    articles.push(article);
+                }
+            }
+        }
+        articles
+    }
+
+    /// Extract all of the images of the document, without tracking pixels.
     fn image_urls(&self, doc: &Document, base_url: Option<&Url>) -> Vec<Url> {
-        let options = Url::options().base_url(base_url);
-        // TODO extract `picture` and source media
-        doc.find(Name("img"))
-            .filter_map(|n| n.attr("href").map(str::trim))
-            .filter_map(|url| options.parse(url).ok())
+        let images = doc
+            .find(Name("img"))
+            .filter_map(|n| img_node_image(n, base_url))
+            .collect();
+        self.strip_tracking_pixels_and_beacons(images)
+            .into_iter()
+            .map(|image| image.url)
             .collect()
     }
 
+    /// Removes the tracking pixels and beacons from the `images`, see
+    /// [`is_tracking_image`].
+    fn strip_tracking_pixels_and_beacons(&self, mut images: Vec<Image>) -> Vec<Image> {
+        images.retain(|image| !is_tracking_image(image));
+        images
+    }
+
     /// First, perform basic format and domain checks like making sure the
     /// format of the url.
     ///
@@ -512,6 +1609,31 @@ pub trait Extractor {
         false
     }
 
+    /// Whether the article is identified by an id in one of the query
+    /// `params`, like `?p=12345`, rather than by its path.
+    fn is_query_article<T: AsRef<str>>(article: &ArticleUrl, base_url: &Url, params: &[T]) -> bool {
+        if !is_valid_domain(&article.url, base_url) {
+            return false;
+        }
+        if let Some(mut segments) = article.url.path_segments() {
+            if segments.any(|s| BAD_SEGMENTS.contains(&s.to_lowercase().as_str())) {
+                return false;
+            }
+        }
+        article.url.query_pairs().any(|(key, value)| {
+            if !params
+                .iter()
+                .any(|param| param.as_ref().eq_ignore_ascii_case(&key))
+            {
+                return false;
+            }
+            let (dashes, underscores) = count_dashes_and_underscores(&*value);
+            (!value.is_empty() && value.chars().all(|c| c.is_ascii_digit()))
+                || dashes > 4
+                || underscores > 4
+        })
+    }
+
     fn is_category(category: &Category, base_url: &Url) -> bool {
         if category.url.path().starts_with("/#") {
             return false;
@@ -535,25 +1657,80 @@ pub trait Extractor {
         is_valid_domain(&category.url, base_url)
     }
 
+    /// The categories one level below the `parent` that are linked in the
+    /// `doc`, like `/world/europe` for `/world`.
+    fn subcategories(&self, doc: &Document, parent: &Category) -> Vec<Category> {
+        let parent_segments: Vec<_> = parent
+            .url
+            .path_segments()
+            .map(|segments| segments.filter(|s| !s.is_empty()).collect())
+            .unwrap_or_default();
+
+        let subcategories: HashSet<_> = self
+            .all_links(doc, Some(&parent.url))
+            .into_iter()
+            .map(|mut url| {
+                url.set_query(None);
+                Category::new(url)
+            })
+            .filter(|cat| {
+                let is_child = cat
+                    .url
+                    .path_segments()
+                    .map(|segments| {
+                        let segments: Vec<_> = segments.collect();
+                        segments.len() == parent_segments.len() + 1
+                            && segments.starts_with(&parent_segments)
+                            && segments
+                                .last()
+                                .map(|s| !CATEGORY_STOPWORDS.contains(s) && !s.contains('.'))
+                                .unwrap_or_default()
+                    })
+                    .unwrap_or_default();
+                is_child
+                    && cat.url.scheme() == parent.url.scheme()
+                    && is_valid_domain(&cat.url, &parent.url)
+                    && !Self::is_article(&ArticleUrl::new(cat.url.clone()), &parent.url)
+            })
+            .collect();
+        subcategories.into_iter().collect()
+    }
+
     /// Finds all of the top level urls, assuming that these are the category
     /// urls.
     fn categories(&self, doc: &Document, base_url: &Url) -> Vec<Category> {
-        let options = Url::options().base_url(Some(base_url));
+        self.categories_with(doc, base_url, true)
+    }
+
+    /// Like [`Extractor::categories`], but keeps the query of the category
+    /// urls unless `strip_query` is set.
+    fn categories_with(&self, doc: &Document, base_url: &Url, strip_query: bool) -> Vec<Category> {
         let category_urls: HashSet<_> = self
-            .all_urls(doc)
+            .all_links(doc, Some(base_url))
             .into_iter()
-            .filter_map(|url| options.parse(&*url).ok())
             .map(|mut url| {
-                url.set_query(None);
+                if strip_query {
+                    url.set_query(None);
+                }
                 url
             })
             .collect();
 
-        category_urls
+        let categories: HashSet<_> = category_urls
             .into_iter()
             .map(Category::new)
             .filter(|cat| Self::is_category(cat, base_url))
-            .collect()
+            .collect();
+        categories.into_iter().collect()
+    }
+
+    /// Repairs mojibake, like `donâ€™t`, and composes combining diacritical
+    /// marks of the text, see [`crate::Config::normalize_unicode`].
+    fn sanitize_unicode<'a>(&self, txt: &'a str) -> Cow<'a, str> {
+        match repair_mojibake(txt) {
+            Cow::Borrowed(txt) => compose_diacritics(txt),
+            Cow::Owned(repaired) => Cow::Owned(compose_diacritics(&repaired).into_owned()),
+        }
     }
 
     /// Gathers all items for an article from the document.
@@ -563,9 +1740,29 @@ pub trait Extractor {
         base_url: Option<&Url>,
         lang: Option<Language>,
     ) -> ArticleContent<'a> {
+        self.article_content_with(doc, base_url, lang, true, None)
+    }
+
+    /// Like [`Extractor::article_content`], but keeps the tracking pixels and
+    /// beacons in the images unless `strip_tracking_images` is set, see
+    /// [`Extractor::strip_tracking_pixels_and_beacons`], and scores at most
+    /// `max_nodes_to_score` text nodes if set, see
+    /// [`Extractor::article_node_with`].
+    fn article_content_with<'a>(
+        &self,
+        doc: &'a Document,
+        base_url: Option<&Url>,
+        lang: Option<Language>,
+        strip_tracking_images: bool,
+        max_nodes_to_score: Option<usize>,
+    ) -> ArticleContent<'a> {
+        let json_ld = self.json_ld(doc);
         let mut builder = ArticleContent::builder()
-            .authors(self.authors(doc))
-            .keywords(self.meta_keywords(doc));
+            .authors(self.authors_with(doc, &json_ld))
+            .author_details(self.author_details(doc, &json_ld, base_url))
+            .keywords(self.meta_keywords(doc))
+            .social_embeds(self.social_embeds(doc, base_url))
+            .content_tier(self.content_tier(doc, &json_ld));
 
         let lang = if let Some(meta_lang) = self.meta_language(doc) {
             builder = builder.language(meta_lang.clone());
@@ -574,7 +1771,19 @@ pub trait Extractor {
             lang.unwrap_or_default()
         };
 
-        if let Some(txt_node) = self.article_node(doc, lang) {
+        let is_web_story = self.is_web_story(doc);
+        let txt_node = if is_web_story {
+            None
+        } else if let Some(max_nodes) = max_nodes_to_score {
+            self.article_node_with(doc, lang.clone(), max_nodes)
+        } else {
+            self.article_node(doc, lang.clone())
+        };
+        if is_web_story {
+            if let Some(txt) = self.web_story_text(doc) {
+                builder = builder.text(txt).text_source(TextSource::Body);
+            }
+        } else if let Some(txt_node) = &txt_node {
             builder = builder
                 .videos(
                     txt_node
@@ -582,11 +1791,79 @@ pub trait Extractor {
                         .into_iter()
                         .filter_map(|x| x.get_src_url(base_url))
                         .filter_map(|url| url.ok())
+                        .map(Video::new)
                         .collect(),
                 )
                 .references(txt_node.references())
-                .text(txt_node.clean_text().into())
-                .images(txt_node.images(base_url));
+                .quotes(txt_node.quotes())
+                .text(self.clean_text(**txt_node).into())
+                .text_source(TextSource::Body)
+                .images(if strip_tracking_images {
+                    self.strip_tracking_pixels_and_beacons(txt_node.images(base_url))
+                } else {
+                    txt_node.images(base_url)
+                });
+            if let Some(image) = self.content_lead_image(doc, Some(txt_node), base_url) {
+                builder = builder.lead_image(image);
+            }
+            builder = builder.link_stats(self.link_stats(doc, Some(txt_node), base_url));
+        }
+        if !is_web_story {
+            builder = builder.looks_like_index(self.looks_like_index(doc, txt_node.as_ref()));
+        }
+
+        if let Some(txt) = builder.text.as_deref() {
+            let attributions = self.attributions(txt);
+            if !attributions.is_empty() {
+                let authors = builder.authors.get_or_insert_with(Vec::new);
+                for attribution in attributions.iter().filter(|a| a.is_reporter()) {
+                    let name = self.normalize_author_name(&attribution.name);
+                    if !name.is_empty() && !authors.iter().any(|author| *author == name) {
+                        authors.push(Cow::Owned(name));
+                    }
+                }
+                builder = builder.attributions(attributions);
+            }
+        }
+
+        if let Some(txt) = builder.text.as_deref() {
+            let url = self.canonical_link(doc).or_else(|| base_url.cloned());
+            if !self.section_language_consistency(doc, url.as_ref(), txt) {
+                builder = builder.language_mismatch(true);
+            }
+        }
+
+        let max_text_keywords = self.max_text_keywords();
+        if max_text_keywords > 0 && builder.keywords.as_ref().map(Vec::is_empty).unwrap_or(true) {
+            if let Some(txt) = builder.text.as_deref() {
+                let keywords = lang
+                    .with_extra_stopwords(self.extra_stopwords())
+                    .keywords(txt, max_text_keywords);
+                builder = builder.keywords(keywords.into_iter().map(Cow::Owned).collect());
+            }
+        }
+
+        let mut geo = self.geo(doc, &json_ld);
+        if geo.as_ref().map(|geo| geo.name.is_none()).unwrap_or(true) {
+            if let Some(place) = builder.text.as_deref().and_then(|txt| self.dateline(txt)) {
+                geo.get_or_insert_with(Default::default).name = Some(place);
+            }
+        }
+        if let Some(geo) = geo {
+            builder = builder.geo(geo);
+        }
+
+        let json_ld_videos = self.json_ld_videos(&json_ld, base_url);
+        if !json_ld_videos.is_empty() {
+            let mut videos = builder.videos.take().unwrap_or_default();
+            for video in json_ld_videos {
+                if let Some(existing) = videos.iter_mut().find(|v| v.url == video.url) {
+                    *existing = video;
+                } else {
+                    videos.push(video);
+                }
+            }
+            builder = builder.videos(videos);
         }
 
         if let Some(description) = self.meta_description(doc) {
@@ -601,10 +1878,27 @@ pub trait Extractor {
         if let Some(date) = self.publishing_date(doc, base_url) {
             builder = builder.publishing_date(date);
         }
-        if let Some(img) = self.meta_img_url(doc, base_url) {
-            builder = builder.top_image(img);
+        if let Some(img) = self.top_image(doc, txt_node.as_ref(), base_url) {
+            builder = builder.top_image(img.url.clone()).top_image_details(img);
+        }
+        if let Some(syndication) = self.syndication(doc, &json_ld, base_url) {
+            builder = builder.syndication(syndication);
+        }
+        if let Some(structured) = self.structured_content(&json_ld) {
+            builder = builder.structured(structured);
+        }
+        if let Some(items) = self.listicle_items(doc, txt_node.as_ref(), base_url) {
+            builder = builder.list_items(items);
         }
-        builder.build()
+        builder = builder.toc(self.table_of_contents(doc));
+        if let Some(product) = self.product(doc, &json_ld) {
+            builder = builder.product(product);
+        }
+        let sections = self.json_ld_sections(&json_ld);
+        if let Some((section, source)) = self.section(doc, &json_ld, base_url) {
+            builder = builder.section(section, source);
+        }
+        builder.sections(sections).build()
     }
 
     /// Return the article's canonical URL
@@ -628,6 +1922,273 @@ pub trait Extractor {
         None
     }
 
+    /// The desktop equivalent of the mobile or AMP `url` the `doc` was
+    /// downloaded from.
+    ///
+    /// Prefers the canonical link of the `doc` and falls back to
+    /// [`desktop_variant`]. Returns `None` if the `url` is not a mobile or AMP
+    /// url.
+    fn desktop_url(&self, url: &Url, doc: &Document) -> Option<Url> {
+        let desktop = desktop_variant(url)?;
+        if let Some(canonical) = self.canonical_link(doc) {
+            if canonical != *url && desktop_variant(&canonical).is_none() {
+                return Some(canonical);
+            }
+        }
+        Some(desktop)
+    }
+
+    /// The url of the AMP version of the page, from its `amphtml` link.
+    fn amp_url(&self, doc: &Document, base_url: Option<&Url>) -> Option<Url> {
+        let options = Url::options().base_url(base_url);
+        doc.find(Name("link").and(Attr("rel", "amphtml")))
+            .filter_map(|node| node.attr("href"))
+            .find_map(|href| options.parse(href.trim()).ok())
+    }
+
+    /// How the [`Extractor::canonical_link`] and [`Extractor::amp_url`] of
+    /// the `doc` relate to the `fetched_url`.
+    ///
+    /// Fragments and trailing slashes are ignored when comparing urls, and
+    /// the `www.` prefix when comparing hosts.
+    fn link_consistency(&self, doc: &Document, fetched_url: &Url) -> LinkConsistency {
+        let canonical = self.canonical_link(doc);
+        let normalized = |url: &Url| {
+            let mut url = url.clone();
+            url.set_fragment(None);
+            url.as_str().trim_end_matches('/').to_string()
+        };
+        let host = |url: &Url| {
+            url.host_str()
+                .map(|host| host.trim_start_matches("www.").to_lowercase())
+        };
+        LinkConsistency {
+            self_canonical: canonical
+                .as_ref()
+                .map(|canonical| normalized(canonical) == normalized(fetched_url))
+                .unwrap_or(true),
+            canonical_host_matches: canonical
+                .as_ref()
+                .map(|canonical| host(canonical) == host(fetched_url))
+                .unwrap_or(true),
+            has_amp: self.amp_url(doc, Some(fetched_url)).is_some(),
+        }
+    }
+
+    /// Detects whether the article is republished from another source.
+    ///
+    /// This is the case if the JSON-LD declares the article `isBasedOn`
+    /// another one or names a `sourceOrganization` other than the publisher,
+    /// or if the canonical url points to another site.
+    fn syndication(
+        &self,
+        doc: &Document,
+        json_ld: &[Value],
+        base_url: Option<&Url>,
+    ) -> Option<Syndication> {
+        let mut syndication = Syndication {
+            source_url: None,
+            source_org: None,
+        };
+
+        for obj in json_ld {
+            if syndication.source_url.is_none() {
+                syndication.source_url = obj.get("isBasedOn").and_then(|based_on| {
+                    based_on
+                        .as_str()
+                        .or_else(|| jsonld::str_value(based_on, "url"))
+                        .and_then(|url| Url::parse(url.trim()).ok())
+                });
+            }
+            if syndication.source_org.is_none() {
+                let publisher = obj
+                    .get("publisher")
+                    .and_then(|publisher| jsonld::str_value(publisher, "name"));
+                syndication.source_org = obj
+                    .get("sourceOrganization")
+                    .and_then(|org| {
+                        org.as_str()
+                            .map(str::trim)
+                            .or_else(|| jsonld::str_value(org, "name"))
+                    })
+                    .filter(|org| !org.is_empty() && Some(*org) != publisher)
+                    .map(str::to_string);
+            }
+        }
+
+        if syndication.source_url.is_none() {
+            if let (Some(canonical), Some(base_url)) = (self.canonical_link(doc), base_url) {
+                let host = |url: &Url| url.host_str().map(str::to_lowercase);
+                if let (Some(canonical_host), Some(host)) = (host(&canonical), host(base_url)) {
+                    if !is_same_site(&canonical_host, &host) {
+                        syndication.source_url = Some(canonical);
+                    }
+                }
+            }
+        }
+
+        if syndication.source_url.is_some() || syndication.source_org.is_some() {
+            Some(syndication)
+        } else {
+            None
+        }
+    }
+
+    /// The section of the article, resolved in order from the
+    /// [`Extractor::json_ld_sections`], the [`Extractor::meta_section`], the
+    /// [`Extractor::breadcrumb_section`] and the
+    /// [`Extractor::section_from_url`] of the canonical url or `base_url`.
+    fn section(
+        &self,
+        doc: &Document,
+        json_ld: &[Value],
+        base_url: Option<&Url>,
+    ) -> Option<(String, SectionSource)> {
+        if let Some(section) = self.json_ld_sections(json_ld).into_iter().next() {
+            return Some((section, SectionSource::JsonLd));
+        }
+        if let Some(section) = self.meta_section(doc) {
+            return Some((section.into_owned(), SectionSource::Meta));
+        }
+        if let Some(section) = self.breadcrumb_section(doc, json_ld) {
+            return Some((section, SectionSource::Breadcrumbs));
+        }
+        let url = self.canonical_link(doc).or_else(|| base_url.cloned())?;
+        self.section_from_url(&url)
+            .map(|section| (section, SectionSource::Url))
+    }
+
+    /// All `articleSection`s declared in the JSON-LD, whether as a single
+    /// string or an array.
+    ///
+    /// The sections of article objects, see [`jsonld::is_article`], come
+    /// before those of other objects, like a `WebPage`.
+    fn json_ld_sections(&self, json_ld: &[Value]) -> Vec<String> {
+        let mut sections: Vec<String> = Vec::new();
+        let mut objects: Vec<_> = json_ld.iter().collect();
+        objects.sort_by_key(|obj| !jsonld::is_article(obj));
+        for obj in objects {
+            for section in jsonld::str_values(obj, "articleSection") {
+                if !sections.iter().any(|s| s == section) {
+                    sections.push(section.to_string());
+                }
+            }
+        }
+        sections
+    }
+
+    /// The `article:section` meta tag.
+    fn meta_section<'a>(&self, doc: &'a Document) -> Option<Cow<'a, str>> {
+        self.meta_content(doc, Attr("property", "article:section"))
+            .or_else(|| self.meta_content(doc, Attr("name", "article:section")))
+    }
+
+    /// The first entry of the breadcrumb navigation, ignoring the link to the
+    /// home page.
+    fn breadcrumb_section(&self, doc: &Document, json_ld: &[Value]) -> Option<String> {
+        let is_home = |name: &str| name.eq_ignore_ascii_case("home");
+
+        for obj in json_ld {
+            if !jsonld::is_type(obj, "BreadcrumbList") {
+                continue;
+            }
+            let mut items: Vec<_> = obj
+                .get("itemListElement")
+                .into_iter()
+                .flat_map(jsonld::one_or_many)
+                .filter_map(|item| {
+                    let name = jsonld::str_value(item, "name").or_else(|| {
+                        item.get("item")
+                            .and_then(|item| jsonld::str_value(item, "name"))
+                    })?;
+                    let position = item.get("position").and_then(Value::as_u64);
+                    Some((position, name))
+                })
+                .collect();
+            items.sort_by_key(|(position, _)| *position);
+            if let Some((_, name)) = items.into_iter().find(|(_, name)| !is_home(name)) {
+                return Some(name.to_string());
+            }
+        }
+
+        doc.find(
+            Attr("aria-label", "breadcrumb")
+                .or(Class("breadcrumb"))
+                .or(Class("breadcrumbs"))
+                .descendant(Name("a")),
+        )
+        .map(|node| normalize_whitespace(&node.text()))
+        .find(|name| !name.is_empty() && !is_home(name))
+    }
+
+    /// The first segment of the `url`'s path, if it is followed by more
+    /// segments and looks like a section name, like `politics` in
+    /// `/politics/some-article`.
+    fn section_from_url(&self, url: &Url) -> Option<String> {
+        let segments: Vec<_> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+        if segments.len() < 2 {
+            return None;
+        }
+        let section = segments[0].to_lowercase();
+        if section.chars().all(|c| c.is_alphabetic() || c == '-')
+            && !BAD_SEGMENTS.contains(&section.as_str())
+        {
+            Some(section)
+        } else {
+            None
+        }
+    }
+
+    /// The text of all quotes in the article.
+    fn quotes(&self, doc: &Document, lang: Option<Language>) -> Vec<String> {
+        if let Some(node) = self.article_node(doc, lang.unwrap_or_default()) {
+            node.quotes()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// The first substantial image inside the article's text `node`, see
+    /// [`ArticleTextNode::lead_image`].
+    ///
+    /// Unlike the [`Extractor::meta_img_url`], this is usually the image that
+    /// is displayed above the article rather than a social share card.
+    fn content_lead_image(
+        &self,
+        _doc: &Document,
+        node: Option<&ArticleTextNode>,
+        base_url: Option<&Url>,
+    ) -> Option<Image> {
+        node?.lead_image(base_url)
+    }
+
+    /// Counts the internal, outbound and affiliate links in the article's
+    /// text `node`, see [`is_affiliate_link`].
+    fn link_stats(
+        &self,
+        _doc: &Document,
+        node: Option<&ArticleTextNode>,
+        base_url: Option<&Url>,
+    ) -> LinkStats {
+        node.map(|node| node_link_stats(**node, base_url))
+            .unwrap_or_default()
+    }
+
+    /// All embedded social media posts, like tweets or instagram posts.
+    ///
+    /// See [`crate::embed::social_embeds`].
+    fn social_embeds(&self, doc: &Document, base_url: Option<&Url>) -> Vec<SocialEmbed> {
+        embed::social_embeds(doc, base_url)
+    }
+
+    /// The endpoints of the oEmbed representations of the page's embeds.
+    ///
+    /// See [`crate::embed::oembed_endpoints`] and
+    /// [`ArticleContent::resolve_oembeds`].
+    fn oembed_endpoints(&self, doc: &Document, base_url: Option<&Url>) -> Vec<Url> {
+        embed::oembed_endpoints(doc, base_url)
+    }
+
     /// All video content in the article.
     fn videos<'a>(&self, doc: &'a Document, lang: Option<Language>) -> Vec<VideoNode<'a>> {
         if let Some(node) = self.article_node(doc, lang.unwrap_or_default()) {
@@ -681,32 +2242,435 @@ fn is_valid_domain(url: &Url, base_url: &Url) -> bool {
     false
 }
 
-/// An Extractor that only uses the default implementation in the `Extractor`
-/// trait.
-#[derive(Debug, Default)]
-pub struct DefaultExtractor;
+/// Whether both hosts are equal or one is a subdomain of the other, like
+/// `edition.cnn.com` and `cnn.com`.
+fn is_same_site(a: &str, b: &str) -> bool {
+    let is_subdomain = |sub: &str, domain: &str| {
+        sub.strip_suffix(domain)
+            .map(|prefix| prefix.ends_with('.'))
+            .unwrap_or_default()
+    };
+    a == b || is_subdomain(a, b) || is_subdomain(b, a)
+}
 
-impl Extractor for DefaultExtractor {}
+/// A [`Comment`] with normalized whitespace, if the `text` isn't empty.
+fn comment(author: Option<String>, text: String, date: Option<String>) -> Option<Comment> {
+    let collapse = |s: &str| s.split_whitespace().collect::<Vec<_>>().join(" ");
+    let text = normalize_whitespace(&text);
+    if text.is_empty() {
+        return None;
+    }
+    Some(Comment {
+        author: author.map(|a| collapse(&a)).filter(|a| !a.is_empty()),
+        text,
+        date: date.and_then(|date| DateExtractor::fuzzy_dtparse(date.trim())),
+    })
+}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Parses a latitude and longitude in degrees, if both are in range.
+fn parse_coordinates(lat: &str, lon: &str) -> Option<(f64, f64)> {
+    let lat = lat.trim().parse::<f64>().ok()?;
+    let lon = lon.trim().parse::<f64>().ok()?;
+    if (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lon) {
+        Some((lat, lon))
+    } else {
+        None
+    }
+}
 
-    #[test]
-    fn author_regex() {
-        let m = RE_AUTHOR_NAME
-            .captures("By &lt;a href=&quot;/profiles/meg-wagner&quot;&gt;Joseph Kelley&lt;/a&gt;")
-            .unwrap()
-            .name("name")
-            .unwrap();
-        assert_eq!(m.as_str(), "Joseph Kelley");
+/// The texts of JSON-LD instructions, which are either plain strings,
+/// `HowToStep`s or `HowToSection`s of steps.
+fn json_ld_steps(value: Option<&Value>) -> Vec<String> {
+    fn collect(value: &Value, steps: &mut Vec<String>) {
+        match value {
+            Value::String(s) => steps.extend(
+                s.lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(str::to_string),
+            ),
+            Value::Array(items) => items.iter().for_each(|item| collect(item, steps)),
+            Value::Object(_) => {
+                if let Some(items) = value.get("itemListElement") {
+                    collect(items, steps);
+                } else if let Some(txt) =
+                    jsonld::str_value(value, "text").or_else(|| jsonld::str_value(value, "name"))
+                {
+                    steps.push(txt.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    let mut steps = Vec::new();
+    if let Some(value) = value {
+        collect(value, &mut steps);
+    }
+    steps
+}
 
-        let m = RE_AUTHOR_NAME
-            .captures("By <a href=&quot;/profiles/meg-wagner&quot;>Joseph Kelley</a>")
-            .unwrap()
-            .name("name")
-            .unwrap();
-        assert_eq!(m.as_str(), "Joseph Kelley");
+/// The headings of the items of a listicle within the `root`, see
+/// [`Extractor::listicle_items`].
+fn listicle_headings(root: Node, is_list_type: bool) -> Vec<Node> {
+    let headings: Vec<_> = root.find(Name("h2").or(Name("h3"))).collect();
+    let (numbered, numbers): (Vec<_>, Vec<u32>) = headings
+        .iter()
+        .filter_map(|h| {
+            let num = RE_LISTICLE_HEADING.captures(&h.text())?["num"]
+                .parse::<u32>()
+                .ok()?;
+            Some((*h, num))
+        })
+        .unzip();
+    let consecutive = numbers.windows(2).all(|w| w[1] == w[0] + 1)
+        || numbers.windows(2).all(|w| w[0] == w[1] + 1);
+    if numbered.len() >= LISTICLE_MIN_ITEMS && consecutive {
+        numbered
+    } else if headings.len() >= LISTICLE_MIN_ITEMS && is_list_type {
+        headings
+    } else {
+        Vec::new()
+    }
+}
+
+/// Honorifics that precede author names, see
+/// [`Extractor::strip_author_honorifics`].
+pub const AUTHOR_HONORIFICS: [&str; 9] = [
+    "Dr.", "Dr", "Prof.", "Prof", "Mr.", "Mrs.", "Ms.", "Mx.", "Sir",
+];
+
+/// Min. number of items of a listicle, see [`Extractor::listicle_items`].
+pub const LISTICLE_MIN_ITEMS: usize = 3;
+
+/// Min. number of words of a link's text to count as headline, see
+/// [`Extractor::looks_like_index`].
+const INDEX_HEADLINE_MIN_WORDS: usize = 4;
+
+/// Min. number of headline links of an index page.
+const INDEX_MIN_HEADLINES: usize = 10;
+
+/// Min. share of linked words among all words of an index page.
+const INDEX_MIN_LINK_RATIO: f64 = 0.5;
+
+/// Number of words outside of links, from which on a text node is considered
+/// dominant.
+const INDEX_MAX_UNLINKED_WORDS: usize = 150;
+
+/// Hosts of affiliate networks and link shorteners used for affiliate links.
+pub const AFFILIATE_HOSTS: [&str; 6] = [
+    "amzn.to",
+    "go.skimresources.com",
+    "click.linksynergy.com",
+    "shareasale.com",
+    "awin1.com",
+    "go.redirectingat.com",
+];
+
+/// Shops and the query parameter that carries their affiliate id.
+pub const AFFILIATE_QUERY_PARAMS: [(&str, &str); 3] =
+    [("amazon", "tag"), ("ebay", "campid"), ("booking", "aid")];
+
+/// Whether the `url` is an affiliate link, like an amazon link with a `tag`,
+/// a link to an affiliate network or with an `/aff/` path, see
+/// [`AFFILIATE_HOSTS`] and [`AFFILIATE_QUERY_PARAMS`].
+pub fn is_affiliate_link(url: &Url) -> bool {
+    let host = url
+        .host_str()
+        .unwrap_or_default()
+        .trim_start_matches("www.");
+    if AFFILIATE_HOSTS
+        .iter()
+        .any(|h| host == *h || host.ends_with(&format!(".{}", h)))
+    {
+        return true;
+    }
+    let has_param = |name: &str| url.query_pairs().any(|(k, _)| k == name);
+    if AFFILIATE_QUERY_PARAMS
+        .iter()
+        .any(|(shop, param)| host.split('.').any(|label| label == *shop) && has_param(param))
+    {
+        return true;
+    }
+    url.path_segments()
+        .map(|mut segments| segments.any(|s| s == "aff"))
+        .unwrap_or_default()
+}
+
+/// Whether the `image` is a tracking pixel or beacon: an inline `data:` url,
+/// an image on one of the [`BAD_DOMAINS`] or [`TRACKING_DOMAINS`], or one
+/// that declares 1x1 dimensions.
+pub fn is_tracking_image(image: &Image) -> bool {
+    if image.url.scheme() == "data" {
+        return true;
+    }
+    let tracking_host = image
+        .url
+        .host_str()
+        .unwrap_or_default()
+        .split('.')
+        .any(|label| BAD_DOMAINS.contains(&label) || TRACKING_DOMAINS.contains(&label));
+    tracking_host || matches!((image.width, image.height), (Some(w), Some(h)) if w <= 1 && h <= 1)
+}
+
+/// Counts the http links within the `node`.
+fn node_link_stats(node: Node, base_url: Option<&Url>) -> LinkStats {
+    let options = Url::options().base_url(base_url);
+    let mut stats = LinkStats::default();
+    for href in node.find(Name("a")).filter_map(|a| a.attr("href")) {
+        let url = match options.parse(href.trim()) {
+            Ok(url) if url.scheme() == "http" || url.scheme() == "https" => url,
+            _ => continue,
+        };
+        match base_url {
+            Some(base_url) if !is_valid_domain(&url, base_url) => stats.outbound += 1,
+            _ => stats.internal += 1,
+        }
+        if is_affiliate_link(&url) {
+            stats.affiliate += 1;
+        }
+    }
+    stats
+}
+
+/// Rewrites a mobile or AMP url to its desktop equivalent by stripping the
+/// `m.`, `mobile.` or `amp.` subdomain and `amp` path segments.
+///
+/// Returns `None` if there is nothing to rewrite.
+pub fn desktop_variant(url: &Url) -> Option<Url> {
+    let mut desktop = url.clone();
+    if let Some(domain) = url.domain() {
+        let mut labels = domain.splitn(2, '.');
+        if let (Some(sub), Some(rest)) = (labels.next(), labels.next()) {
+            if ["m", "mobile", "amp"].contains(&sub) && rest.contains('.') {
+                desktop.set_host(Some(rest)).ok()?;
+            }
+        }
+    }
+    if let Some(segments) = url.path_segments() {
+        let segments: Vec<_> = segments.collect();
+        if segments.contains(&"amp") {
+            let path: Vec<_> = segments.into_iter().filter(|s| *s != "amp").collect();
+            desktop.set_path(&path.join("/"));
+        }
+    }
+    if desktop == *url {
+        None
+    } else {
+        Some(desktop)
+    }
+}
+
+/// The title matching strategy of the default [`Extractor::title`].
+fn default_title<'a, E: Extractor + ?Sized>(
+    extractor: &E,
+    doc: &'a Document,
+) -> Option<Cow<'a, str>> {
+    if let Some(title) = doc
+        .find(Name("h1"))
+        .filter_map(|node| node.as_text().map(str::trim))
+        .next()
+    {
+        return Some(Cow::Borrowed(title));
+    }
+
+    if let Some(title) = extractor.meta_content(doc, Attr("property", "og:title")) {
+        return Some(title);
+    }
+
+    if let Some(title) = extractor.meta_content(doc, Attr("name", "og:title")) {
+        return Some(title);
+    }
+
+    if let Some(title) = doc.find(Name("title")).next() {
+        return title.as_text().map(str::trim).map(Cow::Borrowed);
+    }
+    None
+}
+
+/// Which source the [`ConfigurableExtractor`] prefers for the title of an
+/// article.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub enum TitleStrategy {
+    /// The matching strategy of the default [`Extractor::title`].
+    Heading,
+    /// The `og:title` meta tag.
+    OpenGraph,
+    /// The `<title>` of the document.
+    Document,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for TitleStrategy {
+    fn default() -> Self {
+        TitleStrategy::Heading
+    }
+}
+
+/// Configures how the [`ConfigurableExtractor`] extracts the content,
+/// independent of the [`crate::Config`] of the crawl.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct DefaultExtractorConfig {
+    /// Which source to prefer for the title.
+    pub title_strategy: TitleStrategy,
+    /// Replaces the [`crate::text::ARTICLE_BODY_ATTR`] selectors.
+    pub article_body_selectors: Option<Vec<(String, String)>>,
+    /// Picks the node of the first body selector with a single match, see
+    /// [`Extractor::prefer_first_body_selector`].
+    pub prefer_first_body_selector: bool,
+    /// Replaces the
+    /// [`ArticleTextNodeExtractor::DEFAULT_MAX_NODES_TO_SCORE`].
+    pub max_nodes_to_score: Option<usize>,
+    /// Words treated as stopwords in addition to those of the language.
+    pub extra_stopwords: Vec<String>,
+    /// Max. number of keywords generated from the text if the document has no
+    /// keywords in its metadata, none by default.
+    pub max_text_keywords: Option<usize>,
+    /// Leading role words stripped from author names, like `Reporter`.
+    pub author_role_prefixes: Vec<String>,
+    /// Whether to strip honorifics, like `Dr.`, from author names.
+    pub strip_author_honorifics: bool,
+    /// Drops the lines of the text whose link density exceeds it, see
+    /// [`DocumentCleaner::max_line_link_density`].
+    pub max_line_link_density: Option<f64>,
+    /// Drops short lines of the text, see
+    /// [`DocumentCleaner::min_line_words`].
+    pub min_line_words: Option<usize>,
+}
+
+/// An Extractor that only uses the default implementation in the `Extractor`
+/// trait.
+#[derive(Debug, Clone, Default)]
+pub struct DefaultExtractor;
+
+impl DefaultExtractor {
+    /// A [`ConfigurableExtractor`] that is tuned by the `config`, same as
+    /// [`ConfigurableExtractor::new`].
+    pub fn configurable(config: DefaultExtractorConfig) -> ConfigurableExtractor {
+        ConfigurableExtractor::new(config)
+    }
+}
+
+impl Extractor for DefaultExtractor {}
+
+/// An Extractor that uses the default implementation in the `Extractor`
+/// trait, unless configured otherwise by its [`DefaultExtractorConfig`].
+#[derive(Debug, Clone, Default)]
+pub struct ConfigurableExtractor {
+    config: DefaultExtractorConfig,
+}
+
+impl ConfigurableExtractor {
+    /// A `ConfigurableExtractor` that is tuned by the `config`.
+    pub fn new(config: DefaultExtractorConfig) -> Self {
+        Self { config }
+    }
+
+    /// The configuration of this extractor.
+    pub fn config(&self) -> &DefaultExtractorConfig {
+        &self.config
+    }
+
+    /// Mutable access to the configuration of this extractor.
+    pub fn config_mut(&mut self) -> &mut DefaultExtractorConfig {
+        &mut self.config
+    }
+}
+
+impl From<DefaultExtractorConfig> for ConfigurableExtractor {
+    fn from(config: DefaultExtractorConfig) -> Self {
+        Self::new(config)
+    }
+}
+
+impl Extractor for ConfigurableExtractor {
+    fn title<'a>(&self, doc: &'a Document) -> Option<Cow<'a, str>> {
+        let title = match self.config.title_strategy {
+            TitleStrategy::Heading => None,
+            TitleStrategy::OpenGraph => self
+                .meta_content(doc, Attr("property", "og:title"))
+                .or_else(|| self.meta_content(doc, Attr("name", "og:title"))),
+            TitleStrategy::Document => doc
+                .find(Name("title"))
+                .map(|node| node.text().trim().to_string())
+                .find(|title| !title.is_empty())
+                .map(Cow::Owned),
+        };
+        title.or_else(|| default_title(self, doc))
+    }
+
+    fn clean_text(&self, node: Node) -> String {
+        let config = &self.config;
+        if config.max_line_link_density.is_none() && config.min_line_words.is_none() {
+            return DefaultDocumentCleaner.clean_node_text_normalized(node);
+        }
+        CommonCleaner::new(|node| !has_bad_attr(node))
+            .with_line_thresholds(
+                config.max_line_link_density.unwrap_or(1.0),
+                config.min_line_words.unwrap_or_default(),
+            )
+            .clean_node_text_normalized(node)
+    }
+
+    fn article_body_selectors(&self) -> Vec<(&str, &str)> {
+        match &self.config.article_body_selectors {
+            Some(selectors) => selectors
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_str()))
+                .collect(),
+            None => ARTICLE_BODY_ATTR.to_vec(),
+        }
+    }
+
+    fn prefer_first_body_selector(&self) -> bool {
+        self.config.prefer_first_body_selector
+    }
+
+    fn max_nodes_to_score(&self) -> usize {
+        self.config
+            .max_nodes_to_score
+            .unwrap_or(ArticleTextNodeExtractor::DEFAULT_MAX_NODES_TO_SCORE)
+    }
+
+    fn extra_stopwords(&self) -> &[String] {
+        &self.config.extra_stopwords
+    }
+
+    fn max_text_keywords(&self) -> usize {
+        self.config.max_text_keywords.unwrap_or_default()
+    }
+
+    fn author_role_prefixes(&self) -> &[String] {
+        &self.config.author_role_prefixes
+    }
+
+    fn strip_author_honorifics(&self) -> bool {
+        self.config.strip_author_honorifics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::article::ARTICLE_ID_QUERY_PARAMS;
+    use crate::embed::SocialProvider;
+
+    #[test]
+    fn author_regex() {
+        let m = RE_AUTHOR_NAME
+            .captures("By &lt;a href=&quot;/profiles/meg-wagner&quot;&gt;Joseph Kelley&lt;/a&gt;")
+            .unwrap()
+            .name("name")
+            .unwrap();
+        assert_eq!(m.as_str(), "Joseph Kelley");
+
+        let m = RE_AUTHOR_NAME
+            .captures("By <a href=&quot;/profiles/meg-wagner&quot;>Joseph Kelley</a>")
+            .unwrap()
+            .name("name")
+            .unwrap();
+        assert_eq!(m.as_str(), "Joseph Kelley");
 
         let m = RE_AUTHOR_NAME
             .captures("Joseph Kelley")
@@ -730,6 +2694,1522 @@ mod tests {
         assert_eq!(m.as_str(), "J\'oseph-Kelley");
     }
 
+    #[test]
+    fn unicode_author_names() {
+        let doc = Document::from(
+            r#"<html><body><span class="byline">By Анна Петрова and 王小明</span>
+            <p class="author">محمد علي</p></body></html>"#,
+        );
+        assert_eq!(
+            DefaultExtractor.authors(&doc),
+            vec!["محمد علي", "Анна Петрова", "王小明"]
+        );
+
+        struct CapitalizedWord(Regex);
+
+        impl Extractor for CapitalizedWord {
+            fn author_name_regex(&self) -> &Regex {
+                &self.0
+            }
+        }
+
+        let extractor = CapitalizedWord(Regex::new(r"(?P<name>\p{Lu}\p{Ll}+)").unwrap());
+        let doc = Document::from(
+            r#"<html><body><span class="byline">words by Jane, staff</span></body></html>"#,
+        );
+        assert_eq!(extractor.authors(&doc), vec!["Jane"]);
+    }
+
+    #[test]
+    fn json_ld_authors() {
+        let doc = Document::from(
+            r#"<html><head><script type="application/ld+json">
+            {
+                "@context": "https://schema.org",
+                "@type": "NewsArticle",
+                "author": [
+                    {
+                        "@type": "Person",
+                        "name": "Jane Doe",
+                        "url": "https://extrablatt.com/staff/jane-doe",
+                        "sameAs": ["https://twitter.com/janedoe"],
+                        "jobTitle": "Reporter"
+                    },
+                    { "@type": "Person", "name": "John Smith" },
+                    "Max Mustermann"
+                ]
+            }
+            </script></head><body></body></html>"#,
+        );
+        let authors = DefaultExtractor.json_ld_authors(&jsonld::objects(&doc));
+        assert_eq!(authors.len(), 3);
+        assert_eq!(
+            authors[0],
+            Author {
+                name: "Jane Doe".to_string(),
+                url: Some(Url::parse("https://extrablatt.com/staff/jane-doe").unwrap()),
+                same_as: vec![Url::parse("https://twitter.com/janedoe").unwrap()],
+                job_title: Some("Reporter".to_string()),
+            }
+        );
+        assert_eq!(authors[1], Author::new("John Smith"));
+        assert_eq!(authors[2], Author::new("Max Mustermann"));
+
+        let mut names = DefaultExtractor.authors(&doc);
+        names.sort();
+        assert_eq!(names, vec!["Jane Doe", "John Smith", "Max Mustermann"]);
+    }
+
+    #[test]
+    fn article_quotes() {
+        let doc = Document::from(
+            r#"<html><body>
+            <nav><blockquote>Navigation quote that is not part of the article</blockquote></nav>
+            <div itemprop="articleBody">
+                <p>Some text of the article.</p>
+                <blockquote><p>A notable quote from the article.</p></blockquote>
+                <p>More text with a <q>short</q> quote.</p>
+            </div>
+            </body></html>"#,
+        );
+        assert_eq!(
+            DefaultExtractor.quotes(&doc, None),
+            vec!["A notable quote from the article."]
+        );
+    }
+
+    #[test]
+    fn json_ld_video() {
+        let doc = Document::from(
+            r#"<html><head><script type="application/ld+json">
+            {
+                "@context": "https://schema.org",
+                "@type": "VideoObject",
+                "name": "Some video",
+                "contentUrl": "https://extrablatt.com/videos/some-video.mp4",
+                "thumbnailUrl": ["https://extrablatt.com/videos/some-video.jpg"],
+                "duration": "PT1M30S",
+                "uploadDate": "2020-05-05T10:00:00"
+            }
+            </script></head></html>"#,
+        );
+        let videos = DefaultExtractor.json_ld_videos(&jsonld::objects(&doc), None);
+        assert_eq!(videos.len(), 1);
+        let video = &videos[0];
+        assert_eq!(
+            video.url.as_str(),
+            "https://extrablatt.com/videos/some-video.mp4"
+        );
+        assert_eq!(
+            video.thumbnail.as_ref().map(Url::as_str),
+            Some("https://extrablatt.com/videos/some-video.jpg")
+        );
+        assert_eq!(video.duration_secs, Some(90));
+        assert_eq!(
+            video.upload_date.map(|d| d.to_string()),
+            Some("2020-05-05 10:00:00".to_string())
+        );
+
+        let content = DefaultExtractor.article_content(&doc, None, None);
+        assert_eq!(
+            content.video_urls().map(Url::as_str).collect::<Vec<_>>(),
+            vec!["https://extrablatt.com/videos/some-video.mp4"]
+        );
+    }
+
+    #[test]
+    fn syndicated_canonical() {
+        let doc = Document::from(
+            r#"<html><head>
+            <link rel="canonical" href="https://www.wire-service.com/news/some-story-1234">
+            </head></html>"#,
+        );
+        let base_url = Url::parse("https://www.extrablatt.com/").unwrap();
+        let syndication =
+            DefaultExtractor.syndication(&doc, &jsonld::objects(&doc), Some(&base_url));
+        assert_eq!(
+            syndication,
+            Some(Syndication {
+                source_url: Some(
+                    Url::parse("https://www.wire-service.com/news/some-story-1234").unwrap()
+                ),
+                source_org: None,
+            })
+        );
+
+        let base_url = Url::parse("https://wire-service.com/").unwrap();
+        assert!(DefaultExtractor
+            .syndication(&doc, &jsonld::objects(&doc), Some(&base_url))
+            .is_none());
+
+        let doc = Document::from(
+            r#"<html><head>
+            <link rel="canonical" href="https://edition.cnn.com/2020/06/01/some-story">
+            </head></html>"#,
+        );
+        let base_url = Url::parse("https://cnn.com/").unwrap();
+        assert!(DefaultExtractor
+            .syndication(&doc, &jsonld::objects(&doc), Some(&base_url))
+            .is_none());
+        let base_url = Url::parse("https://notcnn.com/").unwrap();
+        assert!(DefaultExtractor
+            .syndication(&doc, &jsonld::objects(&doc), Some(&base_url))
+            .is_some());
+    }
+
+    #[test]
+    fn byline_dates() {
+        let doc = Document::from(
+            r#"<html><body><div class="byline">By Jane Doe</div>
+            <span class="byline-timestamp">Published: January 2, 2020 3:04 PM</span>
+            </body></html>"#,
+        );
+        let date = DefaultExtractor.byline_date(&doc).unwrap();
+        match date.published {
+            Date::DateTime(date) => assert_eq!(date.to_string(), "2020-01-02 15:04:00"),
+            date => panic!("unexpected date {:?}", date),
+        }
+
+        let doc = Document::from(
+            r#"<html><body><span class="timestamp">Updated 3 hours ago</span></body></html>"#,
+        );
+        let expected = chrono::Utc::now().naive_utc() - chrono::Duration::hours(3);
+        match DefaultExtractor.byline_date(&doc).unwrap().published {
+            Date::DateTime(date) => {
+                assert!((date - expected).num_seconds().abs() < 60);
+            }
+            date => panic!("unexpected date {:?}", date),
+        }
+
+        let doc = Document::from(
+            r#"<html><body><span class="update candidate">Last checked January 2, 2020</span>
+            </body></html>"#,
+        );
+        assert!(DefaultExtractor.byline_date(&doc).is_none());
+    }
+
+    #[test]
+    fn content_tiers() {
+        for (value, tier) in &[
+            ("free", ContentTier::Free),
+            ("metered", ContentTier::Metered),
+            ("locked", ContentTier::Locked),
+        ] {
+            let doc = Document::from(
+                format!(
+                    r#"<html><head><meta property="article:content_tier" content="{}"></head></html>"#,
+                    value
+                )
+                .as_str(),
+            );
+            assert_eq!(
+                DefaultExtractor.content_tier(&doc, &jsonld::objects(&doc)),
+                *tier
+            );
+        }
+
+        let doc = Document::from(
+            r#"<html><head><script type="application/ld+json">
+            {
+                "@context": "https://schema.org",
+                "@type": "NewsArticle",
+                "isAccessibleForFree": "False",
+                "hasPart": {
+                    "@type": "WebPageElement",
+                    "isAccessibleForFree": "False",
+                    "cssSelector": ".paywall"
+                }
+            }
+            </script></head></html>"#,
+        );
+        assert_eq!(
+            DefaultExtractor.content_tier(&doc, &jsonld::objects(&doc)),
+            ContentTier::Locked
+        );
+
+        let doc = Document::from(
+            r#"<html><head><script type="application/ld+json">
+            {
+                "@type": "NewsArticle",
+                "isAccessibleForFree": false,
+                "hasPart": {
+                    "@type": "WebPageElement",
+                    "isAccessibleForFree": false,
+                    "cssSelector": ".meteredContent"
+                }
+            }
+            </script></head></html>"#,
+        );
+        assert_eq!(
+            DefaultExtractor.content_tier(&doc, &jsonld::objects(&doc)),
+            ContentTier::Metered
+        );
+
+        let doc = Document::from("<html><head></head></html>");
+        assert_eq!(
+            DefaultExtractor.content_tier(&doc, &jsonld::objects(&doc)),
+            ContentTier::Unknown
+        );
+    }
+
+    #[test]
+    fn embedded_tweet() {
+        let doc = Document::from(
+            r#"<html><body><div itemprop="articleBody">
+            <p>Some text of the article.</p>
+            <blockquote class="twitter-tweet"><p lang="en">Tweet with a <a href="https://twitter.com/hashtag/news">#news</a></p>&mdash; Extrablatt (@extrablatt)
+            <a href="https://twitter.com/extrablatt/status/1234567890?ref_src=twsrc">May 5, 2020</a></blockquote>
+            <script async src="https://platform.twitter.com/widgets.js"></script>
+            <iframe src="https://www.youtube.com/embed/abc"></iframe>
+            </div></body></html>"#,
+        );
+        let embeds = DefaultExtractor.social_embeds(&doc, None);
+        assert_eq!(
+            embeds,
+            vec![SocialEmbed {
+                url: Url::parse("https://twitter.com/extrablatt/status/1234567890?ref_src=twsrc")
+                    .unwrap(),
+                provider: SocialProvider::Twitter,
+            }]
+        );
+    }
+
+    #[test]
+    fn oembed_endpoints() {
+        let doc = Document::from(
+            r#"<html><head>
+            <link rel="alternate" type="application/json+oembed" href="/oembed?url=https%3A%2F%2Fextrablatt.com%2Fvideo&format=json">
+            <link rel="alternate" type="text/xml+oembed" href="/oembed?url=https%3A%2F%2Fextrablatt.com%2Fvideo&format=xml">
+            <link rel="stylesheet" type="application/json+oembed" href="/style.css">
+            <link rel="alternate" type="application/json+oembed" href="https://www.youtube.com/oembed?url=https%3A%2F%2Fwww.youtube.com%2Fwatch%3Fv%3Dabc">
+            </head></html>"#,
+        );
+        let base_url = Url::parse("https://extrablatt.com/politics/article").unwrap();
+        assert_eq!(
+            DefaultExtractor
+                .oembed_endpoints(&doc, Some(&base_url))
+                .iter()
+                .map(Url::as_str)
+                .collect::<Vec<_>>(),
+            vec![
+                "https://extrablatt.com/oembed?url=https%3A%2F%2Fextrablatt.com%2Fvideo&format=json",
+                "https://www.youtube.com/oembed?url=https%3A%2F%2Fwww.youtube.com%2Fwatch%3Fv%3Dabc"
+            ]
+        );
+    }
+
+    #[test]
+    fn detect_web_story() {
+        let doc = Document::from(
+            r#"<html><head><script async custom-element="amp-story" src="https://cdn.ampproject.org/v0/amp-story-1.0.js"></script></head>
+            <body><amp-story standalone title="Story">
+                <amp-story-page id="cover"><amp-story-grid-layer><h1>Cover</h1></amp-story-grid-layer></amp-story-page>
+                <amp-story-page id="page-1"><amp-story-grid-layer><p>First page</p></amp-story-grid-layer></amp-story-page>
+            </amp-story></body></html>"#,
+        );
+        assert!(DefaultExtractor.is_web_story(&doc));
+        assert_eq!(
+            DefaultExtractor.web_story_text(&doc).unwrap(),
+            "Cover\nFirst page"
+        );
+
+        let doc = Document::from("<html><body><p>Plain article</p></body></html>");
+        assert!(!DefaultExtractor.is_web_story(&doc));
+    }
+
+    #[test]
+    fn custom_body_selectors() {
+        struct PostExtractor;
+
+        impl Extractor for PostExtractor {
+            fn article_body_selectors(&self) -> Vec<(&str, &str)> {
+                vec![("class", "post-content")]
+            }
+        }
+
+        let doc = Document::from(
+            r#"<html><body>
+            <div class="sidebar"><p>Sidebar text that is not part of the article.</p></div>
+            <div class="post-content"><p>The text of the post.</p></div>
+            </body></html>"#,
+        );
+        assert_eq!(
+            PostExtractor.text(&doc, Language::English).unwrap(),
+            "The text of the post."
+        );
+
+        // a node matching another selector falls back to scoring, unless the
+        // first selector with a single match is preferred
+        let doc = Document::from(
+            r#"<html><body>
+            <div itemprop="articleBody"><p>The teaser of the article.</p></div>
+            <div data-testid="article-body"><p>The text of the article.</p></div>
+            </body></html>"#,
+        );
+        let node = DefaultExtractor.article_node(&doc, Language::English);
+        let scored = ArticleTextNodeExtractor::calculate_best_node(&doc, Language::English);
+        assert_eq!(
+            node.map(|node| node.index()),
+            scored.map(|node| node.index())
+        );
+        let extractor = DefaultExtractor::configurable(DefaultExtractorConfig {
+            prefer_first_body_selector: true,
+            ..Default::default()
+        });
+        assert_eq!(
+            extractor.text(&doc, Language::English).unwrap(),
+            "The teaser of the article."
+        );
+    }
+
+    #[test]
+    fn article_node_of_fixtures() {
+        // the node picked before the body selectors were configurable
+        fn baseline_node(doc: &Document) -> Option<usize> {
+            let mut iter = doc
+                .find(Name("body").descendant(ArticleTextNodeExtractor::article_body_predicate()));
+            match (iter.next(), iter.next()) {
+                (Some(node), None) => Some(node.index()),
+                _ => ArticleTextNodeExtractor::calculate_best_node(doc, Language::English)
+                    .map(|node| node.index()),
+            }
+        }
+
+        for html in [
+            include_str!("../fixtures/article.html"),
+            include_str!("../fixtures/category_index.html"),
+            include_str!("../fixtures/json_ld_article.html"),
+            include_str!("../fixtures/listicle.html"),
+            include_str!("../fixtures/menu_lines.html"),
+        ] {
+            let doc = Document::from(html);
+            assert_eq!(
+                DefaultExtractor
+                    .article_node(&doc, Language::English)
+                    .map(|node| node.index()),
+                baseline_node(&doc)
+            );
+        }
+    }
+
+    #[test]
+    fn configured_title_strategy() {
+        let doc = Document::from(
+            r#"<html><head>
+            <title>Document Title</title>
+            <meta property="og:title" content="Open Graph Title">
+            </head><body></body></html>"#,
+        );
+        assert_eq!(DefaultExtractor.title(&doc).unwrap(), "Open Graph Title");
+
+        let extractor = DefaultExtractor::configurable(DefaultExtractorConfig {
+            title_strategy: TitleStrategy::Document,
+            ..Default::default()
+        });
+        assert_eq!(extractor.title(&doc).unwrap(), "Document Title");
+    }
+
+    #[test]
+    fn configured_text_cleaner() {
+        let doc = Document::from(
+            r#"<html><body><div itemprop="articleBody">
+            <p>Share</p>
+            <p>Print</p>
+            <p>The text of the article, which is long enough to be kept.</p>
+            </div></body></html>"#,
+        );
+        // short lines are only kept next to a longer line
+        let txt = "Print\nThe text of the article, which is long enough to be kept.";
+        let extractor = DefaultExtractor::configurable(DefaultExtractorConfig {
+            min_line_words: Some(4),
+            ..Default::default()
+        });
+        assert_eq!(
+            extractor.text(&doc, Language::English).as_deref(),
+            Some(txt)
+        );
+        let content = extractor.article_content(&doc, None, None);
+        assert_eq!(content.text.as_deref(), Some(txt));
+
+        let content = DefaultExtractor.article_content(&doc, None, None);
+        assert!(content.text.unwrap().starts_with("Share\n"));
+    }
+
+    #[test]
+    #[cfg(feature = "stopwords")]
+    fn keywords_without_extra_stopwords() {
+        let doc = Document::from(
+            r#"<html><body><div itemprop="articleBody">
+            <p>The council approved the budget for the city schools.</p>
+            <p>The city council debated the budget of the city for hours.</p>
+            <p>Sign up for the Extrablatt newsletter, the newsletter of the city.</p>
+            </div></body></html>"#,
+        );
+        let keywords = |extractor: &dyn Fn(&Document) -> ArticleContent| {
+            extractor(&doc)
+                .keywords
+                .into_iter()
+                .take(3)
+                .collect::<Vec<_>>()
+        };
+        assert!(keywords(&|doc| DefaultExtractor.article_content(doc, None, None)).is_empty());
+
+        let extractor = DefaultExtractor::configurable(DefaultExtractorConfig {
+            max_text_keywords: Some(10),
+            ..Default::default()
+        });
+        assert_eq!(
+            keywords(&|doc| extractor.article_content(doc, None, None)),
+            vec!["city", "council", "budget"]
+        );
+
+        let extractor = DefaultExtractor::configurable(DefaultExtractorConfig {
+            extra_stopwords: vec!["city".to_string()],
+            max_text_keywords: Some(10),
+            ..Default::default()
+        });
+        assert_eq!(
+            keywords(&|doc| extractor.article_content(doc, None, None)),
+            vec!["council", "budget", "newsletter"]
+        );
+    }
+
+    #[test]
+    fn geo_location() {
+        let extractor = DefaultExtractor;
+        let doc = Document::from(
+            r#"<html><head>
+            <meta name="geo.position" content="52.520008;13.404954">
+            <meta name="geo.placename" content="Berlin">
+            </head></html>"#,
+        );
+        assert_eq!(
+            extractor.geo(&doc, &jsonld::objects(&doc)),
+            Some(GeoLocation {
+                lat: Some(52.520008),
+                lon: Some(13.404954),
+                name: Some("Berlin".to_string()),
+            })
+        );
+
+        let doc = Document::from(
+            r#"<html><head>
+            <meta name="geo.position" content="152.5;13.4">
+            <script type="application/ld+json">
+            {
+                "@type": "NewsArticle",
+                "contentLocation": {
+                    "@type": "Place",
+                    "name": "Hamburg",
+                    "geo": {"@type": "GeoCoordinates", "latitude": 53.55, "longitude": "9.99"}
+                }
+            }
+            </script></head></html>"#,
+        );
+        assert_eq!(
+            extractor.geo(&doc, &jsonld::objects(&doc)),
+            Some(GeoLocation {
+                lat: Some(53.55),
+                lon: Some(9.99),
+                name: Some("Hamburg".to_string()),
+            })
+        );
+        assert_eq!(extractor.geo(&Document::from("<html></html>"), &[]), None);
+
+        assert_eq!(
+            extractor
+                .dateline("SAN FRANCISCO, Calif. (AP) — The city council approved")
+                .as_deref(),
+            Some("San Francisco, Calif.")
+        );
+        assert_eq!(
+            extractor.dateline("BERLIN - The city").as_deref(),
+            Some("Berlin")
+        );
+        assert_eq!(extractor.dateline("The city council - approved"), None);
+
+        let doc = Document::from(
+            r#"<html><head><meta name="ICBM" content="52.52, 13.40"></head><body>
+            <div itemprop="articleBody"><p>BERLIN (Reuters) - The city council approved the new budget for the schools on Monday.</p></div>
+            </body></html>"#,
+        );
+        assert_eq!(
+            extractor.article_content(&doc, None, None).geo,
+            Some(GeoLocation {
+                lat: Some(52.52),
+                lon: Some(13.40),
+                name: Some("Berlin".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn json_ld_product() {
+        let doc = Document::from(
+            r#"<html><head>
+            <meta property="og:type" content="product">
+            <script type="application/ld+json">
+            {
+                "@context": "https://schema.org",
+                "@type": "Product",
+                "name": "Noise Cancelling Headphones",
+                "offers": {
+                    "@type": "Offer",
+                    "price": 199.99,
+                    "priceCurrency": "USD",
+                    "availability": "https://schema.org/InStock"
+                }
+            }
+            </script></head><body><p>Our favorite headphones are on sale.</p></body></html>"#,
+        );
+        assert_eq!(
+            DefaultExtractor.article_content(&doc, None, None).product,
+            Some(Product {
+                name: Some("Noise Cancelling Headphones".to_string()),
+                price: Some("199.99".to_string()),
+                currency: Some("USD".to_string()),
+                availability: Some("InStock".to_string()),
+            })
+        );
+
+        let doc = Document::from(
+            r#"<html><head>
+            <meta property="og:type" content="product.item">
+            <meta property="og:title" content="Coffee Grinder">
+            <meta property="product:price:amount" content="49.00">
+            <meta property="product:price:currency" content="EUR">
+            </head></html>"#,
+        );
+        let product = DefaultExtractor
+            .product(&doc, &jsonld::objects(&doc))
+            .unwrap();
+        assert_eq!(product.name.as_deref(), Some("Coffee Grinder"));
+        assert_eq!(product.price.as_deref(), Some("49.00"));
+        assert_eq!(product.currency.as_deref(), Some("EUR"));
+
+        let doc = Document::from(
+            r#"<html><head><meta property="og:type" content="article"></head></html>"#,
+        );
+        assert_eq!(DefaultExtractor.product(&doc, &jsonld::objects(&doc)), None);
+    }
+
+    #[test]
+    fn json_ld_recipe() {
+        let doc = Document::from(
+            r#"<html><head><script type="application/ld+json">
+            {
+                "@context": "https://schema.org",
+                "@type": "Recipe",
+                "name": "Pancakes",
+                "recipeIngredient": ["200g flour", "2 eggs", "300ml milk"],
+                "recipeInstructions": [
+                    {"@type": "HowToStep", "text": "Whisk the eggs and milk."},
+                    {
+                        "@type": "HowToSection",
+                        "name": "Baking",
+                        "itemListElement": [
+                            {"@type": "HowToStep", "text": "Stir in the flour."},
+                            {"@type": "HowToStep", "text": "Fry in a hot pan."}
+                        ]
+                    }
+                ]
+            }
+            </script></head><body></body></html>"#,
+        );
+        let content = DefaultExtractor.article_content(&doc, None, None);
+        assert_eq!(
+            content.structured,
+            Some(StructuredContent::Recipe {
+                name: Some("Pancakes".to_string()),
+                ingredients: vec![
+                    "200g flour".to_string(),
+                    "2 eggs".to_string(),
+                    "300ml milk".to_string()
+                ],
+                steps: vec![
+                    "Whisk the eggs and milk.".to_string(),
+                    "Stir in the flour.".to_string(),
+                    "Fry in a hot pan.".to_string()
+                ],
+            })
+        );
+
+        let doc = Document::from(
+            r#"<script type="application/ld+json">
+            {"@type": "HowTo", "step": "Unplug the router.\nWait ten seconds."}
+            </script>"#,
+        );
+        assert_eq!(
+            DefaultExtractor.structured_content(&jsonld::objects(&doc)),
+            Some(StructuredContent::HowTo {
+                name: None,
+                steps: vec![
+                    "Unplug the router.".to_string(),
+                    "Wait ten seconds.".to_string()
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn rel_author_links() {
+        let doc = Document::from(
+            r#"<html><head>
+            <link rel="author" title="John Roe" href="https://extrablatt.com/staff/john">
+            <script type="application/ld+json">
+            {"@type": "NewsArticle", "author": {"@type": "Person", "name": "Jane Doe", "jobTitle": "Editor"}}
+            </script>
+            </head><body>
+            <p>By <a rel="author external" href="/staff/jane">Jane  Doe</a></p>
+            <a rel="nofollow" href="/staff/other">Other</a>
+            </body></html>"#,
+        );
+        let base_url = Url::parse("https://extrablatt.com/politics/article.html").unwrap();
+        let extractor = DefaultExtractor;
+
+        let authors = extractor.author_details(&doc, &jsonld::objects(&doc), Some(&base_url));
+        assert_eq!(authors.len(), 2);
+        assert_eq!(authors[0].name, "John Roe");
+        assert_eq!(authors[1].name, "Jane Doe");
+        assert_eq!(
+            authors[1].url,
+            Some(Url::parse("https://extrablatt.com/staff/jane").unwrap())
+        );
+        assert_eq!(authors[1].job_title.as_deref(), Some("Editor"));
+
+        let names = extractor.authors(&doc);
+        assert_eq!(names[..2], ["John Roe", "Jane Doe"]);
+    }
+
+    #[test]
+    fn microdata_comments() {
+        let doc = Document::from(
+            r#"<html><body>
+            <div itemprop="articleBody"><p>The council approved the budget.</p></div>
+            <ol id="comments">
+              <li itemprop="comment" itemscope itemtype="https://schema.org/Comment">
+                <span itemprop="author" itemscope itemtype="https://schema.org/Person">
+                  <span itemprop="name">Jane  Doe</span>
+                </span>
+                <time itemprop="dateCreated" datetime="2020-05-01T12:30:00">May 1</time>
+                <div itemprop="text"><p>Finally some good news.</p></div>
+              </li>
+              <li itemprop="comment" itemscope itemtype="http://schema.org/Comment">
+                <div itemprop="text">Anonymous comment.</div>
+              </li>
+              <li itemscope itemtype="https://schema.org/Comment"><div itemprop="text"> </div></li>
+            </ol>
+            </body></html>"#,
+        );
+        let comments = DefaultExtractor.comments(&doc);
+        assert_eq!(
+            comments,
+            vec![
+                Comment {
+                    author: Some("Jane Doe".to_string()),
+                    text: "Finally some good news.".to_string(),
+                    date: chrono::NaiveDate::from_ymd_opt(2020, 5, 1)
+                        .and_then(|d| d.and_hms_opt(12, 30, 0)),
+                },
+                Comment {
+                    author: None,
+                    text: "Anonymous comment.".to_string(),
+                    date: None,
+                },
+            ]
+        );
+        assert!(DefaultExtractor
+            .article_content(&doc, None, None)
+            .comments
+            .is_empty());
+    }
+
+    #[test]
+    fn class_comments() {
+        let doc = Document::from(
+            r#"<html><body>
+            <div class="comment"><div itemprop="articleBody"><p>Article text.</p></div></div>
+            <ul class="comment-list">
+              <li class="comment even">
+                <div class="comment-author"><cite class="fn">John Roe</cite> says:</div>
+                <time datetime="2020-05-02">May 2</time>
+                <div class="comment-content"><p>Great article.</p></div>
+              </li>
+            </ul>
+            </body></html>"#,
+        );
+        let comments = DefaultExtractor.comments(&doc);
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].author.as_deref(), Some("John Roe"));
+        assert_eq!(comments[0].text, "Great article.");
+        assert!(comments[0].date.is_some());
+    }
+
+    #[test]
+    fn category_index_page() {
+        let extractor = DefaultExtractor;
+        let looks_like_index = |doc: &Document| {
+            let node = extractor.article_node(doc, Language::English);
+            extractor.looks_like_index(doc, node.as_ref())
+        };
+        let index = Document::from(include_str!("../fixtures/category_index.html"));
+        assert!(looks_like_index(&index));
+        assert!(
+            extractor
+                .article_content(&index, None, Some(Language::English))
+                .looks_like_index
+        );
+
+        let article = Document::from(include_str!("../fixtures/article.html"));
+        assert!(!looks_like_index(&article));
+        assert!(
+            !extractor
+                .article_content(&article, None, Some(Language::English))
+                .looks_like_index
+        );
+
+        // a long article with a list of related links is still an article
+        let html = format!(
+            r#"<html><body><div itemprop="articleBody">{}</div><ul>{}</ul></body></html>"#,
+            "<p>The council debated the budget of the city for many hours on Monday.</p>"
+                .repeat(20),
+            r#"<li><a href="/related">Another story about the city council</a></li>"#.repeat(12)
+        );
+        assert!(!looks_like_index(&Document::from(html.as_str())));
+    }
+
+    #[test]
+    fn mobile_desktop_url() {
+        let url = Url::parse("https://m.example.com/x").unwrap();
+        let doc = Document::from(
+            r#"<html><head><link rel="canonical" href="https://example.com/x"></head></html>"#,
+        );
+        assert_eq!(
+            DefaultExtractor.desktop_url(&url, &doc),
+            Some(Url::parse("https://example.com/x").unwrap())
+        );
+
+        let doc = Document::from("<html></html>");
+        let url = Url::parse("https://amp.example.com/news/amp/story").unwrap();
+        assert_eq!(
+            DefaultExtractor.desktop_url(&url, &doc),
+            Some(Url::parse("https://example.com/news/story").unwrap())
+        );
+
+        let url = Url::parse("https://example.com/x").unwrap();
+        assert_eq!(DefaultExtractor.desktop_url(&url, &doc), None);
+        assert_eq!(
+            desktop_variant(&Url::parse("https://m.com/x").unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn lead_image_from_content() {
+        let doc = Document::from(
+            r#"<html><head>
+            <meta property="og:image" content="https://extrablatt.com/share-card.png">
+            </head><body>
+            <div itemprop="articleBody">
+                <img src="/icons/author.png" width="48" height="48">
+                <figure>
+                    <img src="/images/lead.jpg" width="1200" height="675">
+                    <figcaption>The lead image.</figcaption>
+                </figure>
+                <p>The text of the article.</p>
+            </div>
+            </body></html>"#,
+        );
+        let base_url = Url::parse("https://extrablatt.com/").unwrap();
+        let content = DefaultExtractor.article_content(&doc, Some(&base_url), None);
+        assert_eq!(
+            content.top_image.unwrap().as_str(),
+            "https://extrablatt.com/share-card.png"
+        );
+        assert_eq!(
+            content.lead_image,
+            Some(Image {
+                caption: Some("The lead image.".to_string()),
+                width: Some(1200),
+                height: Some(675),
+                ..Image::new(Url::parse("https://extrablatt.com/images/lead.jpg").unwrap())
+            })
+        );
+        let node = DefaultExtractor.article_node(&doc, Language::English);
+        assert_eq!(
+            DefaultExtractor.content_lead_image(&doc, node.as_ref(), Some(&base_url)),
+            content.lead_image
+        );
+    }
+
+    #[test]
+    fn image_attributes() {
+        let doc = Document::from(
+            r#"<html><body><div itemprop="articleBody">
+            <img src="/chart.png" alt="A chart of the results" title="Results" loading="lazy">
+            <img src="/divider.png" alt="">
+            <p>The text of the article.</p>
+            </div></body></html>"#,
+        );
+        let base_url = Url::parse("https://extrablatt.com/").unwrap();
+        let content = DefaultExtractor.article_content(&doc, Some(&base_url), None);
+        assert_eq!(content.images.len(), 2);
+
+        let chart = &content.images[0];
+        assert_eq!(chart.url.as_str(), "https://extrablatt.com/chart.png");
+        assert_eq!(chart.alt.as_deref(), Some("A chart of the results"));
+        assert_eq!(chart.title.as_deref(), Some("Results"));
+        assert_eq!(chart.loading.as_deref(), Some("lazy"));
+
+        let missing = content.images_missing_alt();
+        assert_eq!(missing.len(), 1);
+        assert_eq!(
+            missing[0].url.as_str(),
+            "https://extrablatt.com/divider.png"
+        );
+    }
+
+    #[test]
+    fn tracking_images() {
+        let doc = Document::from(
+            r#"<html><body><div itemprop="articleBody">
+            <img src="https://ad.doubleclick.net/pixel.gif" width="1" height="1">
+            <img src="/pixel.gif" width="1" height="1">
+            <img src="data:image/gif;base64,R0lGODlhAQABAAAAACw=">
+            <img src="/photo.jpg" alt="The new bridge" width="800" height="600">
+            <p>The text of the article.</p>
+            </div></body></html>"#,
+        );
+        let base_url = Url::parse("https://extrablatt.com/").unwrap();
+        let extractor = DefaultExtractor;
+        let photo = "https://extrablatt.com/photo.jpg";
+
+        let content = extractor.article_content(&doc, Some(&base_url), None);
+        let urls: Vec<_> = content.images.iter().map(|img| img.url.as_str()).collect();
+        assert_eq!(urls, vec![photo]);
+        let urls = extractor.image_urls(&doc, Some(&base_url));
+        assert_eq!(
+            urls.iter().map(Url::as_str).collect::<Vec<_>>(),
+            vec![photo]
+        );
+
+        let content = crate::Config::builder()
+            .keep_tracking_images(true)
+            .build()
+            .extract_content(&extractor, &doc, Some(&base_url), None);
+        assert_eq!(content.images.len(), 3);
+    }
+
+    #[test]
+    fn menu_lines() {
+        let doc = Document::from(include_str!("../fixtures/menu_lines.html"));
+        let paragraphs = "The old river bridge reopened on Monday after two years of repairs that cost the city more than twelve million euros.\nCommuters welcomed the shorter way to work, although the speed limit on the bridge stays at thirty for now.";
+
+        let extractor = DefaultExtractor::configurable(DefaultExtractorConfig {
+            max_line_link_density: Some(crate::clean::MAX_LINE_LINK_DENSITY),
+            min_line_words: Some(4),
+            ..Default::default()
+        });
+        assert_eq!(
+            extractor.text(&doc, Language::English).as_deref(),
+            Some(paragraphs)
+        );
+        // also applies to the crawled articles
+        let content = extractor.article_content(&doc, None, Some(Language::English));
+        assert_eq!(content.text.as_deref(), Some(paragraphs));
+
+        let extractor = DefaultExtractor::configurable(DefaultExtractorConfig {
+            max_line_link_density: Some(crate::clean::MAX_LINE_LINK_DENSITY),
+            ..Default::default()
+        });
+        let txt = extractor.text(&doc, Language::English).unwrap();
+        assert!(txt.starts_with("Share\nPrint\nThe old river bridge"));
+        assert!(!txt.contains("Sections:"));
+        assert!(!txt.contains("More:"));
+
+        // link heavy lines are kept by default
+        let txt = DefaultExtractor.text(&doc, Language::English).unwrap();
+        assert!(txt.contains("Sections:"));
+
+        let doc = Document::from(
+            r#"<html><body><div itemprop="articleBody">
+            <p>According to <a href="/report">a new report by the Federal Reserve Bank of New York</a>, inflation rose.</p>
+            </div></body></html>"#,
+        );
+        let txt = DefaultExtractor.text(&doc, Language::English).unwrap();
+        assert!(txt.starts_with("According to a new report by the Federal Reserve"));
+        assert!(txt.ends_with("inflation rose."));
+    }
+
+    #[test]
+    fn ad_blocks() {
+        let doc = Document::from(
+            r#"<html><body><div itemprop="articleBody">
+                <p>The council approved the new budget for the schools on Monday evening.</p>
+                <div class="OUTBRAIN" data-widget-id="AR_1">
+                    <div class="ob-widget-header">Recommended for you</div>
+                    <a href="https://paid.outbrain.com/1">You won't believe these celebrity homes</a>
+                </div>
+                <div class="inline-slot"><span>Advertisement</span><p>Buy the best shoes now</p></div>
+                <p>Teachers and parents welcomed the decision after months of debate.</p>
+                <div id="taboola-feed-below-article"><p>Doctors hate this one trick</p></div>
+            </div></body></html>"#,
+        );
+        assert_eq!(
+            DefaultExtractor
+                .text(&doc, Language::English)
+                .as_deref(),
+            Some("The council approved the new budget for the schools on Monday evening.\nTeachers and parents welcomed the decision after months of debate.")
+        );
+    }
+
+    #[test]
+    fn link_consistency() {
+        let fetched_url = Url::parse("https://extrablatt.com/politics/budget#comments").unwrap();
+        let doc = Document::from(
+            r#"<html><head>
+                <link rel="canonical" href="https://extrablatt.com/politics/budget/">
+                <link rel="amphtml" href="/amp/politics/budget">
+            </head></html>"#,
+        );
+        assert_eq!(
+            DefaultExtractor.link_consistency(&doc, &fetched_url),
+            LinkConsistency {
+                self_canonical: true,
+                canonical_host_matches: true,
+                has_amp: true,
+            }
+        );
+        assert_eq!(
+            DefaultExtractor
+                .amp_url(&doc, Some(&fetched_url))
+                .map(String::from),
+            Some("https://extrablatt.com/amp/politics/budget".to_string())
+        );
+
+        let doc = Document::from(
+            r#"<html><head>
+                <link rel="canonical" href="https://www.wire-service.com/stories/budget">
+            </head></html>"#,
+        );
+        assert_eq!(
+            DefaultExtractor.link_consistency(&doc, &fetched_url),
+            LinkConsistency {
+                self_canonical: false,
+                canonical_host_matches: false,
+                has_amp: false,
+            }
+        );
+
+        let doc = Document::from("<html><head></head></html>");
+        assert!(
+            DefaultExtractor
+                .link_consistency(&doc, &fetched_url)
+                .self_canonical
+        );
+    }
+
+    #[test]
+    fn attributions() {
+        let doc = Document::from(
+            r#"<html><body><span class="byline">By Max Mustermann</span><div itemprop="articleBody">
+            <p>The central bank raised interest rates by a quarter point on Thursday, its third increase this year.</p>
+            <p>(Reporting by Jane Doe and Ali Khan; Additional reporting by Mary Major; Editing by John Smith)</p>
+        </div></body></html>"#,
+        );
+        let content = DefaultExtractor.article_content(&doc, None, None);
+        let credits: Vec<_> = content
+            .attributions
+            .iter()
+            .map(|a| (a.role.as_str(), a.name.as_str()))
+            .collect();
+        assert_eq!(
+            credits,
+            vec![
+                ("Reporting", "Jane Doe"),
+                ("Reporting", "Ali Khan"),
+                ("Additional reporting", "Mary Major"),
+                ("Editing", "John Smith"),
+            ]
+        );
+        assert_eq!(
+            content.authors,
+            vec!["Max Mustermann", "Jane Doe", "Ali Khan", "Mary Major"]
+        );
+
+        let text = "Some text.\nReporting by Jane Doe; Editing by John Smith\nAll rights reserved.";
+        assert!(DefaultExtractor.attributions(text).is_empty());
+
+        let text = "Some text.\nReporting by Jane Doe in Washington and Ali Khan in New York; \
+                    Editing by John Smith, writing for the news desk; Compiled by the news desk";
+        let credits: Vec<_> = DefaultExtractor
+            .attributions(text)
+            .into_iter()
+            .map(|a| (a.role, a.name))
+            .collect();
+        assert_eq!(
+            credits,
+            vec![
+                ("Reporting".to_string(), "Jane Doe".to_string()),
+                ("Reporting".to_string(), "Ali Khan".to_string()),
+                ("Editing".to_string(), "John Smith".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "stopwords")]
+    fn language_mismatch() {
+        let html = |section| {
+            format!(
+                r#"<html lang="en"><head>
+                <link rel="canonical" href="https://extrablatt.com/{}/council-budget">
+            </head><body><div itemprop="articleBody">
+                <p>According to a new report by the Federal Reserve Bank of New York, inflation rose in the second quarter, while wages did not keep up with the prices of food and housing.</p>
+            </div></body></html>"#,
+                section
+            )
+        };
+        // the canonical link takes precedence over the newspaper's url
+        let base_url = Url::parse("https://extrablatt.com/arabic/").unwrap();
+
+        let doc = Document::from(html("arabic").as_str());
+        let content = DefaultExtractor.article_content(&doc, Some(&base_url), None);
+        assert!(content.language_mismatch);
+
+        let doc = Document::from(html("politics").as_str());
+        let content = DefaultExtractor.article_content(&doc, Some(&base_url), None);
+        assert!(!content.language_mismatch);
+
+        // without a canonical link the url the page was fetched from is used
+        let html = html("arabic");
+        let html = html
+            .lines()
+            .filter(|line| !line.contains("canonical"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let doc = Document::from(html.as_str());
+        let content = DefaultExtractor.article_content(&doc, Some(&base_url), None);
+        assert!(content.language_mismatch);
+        let content = DefaultExtractor.article_content(&doc, None, None);
+        assert!(!content.language_mismatch);
+    }
+
+    #[test]
+    fn listicle_items() {
+        let doc = Document::from(include_str!("../fixtures/listicle.html"));
+        let base_url = Url::parse("https://extrablatt.com/").unwrap();
+        let content = DefaultExtractor.article_content(&doc, Some(&base_url), None);
+
+        let headings: Vec<_> = content
+            .list_items
+            .iter()
+            .map(|item| item.heading.as_str())
+            .collect();
+        assert_eq!(
+            headings,
+            vec!["1. Luigi's", "2. Slice of Heaven", "3. Napoli Express"]
+        );
+        assert_eq!(
+            content.list_items[0].body,
+            "The dough rests for three days, and you can taste it in the crust.\n\nGet there early, the line is long on weekends."
+        );
+        assert_eq!(
+            content.list_items[0]
+                .image
+                .as_ref()
+                .map(|image| image.url.as_str()),
+            Some("https://extrablatt.com/images/luigis.jpg")
+        );
+        assert_eq!(content.list_items[1].image, None);
+        // ends at the next heading
+        assert_eq!(
+            content.list_items[2].body,
+            "A small place near the station that only serves four kinds of pizza, all of them great."
+        );
+        assert!(content
+            .text
+            .unwrap()
+            .contains("Open every day except Monday."));
+
+        let article = Document::from(include_str!("../fixtures/article.html"));
+        let node = DefaultExtractor.article_node(&article, Language::English);
+        assert_eq!(
+            DefaultExtractor.listicle_items(&article, node.as_ref(), Some(&base_url)),
+            None
+        );
+    }
+
+    #[test]
+    fn normalize_author_names() {
+        let extractor = DefaultExtractor;
+        assert_eq!(extractor.normalize_author_name("JOHN SMITH"), "John Smith");
+        assert_eq!(
+            extractor.normalize_author_name("mary o'neil-jones"),
+            "Mary O'Neil-Jones"
+        );
+        assert_eq!(
+            extractor.normalize_author_name("Jane McDonald"),
+            "Jane McDonald"
+        );
+        assert_eq!(
+            extractor.normalize_author_name("Reporter John Smith"),
+            "Reporter John Smith"
+        );
+        assert_eq!(
+            extractor.normalize_author_name("Dr. Jane Doe"),
+            "Dr. Jane Doe"
+        );
+
+        let extractor = DefaultExtractor::configurable(DefaultExtractorConfig {
+            author_role_prefixes: vec!["Senior".to_string(), "Reporter".to_string()],
+            strip_author_honorifics: true,
+            ..Default::default()
+        });
+        assert_eq!(
+            extractor.normalize_author_name("Reporter John Smith"),
+            "John Smith"
+        );
+        assert_eq!(
+            extractor.normalize_author_name("senior reporter JOHN SMITH"),
+            "John Smith"
+        );
+        assert_eq!(extractor.normalize_author_name("Dr. Jane Doe"), "Jane Doe");
+        assert_eq!(extractor.normalize_author_name("Reporter"), "Reporter");
+
+        let doc = Document::from(
+            r#"<html><body>
+            <span class="byline">By JOHN SMITH</span>
+            <a rel="author" href="/authors/john">Reporter John Smith</a>
+            </body></html>"#,
+        );
+        assert_eq!(extractor.authors(&doc), vec!["John Smith"]);
+    }
+
+    #[test]
+    fn table_of_contents() {
+        let doc = Document::from(
+            r##"<html><body>
+            <nav class="site-nav"><a href="/politics">Politics</a></nav>
+            <article>
+                <nav>
+                    <ol>
+                        <li><a href="#background">Background</a></li>
+                        <li><a href="#the-vote">The
+                            vote</a></li>
+                        <li><a href="#whats-next">What's next</a></li>
+                    </ol>
+                </nav>
+                <h2 id="background">Background</h2>
+                <p>The council has been discussing the budget for months.</p>
+            </article>
+            </body></html>"##,
+        );
+        assert_eq!(
+            DefaultExtractor.table_of_contents(&doc),
+            vec![
+                ("Background".to_string(), Some("background".to_string())),
+                ("The vote".to_string(), Some("the-vote".to_string())),
+                ("What's next".to_string(), Some("whats-next".to_string())),
+            ]
+        );
+
+        let doc = Document::from(
+            r##"<html><body>
+            <div id="toc"><ul><li><a href="#intro">Intro</a></li><li><a>Summary</a></li></ul></div>
+            </body></html>"##,
+        );
+        let content = DefaultExtractor.article_content(&doc, None, None);
+        assert_eq!(
+            content.toc,
+            vec![
+                ("Intro".to_string(), Some("intro".to_string())),
+                ("Summary".to_string(), None),
+            ]
+        );
+
+        let doc = Document::from(r#"<html><body><nav><a href="/a">A</a></nav></body></html>"#);
+        assert!(DefaultExtractor.table_of_contents(&doc).is_empty());
+    }
+
+    #[test]
+    fn all_links_resolved_and_deduped() {
+        let doc = Document::from(
+            r##"<html><body>
+            <a href="/politics">Politics</a>
+            <a href="https://extrablatt.com/politics">Politics</a>
+            <a href="https://extrablatt.com/politics#top">Politics</a>
+            <a href="sports">Sports</a>
+            <a href="#comments">Comments</a>
+            <a href="mailto:news@extrablatt.com">Mail</a>
+            <a href="javascript:void(0)">Menu</a>
+            </body></html>"##,
+        );
+        let base_url = Url::parse("https://extrablatt.com/").unwrap();
+        let extractor = DefaultExtractor;
+        assert_eq!(extractor.all_urls(&doc).len(), 7);
+        assert_eq!(
+            extractor
+                .all_links(&doc, Some(&base_url))
+                .iter()
+                .map(Url::as_str)
+                .collect::<Vec<_>>(),
+            vec![
+                "https://extrablatt.com/politics",
+                "https://extrablatt.com/sports"
+            ]
+        );
+        assert_eq!(extractor.categories(&doc, &base_url).len(), 2);
+    }
+
+    #[test]
+    fn next_page_url() {
+        let url = Url::parse("https://extrablatt.com/politics").unwrap();
+        let next = |html: &str, page: usize| {
+            DefaultExtractor
+                .next_page_url(&Document::from(html), &url, page)
+                .map(|url| url.to_string())
+        };
+        assert_eq!(
+            next(
+                r#"<html><head><link rel="next" href="/politics?page=2"></head></html>"#,
+                1
+            )
+            .as_deref(),
+            Some("https://extrablatt.com/politics?page=2")
+        );
+        assert_eq!(
+            next(
+                r#"<html><body><div class="pagination"><a href="/politics">1</a>
+                <a href="/politics/page/2">2</a><a href="/politics/page/3">3</a></div></body></html>"#,
+                2
+            )
+            .as_deref(),
+            Some("https://extrablatt.com/politics/page/3")
+        );
+        assert_eq!(
+            next(
+                r#"<html><body><a class="btn load-more" href="?offset=20">Show me more</a></body></html>"#,
+                1
+            )
+            .as_deref(),
+            Some("https://extrablatt.com/politics?offset=20")
+        );
+        assert_eq!(
+            next(
+                r##"<html><body><a rel="next" href="https://other.com/politics?page=2">Next</a>
+                <a href="#top">Next</a><a href="/politics">Next</a></body></html>"##,
+                1
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn og_image_dimensions() {
+        let page = |og_width: u32, og_height: u32| {
+            Document::from(
+                format!(
+                    r#"<html><head>
+                    <meta property="og:image" content="/share-card.png">
+                    <meta property="og:image:width" content="{}">
+                    <meta property="og:image:height" content="{}">
+                    </head><body><div itemprop="articleBody">
+                    <img src="/thumb.png" width="320" height="180">
+                    <img src="/icon.png" width="16" height="16">
+                    <p>The text of the article.</p>
+                    </div></body></html>"#,
+                    og_width, og_height
+                )
+                .as_str(),
+            )
+        };
+        let base_url = Url::parse("https://extrablatt.com/").unwrap();
+
+        let doc = page(1200, 630);
+        assert_eq!(
+            DefaultExtractor.og_image_dimensions(&doc),
+            (Some(1200), Some(630))
+        );
+        let content = DefaultExtractor.article_content(&doc, Some(&base_url), None);
+        assert_eq!(
+            content.top_image.as_ref().map(Url::as_str),
+            Some("https://extrablatt.com/share-card.png")
+        );
+        let details = content.top_image_details.unwrap();
+        assert_eq!((details.width, details.height), (Some(1200), Some(630)));
+        assert_eq!(details.declared_area(), Some(756_000));
+
+        let doc = page(200, 100);
+        let content = DefaultExtractor.article_content(&doc, Some(&base_url), None);
+        assert_eq!(
+            content.top_image.as_ref().map(Url::as_str),
+            Some("https://extrablatt.com/thumb.png")
+        );
+    }
+
+    #[test]
+    fn section_resolution() {
+        let doc = Document::from(
+            r#"<html><head>
+            <meta property="article:section" content="Politics">
+            <link rel="canonical" href="https://extrablatt.com/sports/some-article">
+            </head></html>"#,
+        );
+        let content = DefaultExtractor.article_content(&doc, None, None);
+        assert_eq!(content.section.as_deref(), Some("Politics"));
+        assert_eq!(content.section_source, Some(SectionSource::Meta));
+        assert!(content.sections.is_empty());
+
+        let doc = Document::from(
+            r#"<html><head>
+            <link rel="canonical" href="https://extrablatt.com/sports/some-article">
+            </head></html>"#,
+        );
+        let content = DefaultExtractor.article_content(&doc, None, None);
+        assert_eq!(content.section.as_deref(), Some("sports"));
+        assert_eq!(content.section_source, Some(SectionSource::Url));
+
+        let doc = Document::from(
+            r#"<html><head>
+            <meta property="article:section" content="Politics">
+            <script type="application/ld+json">
+            {"@type": "NewsArticle", "articleSection": ["World", "Europe"]}
+            </script>
+            </head><body>
+            <nav aria-label="breadcrumb"><a href="/">Home</a><a href="/news">News</a></nav>
+            </body></html>"#,
+        );
+        let content = DefaultExtractor.article_content(&doc, None, None);
+        assert_eq!(content.section.as_deref(), Some("World"));
+        assert_eq!(content.section_source, Some(SectionSource::JsonLd));
+        assert_eq!(content.sections, vec!["World", "Europe"]);
+        assert_eq!(
+            DefaultExtractor
+                .breadcrumb_section(&doc, &jsonld::objects(&doc))
+                .as_deref(),
+            Some("News")
+        );
+    }
+
+    #[test]
+    fn json_ld_section_forms() {
+        let sections = |json: &str| {
+            let doc = Document::from(
+                format!(
+                    r#"<html><head><script type="application/ld+json">{}</script></head></html>"#,
+                    json
+                )
+                .as_str(),
+            );
+            let content = DefaultExtractor.article_content(&doc, None, None);
+            (content.section, content.sections)
+        };
+
+        assert_eq!(
+            sections(r#"{"@type": "NewsArticle", "articleSection": "World"}"#),
+            (Some("World".to_string()), vec!["World".to_string()])
+        );
+        assert_eq!(
+            sections(r#"{"@type": "NewsArticle", "articleSection": ["World", " ", "Europe"]}"#),
+            (
+                Some("World".to_string()),
+                vec!["World".to_string(), "Europe".to_string()]
+            )
+        );
+        assert_eq!(
+            sections(
+                r#"[{"@type": "WebPage", "articleSection": "Home"},
+                {"@type": ["NewsArticle", "Article"], "articleSection": ["Politics", "Home"]}]"#
+            ),
+            (
+                Some("Politics".to_string()),
+                vec!["Politics".to_string(), "Home".to_string()]
+            )
+        );
+    }
+
+    #[test]
+    fn count_link_stats() {
+        let doc = Document::from(
+            r#"<html><body><div itemprop="articleBody">
+            <p>See <a href="/politics/other-story">our other story</a> and
+            <a href="https://www.extrablatt.com/about">about us</a>.</p>
+            <p>Read <a href="https://example.org/report">the report</a>.</p>
+            <p>Buy <a href="https://www.amazon.com/dp/B000?tag=extrablatt-20">this book</a>
+            or <a href="https://www.amazon.com/dp/B001">that one</a>.</p>
+            <p><a href="mailto:editor@extrablatt.com">Contact</a></p>
+            </div></body></html>"#,
+        );
+        let base_url = Url::parse("https://extrablatt.com/").unwrap();
+        let stats = LinkStats {
+            internal: 2,
+            outbound: 3,
+            affiliate: 1,
+        };
+        let content = DefaultExtractor.article_content(&doc, Some(&base_url), None);
+        assert_eq!(content.link_stats, stats);
+        let node = DefaultExtractor.article_node(&doc, Language::English);
+        assert_eq!(
+            DefaultExtractor.link_stats(&doc, node.as_ref(), Some(&base_url)),
+            stats
+        );
+
+        struct NoLinkStats;
+
+        impl Extractor for NoLinkStats {
+            fn link_stats(
+                &self,
+                _: &Document,
+                _: Option<&ArticleTextNode>,
+                _: Option<&Url>,
+            ) -> LinkStats {
+                LinkStats::default()
+            }
+        }
+        let content = NoLinkStats.article_content(&doc, Some(&base_url), None);
+        assert_eq!(content.link_stats, LinkStats::default());
+        assert!((stats.affiliate_ratio() - 0.2).abs() < f64::EPSILON);
+
+        assert!(is_affiliate_link(
+            &Url::parse("https://shop.example.com/aff/123").unwrap()
+        ));
+        assert!(is_affiliate_link(
+            &Url::parse("https://www.ebay.com/itm/123?campid=5338").unwrap()
+        ));
+        assert!(!is_affiliate_link(
+            &Url::parse("https://github.com/mattsse/extrablatt/tree/master?ref=main").unwrap()
+        ));
+        assert!(!is_affiliate_link(
+            &Url::parse("https://shop.example.com/item?ref=extrablatt").unwrap()
+        ));
+        assert!(!is_affiliate_link(
+            &Url::parse("https://shop.example.com/item").unwrap()
+        ));
+    }
+
+    #[test]
+    fn html_tag_language() {
+        let doc = Document::from(r#"<html lang="de-DE"><head></head><body></body></html>"#);
+        assert_eq!(DefaultExtractor.meta_language(&doc), Some(Language::German));
+    }
+
+    #[test]
+    fn detect_query_articles() {
+        let base_url = Url::parse("https://extrablatt.com").unwrap();
+        let is_article = |url: &str| {
+            let article = ArticleUrl::new(Url::parse(url).unwrap());
+            DefaultExtractor::is_query_article(&article, &base_url, &ARTICLE_ID_QUERY_PARAMS)
+        };
+        assert!(is_article("https://extrablatt.com/?p=12345"));
+        assert!(is_article(
+            "https://extrablatt.com/index.php?storyId=some-longer-title-with-more-dashes"
+        ));
+        assert!(!is_article("https://extrablatt.com/?utm_source=x"));
+        assert!(!is_article("https://extrablatt.com/contact?id=12345"));
+        assert!(!is_article("https://example.com/?p=12345"));
+    }
+
     #[test]
     fn detect_articles() {
         macro_rules! assert_articles {
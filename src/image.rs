@@ -1,7 +1,51 @@
+use bytes::Bytes;
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
 use url::Url;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
 pub struct Image {
     pub url: Url,
     pub caption: Option<String>,
+    /// The alternative text, empty for decorative images.
+    pub alt: Option<String>,
+    /// The advisory `title` attribute.
+    pub title: Option<String>,
+    /// The `loading` attribute, like `lazy`.
+    pub loading: Option<String>,
+    /// The declared width in pixels.
+    pub width: Option<u32>,
+    /// The declared height in pixels.
+    pub height: Option<u32>,
+    /// The downloaded image, see [`crate::article::ArticleContent::download_media`].
+    pub bytes: Option<Bytes>,
+}
+
+impl Image {
+    pub fn new(url: Url) -> Self {
+        Self {
+            url,
+            caption: None,
+            alt: None,
+            title: None,
+            loading: None,
+            width: None,
+            height: None,
+            bytes: None,
+        }
+    }
+
+    /// The declared area in pixels, if both dimensions are declared.
+    pub fn declared_area(&self) -> Option<u64> {
+        Some(u64::from(self.width?) * u64::from(self.height?))
+    }
+
+    /// Whether the image lacks an alternative text.
+    pub fn is_missing_alt(&self) -> bool {
+        self.alt
+            .as_deref()
+            .map(|alt| alt.trim().is_empty())
+            .unwrap_or(true)
+    }
 }
@@ -0,0 +1,90 @@
+use select::document::Document;
+use select::predicate::{Attr, Name, Predicate};
+use serde_json::Value;
+
+/// Parses the content of all `<script type="application/ld+json">` nodes.
+///
+/// Top level arrays and `@graph` containers are flattened, so that every
+/// returned item is a single JSON-LD object. Scripts that fail to parse are
+/// skipped.
+pub fn objects(doc: &Document) -> Vec<Value> {
+    fn flatten(value: Value, objects: &mut Vec<Value>) {
+        match value {
+            Value::Array(items) => {
+                for item in items {
+                    flatten(item, objects);
+                }
+            }
+            Value::Object(mut obj) => {
+                if let Some(graph) = obj.remove("@graph") {
+                    flatten(graph, objects);
+                }
+                if !obj.is_empty() {
+                    objects.push(Value::Object(obj));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut objects = Vec::new();
+    for script in doc.find(Name("script").and(Attr("type", "application/ld+json"))) {
+        if let Ok(value) = serde_json::from_str(script.text().trim()) {
+            flatten(value, &mut objects);
+        }
+    }
+    objects
+}
+
+/// Yields the elements if the value is an array, or the value itself
+/// otherwise.
+pub fn one_or_many(value: &Value) -> impl Iterator<Item = &Value> {
+    match value {
+        Value::Array(items) => items.iter().collect::<Vec<_>>(),
+        Value::Null => Vec::new(),
+        value => vec![value],
+    }
+    .into_iter()
+}
+
+/// All the `@type`s of the object.
+pub fn types(value: &Value) -> impl Iterator<Item = &str> {
+    value
+        .get("@type")
+        .into_iter()
+        .flat_map(one_or_many)
+        .filter_map(Value::as_str)
+}
+
+/// Whether one of the object's `@type`s is `ty`.
+pub fn is_type(value: &Value, ty: &str) -> bool {
+    types(value).any(|t| t.eq_ignore_ascii_case(ty))
+}
+
+/// Whether one of the object's `@type`s is an article, like `NewsArticle` or
+/// `BlogPosting`.
+pub fn is_article(value: &Value) -> bool {
+    types(value).any(|t| t.ends_with("Article") || t == "BlogPosting")
+}
+
+/// The trimmed, non empty string stored under `key`.
+pub fn str_value<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+/// All the strings stored under `key`, whether it's a single string or an
+/// array of strings.
+pub fn str_values<'a>(value: &'a Value, key: &str) -> Vec<&'a str> {
+    value
+        .get(key)
+        .into_iter()
+        .flat_map(one_or_many)
+        .filter_map(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect()
+}
@@ -1,3 +1,4 @@
+use std::collections::{HashMap, HashSet};
 use std::slice::Iter;
 use std::str::FromStr;
 
@@ -167,6 +168,220 @@ impl Language {
         }
     }
 
+    /// This language with additional stopwords, like a site's recurring
+    /// boilerplate terms.
+    pub fn with_extra_stopwords<T: AsRef<str>>(&self, extra: &[T]) -> CustomLanguage {
+        CustomLanguage {
+            language: self.clone(),
+            extra_stopwords: extra
+                .iter()
+                .map(|word| word.as_ref().trim().to_lowercase())
+                .filter(|word| !word.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Detects the language of the `txt`.
+    ///
+    /// Languages with their own script, like Arabic or Greek, are detected by
+    /// the script of most letters. Latin script languages are detected by
+    /// their stopwords among the first [`DETECT_MAX_WORDS`] words, which
+    /// requires the `stopwords` feature and at least [`DETECT_MIN_WORDS`]
+    /// words. Stopwords shared by several languages count less and the score
+    /// is weighted by the size of the language's stopword list. The best
+    /// language must outscore the runner-up by [`DETECT_MIN_MARGIN`],
+    /// otherwise no language is detected.
+    pub fn detect(txt: &str) -> Option<Language> {
+        let letters: Vec<char> = txt.chars().filter(|c| c.is_alphabetic()).collect();
+        if letters.is_empty() {
+            return None;
+        }
+        let is_dominant = |range: std::ops::RangeInclusive<char>| {
+            letters.iter().filter(|c| range.contains(*c)).count() * 2 > letters.len()
+        };
+        if is_dominant('\u{600}'..='\u{6FF}') {
+            // letters that only exist in the persian alphabet
+            return if txt.contains(['پ', 'چ', 'ژ', 'گ', 'ی']) {
+                Some(Language::Persian)
+            } else {
+                Some(Language::Arabic)
+            };
+        }
+        if is_dominant('\u{400}'..='\u{4FF}') {
+            return if txt.contains(['і', 'ї', 'є', 'ґ']) {
+                Some(Language::Ukrainian)
+            } else {
+                Some(Language::Russian)
+            };
+        }
+        for (range, language) in [
+            ('\u{590}'..='\u{5FF}', Language::Hebrew),
+            ('\u{370}'..='\u{3FF}', Language::Greek),
+            ('\u{AC00}'..='\u{D7AF}', Language::Korean),
+            ('\u{4E00}'..='\u{9FFF}', Language::Chinese),
+        ] {
+            if is_dominant(range) {
+                return Some(language);
+            }
+        }
+
+        #[cfg(feature = "stopwords")]
+        {
+            let words: Vec<_> = ArticleTextNodeExtractor::words(txt)
+                .take(DETECT_MAX_WORDS)
+                .map(str::to_lowercase)
+                .collect();
+            if words.len() < DETECT_MIN_WORDS {
+                return None;
+            }
+            let lists: Vec<_> = Language::known_languages()
+                .filter(|language| !NON_LATIN_SCRIPT.contains(language))
+                .filter_map(|language| Some((language, language.stopwords()?)))
+                .collect();
+            let mut scores = vec![0f64; lists.len()];
+            for word in &words {
+                let matches: Vec<_> = lists
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (_, stopwords))| stopwords.contains(&word.as_str()))
+                    .map(|(idx, _)| idx)
+                    .collect();
+                // stopwords shared by several languages tell less
+                for idx in &matches {
+                    scores[*idx] += 1.0 / matches.len() as f64;
+                }
+            }
+            let mut scores: Vec<_> = lists
+                .iter()
+                .zip(scores)
+                .filter(|(_, score)| *score > 0.0)
+                // longer lists match more words of any language
+                .map(|((language, stopwords), score)| {
+                    (*language, score / (stopwords.len().max(2) as f64).ln())
+                })
+                .collect();
+            scores.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+            let (best, score) = scores.first()?;
+            match scores.get(1) {
+                Some((_, runner_up)) if *score < runner_up * DETECT_MIN_MARGIN => None,
+                _ => Some((*best).clone()),
+            }
+        }
+
+        #[cfg(not(feature = "stopwords"))]
+        None
+    }
+
+    /// Splits the `text` into its trimmed sentences.
+    ///
+    /// Lines always end a sentence. A terminator, like `.`, only ends a
+    /// sentence if it is followed by whitespace, unless it's a full width
+    /// terminator like `。`. A `.` doesn't end a sentence after an
+    /// abbreviation of the language, like `Dr.`, an initial or if the next
+    /// word is lowercase, and neither does any other terminator that is
+    /// followed by a lowercase word, like `"Yes!" she said`.
+    pub fn split_sentences<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        let terminators = self.sentence_terminators();
+        let mut sentences = Vec::new();
+        for line in text.lines() {
+            let mut start = 0;
+            let mut chars = line.char_indices().peekable();
+            while let Some((idx, c)) = chars.next() {
+                if !terminators.contains(&c) {
+                    continue;
+                }
+                // repeated terminators and closing quotes belong to the sentence, like `?!"`
+                let mut end = idx + c.len_utf8();
+                while let Some(&(next_idx, next)) = chars.peek() {
+                    if terminators.contains(&next) || SENTENCE_CLOSINGS.contains(&next) {
+                        end = next_idx + next.len_utf8();
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                let next_is_lowercase = || {
+                    line[end..]
+                        .trim_start()
+                        .chars()
+                        .next()
+                        .map(char::is_lowercase)
+                        .unwrap_or_default()
+                };
+                let is_boundary = match chars.peek() {
+                    None => true,
+                    Some(_) if FULL_WIDTH_TERMINATORS.contains(&c) => true,
+                    Some((_, next)) if !next.is_whitespace() => false,
+                    Some(_) if next_is_lowercase() => false,
+                    Some(_) => c != '.' || !self.is_abbreviation(&line[start..idx]),
+                };
+                if is_boundary {
+                    sentences.push(line[start..end].trim());
+                    start = end;
+                }
+            }
+            sentences.push(line[start..].trim());
+        }
+        sentences.retain(|sentence| !sentence.is_empty());
+        sentences
+    }
+
+    /// The characters that end a sentence.
+    fn sentence_terminators(&self) -> &'static [char] {
+        match self {
+            Language::Chinese => &['.', '!', '?', '…', '。', '！', '？'],
+            // the greek question mark is a semicolon
+            Language::Greek => &['.', '!', ';', '\u{37E}', '…'],
+            Language::Arabic | Language::Persian => &['.', '!', '?', '…', '؟'],
+            _ => &['.', '!', '?', '…'],
+        }
+    }
+
+    /// Whether the last word of the `text` is an abbreviation, like `Dr`,
+    /// `U.S` or an initial, that is followed by a `.`.
+    fn is_abbreviation(&self, text: &str) -> bool {
+        let word = text
+            .split_whitespace()
+            .last()
+            .unwrap_or_default()
+            .trim_start_matches(|c: char| !c.is_alphanumeric());
+        let mut chars = word.chars();
+        match (chars.next(), chars.next()) {
+            (None, _) => false,
+            (Some(initial), None) if initial.is_uppercase() => true,
+            // ordinals, like `3. Oktober`
+            _ if *self == Language::German && word.chars().all(|c| c.is_ascii_digit()) => true,
+            _ if word.contains('.') => true,
+            _ => {
+                let abbreviations: &[&str] = match self {
+                    Language::English => &ENGLISH_ABBREVIATIONS,
+                    Language::German => &GERMAN_ABBREVIATIONS,
+                    Language::French => &FRENCH_ABBREVIATIONS,
+                    _ => &[],
+                };
+                let word = word.to_lowercase();
+                abbreviations.contains(&word.as_str())
+            }
+        }
+    }
+
+    /// Checks that the stopwords of every [`Language::known_languages`] are
+    /// available, so that a missing set surfaces at startup instead of
+    /// mid-crawl.
+    ///
+    /// The stopwords are compiled into static arrays, so there is nothing to
+    /// initialize lazily. Returns the first language without stopwords as
+    /// error.
+    #[cfg(feature = "stopwords")]
+    pub fn preload_all_stopwords() -> Result<(), Language> {
+        for language in Language::known_languages() {
+            if language.stopwords().map(<[_]>::is_empty).unwrap_or(true) {
+                return Err(language.clone());
+            }
+        }
+        Ok(())
+    }
+
     #[cfg(feature = "stopwords")]
     /// Get the stopwords for a language.
     pub fn stopwords(&self) -> Option<&[&str]> {
@@ -201,11 +416,66 @@ impl Language {
     }
 }
 
+/// Max. number of words of a text whose stopwords are counted to detect its
+/// language, see [`Language::detect`].
+pub const DETECT_MAX_WORDS: usize = 200;
+
+/// Min. number of words of a text to detect its language by its stopwords,
+/// see [`Language::detect`].
+pub const DETECT_MIN_WORDS: usize = 20;
+
+/// Factor by which the score of the detected language must exceed the score
+/// of the runner-up, see [`Language::detect`].
+pub const DETECT_MIN_MARGIN: f64 = 1.5;
+
+/// Languages that aren't written in latin script.
+#[cfg(feature = "stopwords")]
+const NON_LATIN_SCRIPT: [Language; 8] = [
+    Language::Arabic,
+    Language::Chinese,
+    Language::Greek,
+    Language::Hebrew,
+    Language::Korean,
+    Language::Persian,
+    Language::Russian,
+    Language::Ukrainian,
+];
+
+/// Terminators that end a sentence without trailing whitespace.
+const FULL_WIDTH_TERMINATORS: [char; 3] = ['。', '！', '？'];
+
+/// Closing quotes and brackets that still belong to the sentence before.
+const SENTENCE_CLOSINGS: [char; 9] = ['"', '\'', '”', '’', '»', '«', '」', ')', ']'];
+
+/// Lowercase english abbreviations that are followed by a `.`.
+const ENGLISH_ABBREVIATIONS: [&str; 36] = [
+    "mr", "mrs", "ms", "dr", "prof", "sr", "jr", "st", "vs", "etc", "inc", "ltd", "co", "corp",
+    "gen", "gov", "sen", "rep", "rev", "mt", "no", "fig", "approx", "dept", "jan", "feb", "mar",
+    "apr", "jun", "jul", "aug", "sep", "sept", "oct", "nov", "dec",
+];
+
+/// Lowercase german abbreviations that are followed by a `.`.
+const GERMAN_ABBREVIATIONS: [&str; 24] = [
+    "dr", "prof", "hr", "fr", "nr", "str", "bzw", "ca", "evtl", "ggf", "usw", "vgl", "inkl", "sog",
+    "jh", "geb", "abs", "bspw", "z", "b", "d", "h", "u", "a",
+];
+
+/// Lowercase french abbreviations that are followed by a `.`.
+const FRENCH_ABBREVIATIONS: [&str; 14] = [
+    "m", "mm", "mme", "mlle", "dr", "pr", "st", "ste", "av", "bd", "env", "etc", "cf", "ex",
+];
+
 impl FromStr for Language {
     type Err = Language;
 
+    /// Parses the language name or its ISO 639-1 code.
+    ///
+    /// BCP 47 tags like `de-DE` or `zh-Hant` are matched by their primary
+    /// language subtag, case insensitive.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
+        let lower = s.trim().to_lowercase();
+        let primary = lower.split(['-', '_']).next().unwrap_or_default();
+        match primary {
             "ar" | "arabic" => Ok(Language::Arabic),
             "ru" | "russian" => Ok(Language::Russian),
             "nl" | "dutch" => Ok(Language::Dutch),
@@ -216,7 +486,7 @@ impl FromStr for Language {
             "he" | "hebrew" => Ok(Language::Hebrew),
             "it" | "italian" => Ok(Language::Italian),
             "ko" | "korean" => Ok(Language::Korean),
-            "no" | "norwegian" => Ok(Language::Norwegian),
+            "no" | "nb" | "nn" | "norwegian" => Ok(Language::Norwegian),
             "fa" | "persian" => Ok(Language::Persian),
             "pl" | "polish" => Ok(Language::Polish),
             "pt" | "portuguese" => Ok(Language::Portuguese),
@@ -231,7 +501,7 @@ impl FromStr for Language {
             "tr" | "turkish" => Ok(Language::Turkish),
             "el" | "greek" => Ok(Language::Greek),
             "uk" | "ukrainian" => Ok(Language::Ukrainian),
-            s => Err(Language::Other(s.to_string())),
+            _ => Err(Language::Other(s.to_string())),
         }
     }
 }
@@ -241,3 +511,231 @@ impl Default for Language {
         Language::English
     }
 }
+
+/// A [`Language`] with additional stopwords, see
+/// [`Language::with_extra_stopwords`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CustomLanguage {
+    pub language: Language,
+    /// The additional stopwords in lowercase, matched case insensitive.
+    pub extra_stopwords: HashSet<String>,
+}
+
+impl CustomLanguage {
+    /// Whether the `word` is a stopword of the language or one of the extra
+    /// stopwords.
+    pub fn is_stopword(&self, word: &str) -> bool {
+        #[cfg(feature = "stopwords")]
+        if let Some(stopwords) = self.language.stopwords() {
+            if stopwords.contains(&word) {
+                return true;
+            }
+        }
+        !self.extra_stopwords.is_empty() && self.extra_stopwords.contains(&word.to_lowercase())
+    }
+
+    /// Same as [`Language::stopword_count`], but also counts the extra
+    /// stopwords.
+    pub fn stopword_count(&self, txt: &str) -> Option<WordsStats> {
+        if self.extra_stopwords.is_empty() || cfg!(not(feature = "stopwords")) {
+            return self.language.stopword_count(txt);
+        }
+        let (word_count, stopword_count) = ArticleTextNodeExtractor::words(txt).fold(
+            (0usize, 0usize),
+            |(word_count, stopword_count), word| {
+                (
+                    word_count + 1,
+                    stopword_count + self.is_stopword(word) as usize,
+                )
+            },
+        );
+        Some(WordsStats {
+            word_count,
+            stopword_count,
+        })
+    }
+
+    /// The `max` most frequent words of the `txt` in lowercase, that are not
+    /// stopwords, numbers or shorter than 3 characters.
+    ///
+    /// Words with the same frequency are ordered by their first occurrence.
+    pub fn keywords(&self, txt: &str, max: usize) -> Vec<String> {
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        let mut counts: Vec<(String, usize)> = Vec::new();
+        for word in ArticleTextNodeExtractor::words(txt) {
+            let word = word.to_lowercase();
+            if word.chars().count() < 3
+                || word.chars().all(|c| c.is_numeric())
+                || self.is_stopword(&word)
+            {
+                continue;
+            }
+            if let Some(&idx) = positions.get(&word) {
+                counts[idx].1 += 1;
+            } else {
+                positions.insert(word.clone(), counts.len());
+                counts.push((word, 1));
+            }
+        }
+        // stable sort keeps the order of the first occurrence
+        counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        counts.into_iter().take(max).map(|(word, _)| word).collect()
+    }
+}
+
+impl From<Language> for CustomLanguage {
+    fn from(language: Language) -> Self {
+        Self {
+            language,
+            extra_stopwords: HashSet::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_language_tags() {
+        assert_eq!(Language::from_str("de"), Ok(Language::German));
+        assert_eq!(Language::from_str("de-DE"), Ok(Language::German));
+        assert_eq!(Language::from_str("EN_us"), Ok(Language::English));
+        assert_eq!(Language::from_str("zh-Hant"), Ok(Language::Chinese));
+        assert_eq!(
+            Language::from_str("xx-YY"),
+            Err(Language::Other("xx-YY".to_string()))
+        );
+    }
+
+    #[test]
+    fn detect_language() {
+        assert_eq!(
+            Language::detect("مرحبا بكم في الموقع الإخباري"),
+            Some(Language::Arabic)
+        );
+        assert_eq!(
+            Language::detect("Привет, как дела?"),
+            Some(Language::Russian)
+        );
+        assert_eq!(Language::detect("今天天气很好。"), Some(Language::Chinese));
+        assert_eq!(Language::detect("1234 !?"), None);
+        #[cfg(feature = "stopwords")]
+        {
+            assert_eq!(
+                Language::detect(
+                    "The council approved the budget for all of the schools in the city. \
+                     Prices rose in May as energy costs climbed, data showed on Tuesday, \
+                     and the mayor said that it was too early to tell."
+                ),
+                Some(Language::English)
+            );
+            assert_eq!(
+                Language::detect(
+                    "Der Stadtrat hat den Haushalt für die Schulen in der Stadt beschlossen. \
+                     Die Preise sind im Mai gestiegen, weil die Kosten für Energie höher \
+                     waren als im letzten Jahr."
+                ),
+                Some(Language::German)
+            );
+            assert_eq!(
+                Language::detect(
+                    "Le conseil a approuvé le budget pour toutes les écoles de la ville. \
+                     Les prix ont augmenté en mai, car les coûts de l'énergie étaient plus \
+                     élevés que l'année dernière."
+                ),
+                Some(Language::French)
+            );
+            // too short to tell
+            assert_eq!(
+                Language::detect("Prices rose in May as energy costs climbed, data showed."),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn split_sentences() {
+        assert_eq!(
+            Language::English.split_sentences("Dr. Smith went home."),
+            vec!["Dr. Smith went home."]
+        );
+        assert_eq!(
+            Language::English.split_sentences(
+                "The U.S. economy grew. Did it? \"Yes!\" said John F. Kennedy.\nA heading\nLast one"
+            ),
+            vec![
+                "The U.S. economy grew.",
+                "Did it?",
+                "\"Yes!\" said John F. Kennedy.",
+                "A heading",
+                "Last one"
+            ]
+        );
+        assert_eq!(
+            Language::German
+                .split_sentences("Am 3. Oktober kam Prof. Müller, z. B. mit dem Rad. Es regnete."),
+            vec![
+                "Am 3. Oktober kam Prof. Müller, z. B. mit dem Rad.",
+                "Es regnete."
+            ]
+        );
+        assert_eq!(
+            Language::French.split_sentences("M. Dupont est arrivé. Il pleut."),
+            vec!["M. Dupont est arrivé.", "Il pleut."]
+        );
+        assert_eq!(
+            Language::Chinese.split_sentences("今天天气很好。我们去公园吧！你去吗？"),
+            vec!["今天天气很好。", "我们去公园吧！", "你去吗？"]
+        );
+        assert_eq!(
+            Language::Greek.split_sentences("Τι κάνεις; Καλά είμαι."),
+            vec!["Τι κάνεις;", "Καλά είμαι."]
+        );
+        assert_eq!(
+            Language::English.split_sentences("  \n "),
+            Vec::<&str>::new()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "stopwords")]
+    fn preload_all_stopwords() {
+        assert_eq!(Language::preload_all_stopwords(), Ok(()));
+        for language in Language::known_languages() {
+            let stopword = language
+                .stopwords()
+                .unwrap()
+                .iter()
+                .find(|word| word.chars().all(char::is_alphabetic))
+                .unwrap();
+            assert!(
+                language.stopword_count(stopword).unwrap().stopword_count > 0,
+                "{:?}",
+                language
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "stopwords")]
+    fn extra_stopwords() {
+        let txt = "The Extrablatt newsletter: the newsletter about the city council and the city.";
+        let lang = Language::English.with_extra_stopwords(&["Newsletter"]);
+        assert!(lang.is_stopword("newsletter"));
+        assert!(lang.is_stopword("Newsletter"));
+        assert_eq!(
+            Language::English
+                .stopword_count(txt)
+                .unwrap()
+                .stopword_count
+                + 2,
+            lang.stopword_count(txt).unwrap().stopword_count
+        );
+        assert_eq!(
+            CustomLanguage::from(Language::English).keywords(txt, 2),
+            vec!["newsletter", "city"]
+        );
+        assert_eq!(lang.keywords(txt, 2), vec!["city", "extrablatt"]);
+    }
+}
@@ -119,21 +119,34 @@ pub use select;
 
 pub use crate::article::{Article, PureArticle};
 pub use crate::category::Category;
-pub use crate::extrablatt::{ArticleStream, Config, Extrablatt, ExtrablattBuilder};
-pub use crate::extract::{DefaultExtractor, Extractor};
+pub use crate::extrablatt::{
+    ArticleStream, BrowserProfile, Config, CrawlEvent, Extrablatt, ExtrablattBuilder, MultiSource,
+    RetryConfig,
+};
+pub use crate::extract::{
+    ConfigurableExtractor, DefaultExtractor, DefaultExtractorConfig, Extractor,
+};
 pub use crate::language::Language;
 
 pub mod article;
+pub mod auth;
 pub mod category;
 pub mod clean;
 pub mod date;
+pub mod embed;
 mod error;
 pub mod extrablatt;
 pub mod extract;
 pub mod image;
+pub mod jsonld;
 pub mod language;
+pub mod schema;
+pub mod sitemap;
+pub mod sites;
 #[cfg(feature = "stopwords")]
 mod stopwords;
+#[cfg(any(test, feature = "testutil"))]
+pub mod testutil;
 pub mod text;
 pub mod video;
 
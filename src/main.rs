@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use futures::{stream, StreamExt};
+use futures::StreamExt;
 use structopt::StructOpt;
 use url::Url;
 
@@ -52,7 +52,7 @@ impl App {
     async fn run(self) -> anyhow::Result<()> {
         let (out, articles) = match self {
             App::Site { url, opts } => {
-                let config = opts.as_config();
+                let config = opts.as_config()?;
                 (
                     opts.output,
                     Extrablatt::builder(url)
@@ -67,18 +67,26 @@ impl App {
                         .collect::<Result<Vec<_>, _>>()?,
                 )
             }
-            App::Article { urls, output } => (
-                output,
-                stream::iter(
-                    urls.into_iter()
-                        .map(|url| Article::builder(url).unwrap().get()),
-                )
-                .buffer_unordered(10)
-                .collect::<Vec<_>>()
-                .await
-                .into_iter()
-                .collect::<Result<Vec<_>, _>>()?,
-            ),
+            App::Article { urls, output } => {
+                let requested = urls.len();
+                // the default config has no completeness restrictions
+                let articles = Article::get_all(urls, &Config::default())?
+                    .filter_map(|(url, article)| async move {
+                        match article {
+                            Ok(article) => Some(article),
+                            Err(err) => {
+                                eprintln!("Failed to download {}: {}", url, err);
+                                None
+                            }
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .await;
+                if requested > 0 && articles.is_empty() {
+                    anyhow::bail!("Failed to download any of the {} articles", requested);
+                }
+                (output, articles)
+            }
             App::Category { url, output } => (
                 output,
                 Category::new(url)
@@ -141,7 +149,7 @@ pub struct Opts {
 }
 
 impl Opts {
-    fn as_config(&self) -> Config {
+    fn as_config(&self) -> anyhow::Result<Config> {
         let mut config = Config::builder();
         if let Some(min_word_count) = self.min_word_count {
             config = config.min_word_count(min_word_count);
@@ -159,13 +167,13 @@ impl Opts {
             config = config.max_authors(max_authors);
         }
         if let Some(user_agent) = self.user_agent.clone() {
-            config = config.user_agent(user_agent);
+            config = config.user_agent(user_agent)?;
         }
         if let Some(http_success_only) = self.http_success_only {
             config = config.http_success_only(http_success_only);
         }
 
-        config.build()
+        Ok(config.build())
     }
 }
 
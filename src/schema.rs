@@ -0,0 +1,520 @@
+//! A [JSON Schema](https://json-schema.org) of the serialized
+//! [`PureArticle`], as written by the CLI.
+//!
+//! Fields are serialized in the order they are declared, the `required` lists
+//! of the schema follow that same order.
+
+use serde_json::{json, Value};
+
+use crate::article::PureArticle;
+
+impl PureArticle {
+    /// The JSON Schema (draft 2020-12) of a serialized [`PureArticle`].
+    pub fn json_schema() -> Value {
+        let mut schema = object(&[
+            ("url", string_format("uri")),
+            ("content", reference("ArticleContent")),
+            ("language", reference("Language")),
+        ]);
+        schema["$schema"] = json!("https://json-schema.org/draft/2020-12/schema");
+        schema["title"] = json!("PureArticle");
+        schema["$defs"] = definitions();
+        schema
+    }
+}
+
+fn definitions() -> Value {
+    let date = |variants: &[(&str, Value)]| {
+        json!({
+            "oneOf": variants
+                .iter()
+                .map(|(variant, schema)| object(&[(variant, schema.clone())]))
+                .collect::<Vec<_>>()
+        })
+    };
+
+    json!({
+        "ArticleContent": object(&[
+            ("authors", array(string())),
+            ("author_details", array(reference("Author"))),
+            ("title", nullable(string())),
+            ("publishing_date", nullable(reference("ArticleDate"))),
+            ("keywords", array(string())),
+            ("description", nullable(string())),
+            ("text", nullable(string())),
+            ("text_source", nullable(unit_enum(&["Body", "Description"]))),
+            ("language", nullable(reference("Language"))),
+            ("thumbnail", nullable(string_format("uri"))),
+            ("top_image", nullable(string_format("uri"))),
+            ("top_image_details", nullable(reference("Image"))),
+            ("lead_image", nullable(reference("Image"))),
+            ("references", array(string_format("uri"))),
+            ("quotes", array(string())),
+            ("images", array(reference("Image"))),
+            ("videos", array(reference("Video"))),
+            ("social_embeds", array(reference("SocialEmbed"))),
+            ("syndication", nullable(reference("Syndication"))),
+            ("content_tier", unit_enum(&["Free", "Metered", "Locked", "Unknown"])),
+            ("section", nullable(string())),
+            (
+                "section_source",
+                nullable(unit_enum(&["JsonLd", "Meta", "Breadcrumbs", "Url"])),
+            ),
+            ("sections", array(string())),
+            ("link_stats", reference("LinkStats")),
+            ("structured", nullable(reference("StructuredContent"))),
+            ("comments", array(reference("Comment"))),
+            ("list_items", array(reference("ListItem"))),
+            ("toc", array(tuple(&[string(), nullable(string())]))),
+            ("product", nullable(reference("Product"))),
+            ("geo", nullable(reference("GeoLocation"))),
+            ("language_mismatch", json!({ "type": "boolean" })),
+            ("looks_like_index", json!({ "type": "boolean" })),
+            ("text_truncated", json!({ "type": "boolean" })),
+            ("attributions", array(reference("Attribution"))),
+        ]),
+        "Author": object(&[
+            ("name", string()),
+            ("url", nullable(string_format("uri"))),
+            ("same_as", array(string_format("uri"))),
+            ("job_title", nullable(string())),
+        ]),
+        "ArticleDate": object(&[
+            (
+                "published",
+                date(&[
+                    ("Date", string_format("date")),
+                    ("DateTime", naive_date_time()),
+                ]),
+            ),
+            (
+                "last_updated",
+                nullable(date(&[
+                    ("Date", string_format("date")),
+                    ("DateTime", naive_date_time()),
+                    ("Time", naive_time()),
+                ])),
+            ),
+            ("published_from_modified", json!({ "type": "boolean" })),
+        ]),
+        "Image": object(&[
+            ("url", string_format("uri")),
+            ("caption", nullable(string())),
+            ("alt", nullable(string())),
+            ("title", nullable(string())),
+            ("loading", nullable(string())),
+            ("width", nullable(unsigned())),
+            ("height", nullable(unsigned())),
+            ("bytes", nullable(bytes())),
+        ]),
+        "Video": object(&[
+            ("url", string_format("uri")),
+            ("thumbnail", nullable(string_format("uri"))),
+            ("thumbnail_bytes", nullable(bytes())),
+            ("duration_secs", nullable(unsigned())),
+            ("upload_date", nullable(naive_date_time())),
+            (
+                "provider",
+                nullable(with_other(&["Youtube", "Vimeo", "Dailymotion"])),
+            ),
+        ]),
+        "SocialEmbed": object(&[
+            ("url", string_format("uri")),
+            (
+                "provider",
+                unit_enum(&["Twitter", "Instagram", "TikTok", "Facebook"]),
+            ),
+        ]),
+        "Syndication": object(&[
+            ("source_url", nullable(string_format("uri"))),
+            ("source_org", nullable(string())),
+        ]),
+        "StructuredContent": json!({
+            "oneOf": [
+                object(&[(
+                    "Recipe",
+                    object(&[
+                        ("name", nullable(string())),
+                        ("ingredients", array(string())),
+                        ("steps", array(string())),
+                    ]),
+                )]),
+                object(&[(
+                    "HowTo",
+                    object(&[("name", nullable(string())), ("steps", array(string()))]),
+                )]),
+            ]
+        }),
+        "Comment": object(&[
+            ("author", nullable(string())),
+            ("text", string()),
+            ("date", nullable(naive_date_time())),
+        ]),
+        "ListItem": object(&[
+            ("heading", string()),
+            ("body", string()),
+            ("image", nullable(reference("Image"))),
+        ]),
+        "Product": object(&[
+            ("name", nullable(string())),
+            ("price", nullable(string())),
+            ("currency", nullable(string())),
+            ("availability", nullable(string())),
+        ]),
+        "Attribution": object(&[("role", string()), ("name", string())]),
+        "GeoLocation": object(&[
+            ("lat", nullable(number())),
+            ("lon", nullable(number())),
+            ("name", nullable(string())),
+        ]),
+        "LinkStats": object(&[
+            ("internal", unsigned()),
+            ("outbound", unsigned()),
+            ("affiliate", unsigned()),
+        ]),
+        "Language": with_other(&[
+            "Arabic",
+            "Russian",
+            "Dutch",
+            "German",
+            "English",
+            "Spanish",
+            "French",
+            "Hebrew",
+            "Italian",
+            "Korean",
+            "Norwegian",
+            "Persian",
+            "Polish",
+            "Portuguese",
+            "Swedish",
+            "Hungarian",
+            "Finnish",
+            "Danish",
+            "Chinese",
+            "Indonesian",
+            "Vietnamese",
+            "Swahili",
+            "Turkish",
+            "Greek",
+            "Ukrainian",
+        ]),
+    })
+}
+
+/// An object with all `properties` required, in the given order.
+fn object(properties: &[(&str, Value)]) -> Value {
+    json!({
+        "type": "object",
+        "properties": properties
+            .iter()
+            .cloned()
+            .map(|(name, schema)| (name.to_string(), schema))
+            .collect::<serde_json::Map<_, _>>(),
+        "required": properties.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+        "additionalProperties": false,
+    })
+}
+
+fn string() -> Value {
+    json!({ "type": "string" })
+}
+
+fn string_format(format: &str) -> Value {
+    json!({ "type": "string", "format": format })
+}
+
+/// A date and time without offset, like `2020-01-01T12:00:00`, which the
+/// `date-time` format of RFC 3339 rejects.
+fn naive_date_time() -> Value {
+    json!({
+        "type": "string",
+        "pattern": r"^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(\.\d+)?$",
+    })
+}
+
+/// A time of day without offset, like `12:00:00`.
+fn naive_time() -> Value {
+    json!({ "type": "string", "pattern": r"^\d{2}:\d{2}:\d{2}(\.\d+)?$" })
+}
+
+fn unsigned() -> Value {
+    json!({ "type": "integer", "minimum": 0 })
+}
+
+/// Downloaded media, serialized as an array of bytes.
+fn bytes() -> Value {
+    array(json!({ "type": "integer", "minimum": 0, "maximum": 255 }))
+}
+
+fn number() -> Value {
+    json!({ "type": "number" })
+}
+
+fn array(items: Value) -> Value {
+    json!({ "type": "array", "items": items })
+}
+
+/// An array with exactly one item per schema, like a Rust tuple.
+fn tuple(items: &[Value]) -> Value {
+    json!({
+        "type": "array",
+        "prefixItems": items,
+        "minItems": items.len(),
+        "maxItems": items.len(),
+    })
+}
+
+fn reference(name: &str) -> Value {
+    json!({ "$ref": format!("#/$defs/{}", name) })
+}
+
+fn nullable(schema: Value) -> Value {
+    json!({ "anyOf": [schema, { "type": "null" }] })
+}
+
+fn unit_enum(variants: &[&str]) -> Value {
+    json!({ "type": "string", "enum": variants })
+}
+
+/// An enum of unit `variants` and an `Other(String)` variant.
+fn with_other(variants: &[&str]) -> Value {
+    json!({ "oneOf": [unit_enum(variants), object(&[("Other", string())])] })
+}
+
+#[cfg(all(test, feature = "serde0"))]
+mod tests {
+    use super::*;
+    use crate::article::{ArticleContent, Author, LinkStats, StructuredContent};
+    use crate::date::{ArticleDate, Date, Update};
+    use crate::image::Image;
+    use crate::video::Video;
+    use crate::Language;
+    use chrono::NaiveDate;
+    use url::Url;
+
+    /// Validates the subset of JSON Schema used by [`PureArticle::json_schema`].
+    fn validate(value: &Value, schema: &Value, root: &Value, path: &str) -> Result<(), String> {
+        if let Some(reference) = schema["$ref"].as_str() {
+            let name = reference.trim_start_matches("#/$defs/");
+            return validate(value, &root["$defs"][name], root, path);
+        }
+        if let Some(schemas) = schema["anyOf"].as_array() {
+            return if schemas
+                .iter()
+                .any(|s| validate(value, s, root, path).is_ok())
+            {
+                Ok(())
+            } else {
+                Err(format!("{}: no schema of anyOf matches {}", path, value))
+            };
+        }
+        if let Some(schemas) = schema["oneOf"].as_array() {
+            let matches = schemas
+                .iter()
+                .filter(|s| validate(value, s, root, path).is_ok())
+                .count();
+            return if matches == 1 {
+                Ok(())
+            } else {
+                Err(format!(
+                    "{}: {} schemas of oneOf match {}",
+                    path, matches, value
+                ))
+            };
+        }
+        let matches_type = match schema["type"].as_str() {
+            Some("object") => value.is_object(),
+            Some("array") => value.is_array(),
+            Some("string") => value.is_string(),
+            Some("integer") => value.is_u64() || value.is_i64(),
+            Some("number") => value.is_number(),
+            Some("boolean") => value.is_boolean(),
+            Some("null") => value.is_null(),
+            _ => return Err(format!("{}: unsupported schema {}", path, schema)),
+        };
+        if !matches_type {
+            return Err(format!(
+                "{}: expected {}, got {}",
+                path, schema["type"], value
+            ));
+        }
+        if let Some(pattern) = schema["pattern"].as_str() {
+            let matches = value
+                .as_str()
+                .map(|value| regex::Regex::new(pattern).unwrap().is_match(value))
+                .unwrap_or_default();
+            if !matches {
+                return Err(format!("{}: {} doesn't match {}", path, value, pattern));
+            }
+        }
+        if let Some(variants) = schema["enum"].as_array() {
+            if !variants.contains(value) {
+                return Err(format!("{}: {} is not a variant", path, value));
+            }
+        }
+        if let Some(min) = schema["minimum"].as_i64() {
+            if value.as_i64().map(|v| v < min).unwrap_or_default() {
+                return Err(format!("{}: {} is below {}", path, value, min));
+            }
+        }
+        if let Some(items) = value.as_array() {
+            if let Some(prefix) = schema["prefixItems"].as_array() {
+                if items.len() != prefix.len() {
+                    return Err(format!("{}: expected {} items", path, prefix.len()));
+                }
+            }
+            for (idx, item) in items.iter().enumerate() {
+                let item_schema = match schema["prefixItems"].as_array() {
+                    Some(prefix) => &prefix[idx],
+                    None => &schema["items"],
+                };
+                validate(item, item_schema, root, &format!("{}[{}]", path, idx))?;
+            }
+        }
+        if let Some(obj) = value.as_object() {
+            for required in schema["required"].as_array().into_iter().flatten() {
+                let required = required.as_str().unwrap();
+                if !obj.contains_key(required) {
+                    return Err(format!("{}: missing {}", path, required));
+                }
+            }
+            for (key, item) in obj {
+                let property = &schema["properties"][key];
+                if property.is_null() {
+                    return Err(format!("{}: unexpected property {}", path, key));
+                }
+                validate(item, property, root, &format!("{}.{}", path, key))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn article() -> PureArticle {
+        let url = Url::parse("https://extrablatt.com/politics/article.html").unwrap();
+        let mut author = Author::new("Jane Doe");
+        author.url = Some(Url::parse("https://extrablatt.com/authors/jane").unwrap());
+        let mut image = Image::new(Url::parse("https://extrablatt.com/lead.jpg").unwrap());
+        image.alt = Some("The lead image".to_string());
+        let mut video = Video::new(Url::parse("https://www.youtube.com/embed/abc").unwrap());
+        video.duration_secs = Some(90);
+        video.upload_date =
+            NaiveDate::from_ymd_opt(2020, 5, 1).and_then(|date| date.and_hms_opt(12, 0, 0));
+
+        let content = ArticleContent::builder()
+            .authors(vec!["Jane Doe".into()])
+            .author_details(vec![author])
+            .title("Title".into())
+            .publishing_date(ArticleDate {
+                published: Date::Date(NaiveDate::from_ymd_opt(2020, 5, 1).unwrap()),
+                last_updated: NaiveDate::from_ymd_opt(2020, 5, 2).map(Update::Date),
+                published_from_modified: false,
+            })
+            .text("Some text.".into())
+            .language(Language::Other("Klingon".to_string()))
+            .lead_image(image.clone())
+            .images(vec![image])
+            .videos(vec![video])
+            .link_stats(LinkStats {
+                internal: 2,
+                outbound: 1,
+                affiliate: 0,
+            })
+            .toc(vec![
+                ("Background".to_string(), Some("background".to_string())),
+                ("Summary".to_string(), None),
+            ])
+            .structured(StructuredContent::HowTo {
+                name: Some("How to read".to_string()),
+                steps: vec!["Open the article.".to_string()],
+            })
+            .build();
+
+        PureArticle {
+            url,
+            content: content.into_owned(),
+            language: Language::English,
+        }
+    }
+
+    #[test]
+    fn serialized_article_matches_schema() {
+        let schema = PureArticle::json_schema();
+        let value = serde_json::to_value(article()).unwrap();
+        validate(&value, &schema, &schema, "$").unwrap();
+
+        let empty = serde_json::to_value(&PureArticle {
+            content: ArticleContent::builder().build(),
+            ..article()
+        })
+        .unwrap();
+        validate(&empty, &schema, &schema, "$").unwrap();
+
+        let noon = NaiveDate::from_ymd_opt(2020, 5, 1)
+            .and_then(|date| date.and_hms_opt(12, 0, 0))
+            .unwrap();
+        let timed = serde_json::to_value(&PureArticle {
+            content: ArticleContent::builder()
+                .publishing_date(ArticleDate {
+                    published: Date::DateTime(noon),
+                    last_updated: Some(Update::Time(noon.time())),
+                    published_from_modified: false,
+                })
+                .build(),
+            ..article()
+        })
+        .unwrap();
+        // serialized without an offset
+        assert_eq!(
+            timed["content"]["publishing_date"]["published"]["DateTime"],
+            "2020-05-01T12:00:00"
+        );
+        validate(&timed, &schema, &schema, "$").unwrap();
+
+        let mut invalid = value;
+        invalid["content"]["link_stats"]["internal"] = json!("two");
+        assert!(validate(&invalid, &schema, &schema, "$").is_err());
+    }
+
+    /// Fails when a field is added to or removed from one of the structs
+    /// without updating the schema.
+    #[test]
+    fn schema_properties_match_fields() {
+        let schema = PureArticle::json_schema();
+        let content = serde_json::to_value(&article().content).unwrap();
+        let structs = [
+            ("ArticleContent", &content),
+            ("Author", &content["author_details"][0]),
+            ("Image", &content["images"][0]),
+            ("Video", &content["videos"][0]),
+            ("LinkStats", &content["link_stats"]),
+        ];
+        for (name, value) in structs.iter() {
+            let mut fields: Vec<_> = value.as_object().unwrap().keys().collect();
+            let mut properties: Vec<_> = schema["$defs"][*name]["properties"]
+                .as_object()
+                .unwrap()
+                .keys()
+                .collect();
+            fields.sort();
+            properties.sort();
+            assert_eq!(fields, properties, "fields of {} diverge", name);
+        }
+    }
+
+    #[test]
+    fn stable_field_order() {
+        let schema = PureArticle::json_schema();
+        let json = serde_json::to_string(&article().content).unwrap();
+        let positions: Vec<_> = schema["$defs"]["ArticleContent"]["required"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|field| {
+                json.find(&format!("\"{}\":", field.as_str().unwrap()))
+                    .unwrap()
+            })
+            .collect();
+        assert!(positions.windows(2).all(|w| w[0] < w[1]));
+    }
+}
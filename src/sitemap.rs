@@ -0,0 +1,263 @@
+//! Discovery of articles through [Google News sitemaps](https://developers.google.com/search/docs/crawling-indexing/sitemaps/news-sitemap).
+
+use std::cmp::Reverse;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use lazy_static::lazy_static;
+use regex::Regex;
+use url::Url;
+
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+
+use crate::article::ArticleUrl;
+
+lazy_static! {
+    /// Regex for the `<url>` entries of a sitemap.
+    static ref RE_URL_ENTRY: Regex = element_regex("url");
+    /// Regex for the `<sitemap>` entries of a sitemap index.
+    static ref RE_SITEMAP_ENTRY: Regex = element_regex("sitemap");
+    /// Regex for the opening tag of a sitemap index.
+    static ref RE_SITEMAP_INDEX: Regex =
+        Regex::new(r"(?i)<(?:[\w.-]+:)?sitemapindex[\s/>]").unwrap();
+    /// Regex for the `<loc>` of an entry.
+    static ref RE_LOC: Regex = element_regex("loc");
+    /// Regex for the `<news:title>` of an entry.
+    static ref RE_NEWS_TITLE: Regex = element_regex("news:title");
+    /// Regex for the `<news:publication_date>` of an entry.
+    static ref RE_NEWS_PUBLICATION_DATE: Regex = element_regex("news:publication_date");
+    /// Regex for the `<news:keywords>` of an entry.
+    static ref RE_NEWS_KEYWORDS: Regex = element_regex("news:keywords");
+    /// Regex for `Sitemap:` lines of a `robots.txt`.
+    static ref RE_ROBOTS_SITEMAP: Regex = Regex::new(r"(?im)^\s*sitemap:\s*(\S+)").unwrap();
+}
+
+/// The `news:` metadata of an entry in a Google News sitemap.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct NewsMetadata {
+    /// The `<news:title>`.
+    pub title: Option<String>,
+    /// The `<news:publication_date>`, in UTC.
+    pub publication_date: Option<NaiveDateTime>,
+    /// The comma separated `<news:keywords>`.
+    pub keywords: Vec<String>,
+}
+
+/// Parses the `<url>` entries of a Google News sitemap, most recent first.
+///
+/// The `<news:title>` is also used as title of the [`ArticleUrl`]. Entries
+/// without a valid `<loc>` are skipped.
+pub fn parse_news_sitemap(xml: &str, base_url: Option<&Url>) -> Vec<(ArticleUrl, NewsMetadata)> {
+    let options = Url::options().base_url(base_url);
+    let mut entries: Vec<_> = RE_URL_ENTRY
+        .captures_iter(xml)
+        .filter_map(|entry| {
+            let entry = &entry[1];
+            let url = options.parse(&element_text(entry, &RE_LOC)?).ok()?;
+            let metadata = NewsMetadata {
+                title: element_text(entry, &RE_NEWS_TITLE),
+                publication_date: element_text(entry, &RE_NEWS_PUBLICATION_DATE)
+                    .and_then(|date| parse_publication_date(&date)),
+                keywords: element_text(entry, &RE_NEWS_KEYWORDS)
+                    .map(|keywords| {
+                        keywords
+                            .split(',')
+                            .map(str::trim)
+                            .filter(|keyword| !keyword.is_empty())
+                            .map(str::to_string)
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            };
+            Some((
+                ArticleUrl::new_with_title(url, metadata.title.as_ref()),
+                metadata,
+            ))
+        })
+        .collect();
+    entries.sort_by_key(|(_, metadata)| Reverse(metadata.publication_date));
+    entries
+}
+
+/// Whether the `xml` is a sitemap index rather than a sitemap.
+pub fn is_sitemap_index(xml: &str) -> bool {
+    RE_SITEMAP_INDEX.is_match(xml)
+}
+
+/// The `<loc>` of all `<sitemap>` entries of a sitemap index.
+pub fn sitemap_index_urls(xml: &str, base_url: Option<&Url>) -> Vec<Url> {
+    let options = Url::options().base_url(base_url);
+    RE_SITEMAP_ENTRY
+        .captures_iter(xml)
+        .filter_map(|entry| element_text(&entry[1], &RE_LOC))
+        .filter_map(|loc| options.parse(&loc).ok())
+        .collect()
+}
+
+/// The urls of all `Sitemap:` lines of a `robots.txt`.
+pub fn robots_sitemap_urls(robots: &str, base_url: Option<&Url>) -> Vec<Url> {
+    let options = Url::options().base_url(base_url);
+    RE_ROBOTS_SITEMAP
+        .captures_iter(robots)
+        .filter_map(|line| options.parse(&line[1]).ok())
+        .collect()
+}
+
+/// Parses a W3C date, like `2020-06-01T12:00:00+02:00` or `2020-06-01`.
+fn parse_publication_date(date: &str) -> Option<NaiveDateTime> {
+    DateTime::parse_from_rfc3339(date)
+        .map(|date| date.naive_utc())
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .ok()?
+                .and_hms_opt(0, 0, 0)
+        })
+}
+
+/// A regex for the content of `<name>` elements, which may have attributes.
+///
+/// Unless the `name` is qualified, like `news:title`, the element may also
+/// have any namespace prefix, like `<ns:loc>`.
+fn element_regex(name: &str) -> Regex {
+    let name = if name.contains(':') {
+        regex::escape(name)
+    } else {
+        format!(r"(?:[\w.-]+:)?{}", regex::escape(name))
+    };
+    Regex::new(&format!(r"(?is)<{0}(?:\s[^>]*)?>(.*?)</{0}\s*>", name)).unwrap()
+}
+
+/// The unescaped, trimmed text of the first element in the `xml` matched by
+/// the `element` regex, see [`element_regex`].
+fn element_text(xml: &str, element: &Regex) -> Option<String> {
+    let text = element.captures(xml)?.get(1)?.as_str().trim();
+    let text = text
+        .strip_prefix("<![CDATA[")
+        .and_then(|text| text.strip_suffix("]]>"))
+        .map(str::to_string)
+        .unwrap_or_else(|| {
+            text.replace("&lt;", "<")
+                .replace("&gt;", ">")
+                .replace("&quot;", "\"")
+                .replace("&apos;", "'")
+                .replace("&amp;", "&")
+        });
+    let text = text.trim();
+    if text.is_empty() {
+        None
+    } else {
+        Some(text.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn news_sitemap() {
+        let base_url = Url::parse("https://extrablatt.com/").unwrap();
+        let entries = parse_news_sitemap(
+            include_str!("../fixtures/news_sitemap.xml"),
+            Some(&base_url),
+        );
+        let urls: Vec<_> = entries
+            .iter()
+            .map(|(article, _)| article.url.path())
+            .collect();
+        assert_eq!(
+            urls,
+            vec![
+                "/politics/2020/06/03/storm-causes-power-outages-across-the-region",
+                "/politics/2020/06/02/local-team-wins-championship-after-extra-time",
+                "/politics/2020/06/01/city-council-approves-new-budget-for-schools",
+                "/about",
+            ]
+        );
+
+        let (article, metadata) = &entries[0];
+        assert_eq!(
+            article.title.as_deref(),
+            Some("Storm causes power outages across the region")
+        );
+        assert_eq!(
+            metadata.publication_date,
+            NaiveDate::from_ymd_opt(2020, 6, 3).and_then(|date| date.and_hms_opt(6, 30, 0))
+        );
+        assert_eq!(metadata.keywords, vec!["Weather", "Storm", "Region"]);
+
+        let (article, metadata) = &entries[1];
+        assert_eq!(
+            article.title.as_deref(),
+            Some("Local team wins championship & celebrates")
+        );
+        assert_eq!(
+            metadata.publication_date,
+            NaiveDate::from_ymd_opt(2020, 6, 2).and_then(|date| date.and_hms_opt(0, 0, 0))
+        );
+        assert!(metadata.keywords.is_empty());
+
+        assert_eq!(entries[3].1, NewsMetadata::default());
+    }
+
+    #[test]
+    fn sitemap_discovery() {
+        let base_url = Url::parse("https://extrablatt.com/").unwrap();
+        let robots = "User-agent: *\nDisallow: /admin\nSitemap: https://extrablatt.com/sitemap_index.xml\nsitemap: /news-sitemap.xml\n";
+        assert_eq!(
+            robots_sitemap_urls(robots, Some(&base_url))
+                .iter()
+                .map(Url::as_str)
+                .collect::<Vec<_>>(),
+            vec![
+                "https://extrablatt.com/sitemap_index.xml",
+                "https://extrablatt.com/news-sitemap.xml"
+            ]
+        );
+
+        let index = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+                <sitemap><loc>https://extrablatt.com/news-sitemap.xml</loc></sitemap>
+                <sitemap><loc>https://extrablatt.com/archive-2019.xml</loc></sitemap>
+            </sitemapindex>"#;
+        assert!(is_sitemap_index(index));
+        assert_eq!(sitemap_index_urls(index, Some(&base_url)).len(), 2);
+
+        let prefixed = r#"<sm:sitemapindex xmlns:sm="http://www.sitemaps.org/schemas/sitemap/0.9">
+                <sm:sitemap id="news"><sm:loc>https://extrablatt.com/news-sitemap.xml</sm:loc></sm:sitemap>
+            </sm:sitemapindex>"#;
+        assert!(is_sitemap_index(prefixed));
+        assert_eq!(
+            sitemap_index_urls(prefixed, Some(&base_url))
+                .iter()
+                .map(Url::as_str)
+                .collect::<Vec<_>>(),
+            vec!["https://extrablatt.com/news-sitemap.xml"]
+        );
+        assert!(!is_sitemap_index(include_str!(
+            "../fixtures/news_sitemap.xml"
+        )));
+    }
+
+    #[test]
+    fn prefixed_news_sitemap() {
+        let xml = r#"<sm:urlset xmlns:sm="http://www.sitemaps.org/schemas/sitemap/0.9"
+                xmlns:news="http://www.google.com/schemas/sitemap-news/0.9">
+            <sm:url>
+                <sm:loc>https://extrablatt.com/politics/2020/06/01/budget</sm:loc>
+                <news:news>
+                    <news:title xml:lang="en">Budget approved</news:title>
+                </news:news>
+            </sm:url>
+            </sm:urlset>"#;
+        let entries = parse_news_sitemap(xml, None);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            entries[0].0.url.as_str(),
+            "https://extrablatt.com/politics/2020/06/01/budget"
+        );
+        assert_eq!(entries[0].1.title.as_deref(), Some("Budget approved"));
+    }
+}
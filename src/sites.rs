@@ -0,0 +1,114 @@
+//! Tuned configurations for well known news sites.
+
+use anyhow::Result;
+use url::Url;
+
+use crate::extrablatt::{Config, ConfigBuilder};
+
+/// A user agent of a common desktop browser, for sites that block unknown
+/// clients.
+pub const BROWSER_USER_AGENT: &str =
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:78.0) Gecko/20100101 Firefox/78.0";
+
+/// A site that requires a tuned [`Config`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KnownSite {
+    /// The domain of the site, which also matches all of its subdomains.
+    pub domain: &'static str,
+    /// The user agent to send instead of the default one.
+    pub user_agent: Option<&'static str>,
+    /// Whether to only accept 2XX responses.
+    pub http_success_only: Option<bool>,
+}
+
+/// All bundled site profiles.
+pub const KNOWN_SITES: &[KnownSite] = &[
+    KnownSite {
+        domain: "bloomberg.com",
+        user_agent: Some(BROWSER_USER_AGENT),
+        http_success_only: Some(false),
+    },
+    KnownSite {
+        domain: "nytimes.com",
+        user_agent: Some(BROWSER_USER_AGENT),
+        http_success_only: None,
+    },
+    KnownSite {
+        domain: "reuters.com",
+        user_agent: Some(BROWSER_USER_AGENT),
+        http_success_only: None,
+    },
+    KnownSite {
+        domain: "washingtonpost.com",
+        user_agent: Some(BROWSER_USER_AGENT),
+        http_success_only: Some(false),
+    },
+    KnownSite {
+        domain: "wsj.com",
+        user_agent: Some(BROWSER_USER_AGENT),
+        http_success_only: Some(false),
+    },
+];
+
+impl KnownSite {
+    /// The profile for the host of the `url`, if there is one.
+    pub fn find(url: &Url) -> Option<&'static KnownSite> {
+        let host = url.host_str()?.to_lowercase();
+        KNOWN_SITES.iter().find(|site| {
+            host == site.domain
+                || host
+                    .strip_suffix(site.domain)
+                    .map(|sub| sub.ends_with('.'))
+                    .unwrap_or_default()
+        })
+    }
+
+    /// A [`ConfigBuilder`] with the tuned settings applied.
+    ///
+    /// Fails if the `user_agent` isn't a valid header value, see
+    /// [`ConfigBuilder::user_agent`].
+    pub fn config_builder(&self) -> Result<ConfigBuilder> {
+        let mut builder = Config::builder();
+        if let Some(user_agent) = self.user_agent {
+            builder = builder.user_agent(user_agent)?;
+        }
+        if let Some(http_success_only) = self.http_success_only {
+            builder = builder.http_success_only(http_success_only);
+        }
+        Ok(builder)
+    }
+
+    /// The tuned [`Config`] for this site.
+    pub fn config(&self) -> Result<Config> {
+        Ok(self.config_builder()?.build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_known_site() {
+        let site =
+            KnownSite::find(&Url::parse("https://www.nytimes.com/section/world").unwrap()).unwrap();
+        assert_eq!(site.domain, "nytimes.com");
+
+        assert!(KnownSite::find(&Url::parse("https://notnytimes.com").unwrap()).is_none());
+        assert!(KnownSite::find(&Url::parse("https://extrablatt.com").unwrap()).is_none());
+    }
+
+    #[test]
+    fn known_site_configs_are_valid() {
+        for site in KNOWN_SITES {
+            assert!(site.config().is_ok(), "{}", site.domain);
+        }
+
+        let site = KnownSite {
+            domain: "extrablatt.com",
+            user_agent: Some("extrablatt\n"),
+            http_success_only: None,
+        };
+        assert!(site.config().is_err());
+    }
+}
@@ -0,0 +1,239 @@
+//! Helpers to test extractors against saved html instead of the network.
+//!
+//! Available in the crate's own tests and with the `testutil` feature.
+
+use select::document::Document;
+use url::Url;
+
+use crate::article::ArticleContent;
+use crate::extract::{DefaultExtractor, Extractor};
+
+#[cfg(test)]
+pub(crate) use self::mock::{MockResponse, MockServer};
+
+/// Parses the `html` into a [`Document`].
+pub fn build_document(html: &str) -> Document {
+    Document::from(html)
+}
+
+/// Extracts the [`ArticleContent`] of the `html` using the
+/// [`DefaultExtractor`], as if it was downloaded from the `base_url`.
+///
+/// # Panics
+///
+/// If `base_url` is not a valid url.
+pub fn extract_from_html(html: &str, base_url: &str) -> ArticleContent<'static> {
+    extract_from_html_with(&DefaultExtractor, html, base_url)
+}
+
+/// Extracts the [`ArticleContent`] of the `html` using the `extractor`, as
+/// if it was downloaded from the `base_url`.
+///
+/// # Panics
+///
+/// If `base_url` is not a valid url.
+pub fn extract_from_html_with<TExtractor: Extractor>(
+    extractor: &TExtractor,
+    html: &str,
+    base_url: &str,
+) -> ArticleContent<'static> {
+    let base_url = Url::parse(base_url).expect("Invalid base url");
+    let doc = build_document(html);
+    extractor
+        .article_content(&doc, Some(&base_url), None)
+        .into_owned()
+}
+
+#[cfg(test)]
+mod mock {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::{SocketAddr, TcpListener, TcpStream};
+    use std::sync::Arc;
+    use std::thread;
+
+    use url::Url;
+
+    /// A request received by the [`MockServer`].
+    #[derive(Debug, Clone)]
+    pub(crate) struct MockRequest {
+        pub path: String,
+        pub headers: Vec<(String, String)>,
+        pub body: String,
+    }
+
+    impl MockRequest {
+        /// The value of the first header with the `name`, ignoring case.
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v.as_str())
+        }
+    }
+
+    /// The response the [`MockServer`] answers a request with.
+    #[derive(Debug, Clone)]
+    pub(crate) struct MockResponse {
+        pub status: u16,
+        pub headers: Vec<(String, String)>,
+        pub body: Vec<u8>,
+    }
+
+    impl MockResponse {
+        /// A `200 OK` response with the `body`.
+        pub fn ok<T: Into<Vec<u8>>>(body: T) -> Self {
+            Self {
+                status: 200,
+                headers: Vec::new(),
+                body: body.into(),
+            }
+        }
+
+        /// An empty response with the `status`.
+        pub fn status(status: u16) -> Self {
+            Self {
+                status,
+                headers: Vec::new(),
+                body: Vec::new(),
+            }
+        }
+    }
+
+    /// A minimal HTTP/1.1 server on localhost that answers every request using a
+    /// handler function, one connection at a time per thread.
+    pub(crate) struct MockServer {
+        addr: SocketAddr,
+    }
+
+    impl MockServer {
+        pub fn start<F>(handler: F) -> Self
+        where
+            F: Fn(&MockRequest) -> MockResponse + Send + Sync + 'static,
+        {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let addr = listener.local_addr().unwrap();
+            let handler = Arc::new(handler);
+            thread::spawn(move || {
+                for stream in listener.incoming().flatten() {
+                    let handler = Arc::clone(&handler);
+                    thread::spawn(move || {
+                        let _ = MockServer::handle(stream, &*handler);
+                    });
+                }
+            });
+            Self { addr }
+        }
+
+        /// The absolute url for the `path` on this server.
+        pub fn url(&self, path: &str) -> Url {
+            Url::parse(&format!("http://{}{}", self.addr, path)).unwrap()
+        }
+
+        fn handle<F>(stream: TcpStream, handler: &F) -> std::io::Result<()>
+        where
+            F: Fn(&MockRequest) -> MockResponse,
+        {
+            let mut reader = BufReader::new(stream.try_clone()?);
+            let mut line = String::new();
+            reader.read_line(&mut line)?;
+            let mut parts = line.split_whitespace();
+            let _method = parts.next();
+            let path = parts.next().unwrap_or_default().to_string();
+
+            let mut headers = Vec::new();
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+                    break;
+                }
+                if let Some(idx) = line.find(':') {
+                    headers.push((
+                        line[..idx].trim().to_string(),
+                        line[idx + 1..].trim().to_string(),
+                    ));
+                }
+            }
+
+            let mut request = MockRequest {
+                path,
+                headers,
+                body: String::new(),
+            };
+            let len = request
+                .header("content-length")
+                .and_then(|len| len.parse().ok())
+                .unwrap_or(0usize);
+            let mut body = vec![0; len];
+            reader.read_exact(&mut body)?;
+            request.body = String::from_utf8_lossy(&body).into_owned();
+
+            let response = handler(&request);
+            let mut stream = stream;
+            write!(
+                stream,
+                "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n",
+                response.status,
+                response.body.len()
+            )?;
+            for (key, value) in &response.headers {
+                write!(stream, "{}: {}\r\n", key, value)?;
+            }
+            write!(stream, "\r\n")?;
+            stream.write_all(&response.body)?;
+            stream.flush()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::Date;
+    use chrono::NaiveDate;
+
+    const ARTICLE: &str = include_str!("../fixtures/article.html");
+    const JSON_LD_ARTICLE: &str = include_str!("../fixtures/json_ld_article.html");
+
+    fn published(content: &ArticleContent) -> String {
+        match &content.publishing_date.as_ref().unwrap().published {
+            Date::Date(date) => date.to_string(),
+            Date::DateTime(date) => date.to_string(),
+        }
+    }
+
+    #[test]
+    fn extract_article_fixture() {
+        let content = extract_from_html(ARTICLE, "https://extrablatt.com/");
+        assert_eq!(
+            content.title.as_deref(),
+            Some("Local Team Wins Championship")
+        );
+        assert_eq!(content.authors, vec!["Jane Doe"]);
+        assert_eq!(published(&content), "2020-06-14 18:30:00");
+        assert_eq!(
+            content.top_image.as_ref().map(|url| url.as_str()),
+            Some("https://extrablatt.com/images/championship.jpg")
+        );
+        assert!(content
+            .text
+            .unwrap()
+            .starts_with("The local team won the championship on Sunday"));
+    }
+
+    #[test]
+    fn extract_json_ld_fixture() {
+        let content = extract_from_html(JSON_LD_ARTICLE, "https://extrablatt.de/");
+        assert_eq!(content.title.as_deref(), Some("Neue Brücke eröffnet"));
+        let mut authors = content.authors.clone();
+        authors.sort();
+        assert_eq!(authors, vec!["Erika Musterfrau", "Max Mustermann"]);
+        assert_eq!(
+            published(&content),
+            NaiveDate::from_ymd_opt(2021, 3, 2)
+                .unwrap()
+                .and_hms_opt(9, 15, 0)
+                .unwrap()
+                .to_string()
+        );
+    }
+}
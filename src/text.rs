@@ -2,15 +2,145 @@ use std::collections::{HashMap, HashSet};
 
 use std::ops::Deref;
 
+use lazy_static::lazy_static;
+use regex::Regex;
+
 use select::document::Document;
 use select::node::Node;
 use select::predicate::{Attr, Class, Name, Predicate};
 
 use crate::clean::{DefaultDocumentCleaner, DocumentCleaner};
+use crate::image::Image;
+use crate::language::CustomLanguage;
 use crate::video::VideoNode;
 use crate::Language;
 use url::Url;
 
+lazy_static! {
+    /// Regex for classes of nodes that hold a pull-quote.
+    static ref RE_PULL_QUOTE: Regex = Regex::new(r"(?i)pull-?quote|quote-?pull").unwrap();
+}
+
+/// Attributes that hold the url of an image, lazy loading ones first, because
+/// the `src` of lazy loaded images is usually a placeholder.
+const IMG_SRC_ATTRS: [&str; 4] = ["data-lazy-src", "data-original", "data-src", "src"];
+
+/// The [`Image`] of an `<img>` or `<amp-img>` node with its attributes and
+/// the `<figcaption>` of the surrounding `<figure>` as caption.
+///
+/// Within a `<picture>`, the largest candidate of its `<source srcset>` is
+/// preferred over the fallback `<img>`. Otherwise the largest candidate of
+/// the `srcset` or `data-srcset` is preferred over the [`IMG_SRC_ATTRS`].
+/// Inline `data:` urls are ignored.
+pub(crate) fn img_node_image(node: Node, base_url: Option<&Url>) -> Option<Image> {
+    let options = Url::options().base_url(base_url);
+    let source = picture_source(&node)
+        .or_else(|| {
+            largest_candidate(
+                node.attr("srcset")
+                    .into_iter()
+                    .chain(node.attr("data-srcset")),
+            )
+        })
+        .and_then(|(src, width)| options.parse(src).ok().map(|url| (url, width)));
+    let (url, source_width) = match source {
+        Some(source) => source,
+        None => IMG_SRC_ATTRS
+            .iter()
+            .filter_map(|attr| node.attr(attr).map(str::trim))
+            .filter(|src| !src.is_empty() && !src.starts_with("data:"))
+            .find_map(|src| options.parse(src).ok())
+            .map(|url| (url, None))?,
+    };
+    let mut width = declared_dimension(&node, "width");
+    let mut height = declared_dimension(&node, "height");
+    if let Some(source_width) = source_width {
+        // keep the aspect ratio of the fallback image
+        height = match (width, height) {
+            (Some(w), Some(h)) if w > 0 => {
+                Some((u64::from(h) * u64::from(source_width) / u64::from(w)) as u32)
+            }
+            _ => None,
+        };
+        width = Some(source_width);
+    }
+    let caption = std::iter::successors(node.parent(), |n| n.parent())
+        .take(3)
+        .find(|n| Name("figure").matches(n))
+        .and_then(|figure| figure.find(Name("figcaption")).next())
+        .map(|caption| caption.text().trim().to_string())
+        .filter(|caption| !caption.is_empty());
+    let attr = |name: &str| node.attr(name).map(|value| value.trim().to_string());
+    Some(Image {
+        url,
+        caption,
+        alt: attr("alt"),
+        title: attr("title"),
+        loading: attr("loading"),
+        width,
+        height,
+        bytes: None,
+    })
+}
+
+/// The candidate with the largest descriptor among the `<source srcset>` of
+/// the `<picture>` that contains the `img`, and its width if the descriptor
+/// is a width, like `1200` for `hero.jpg 1200w`.
+fn picture_source<'a>(img: &Node<'a>) -> Option<(&'a str, Option<u32>)> {
+    let picture = img.parent().filter(|n| Name("picture").matches(n))?;
+    largest_candidate(
+        picture
+            .children()
+            .filter(|n| Name("source").matches(n))
+            .filter_map(|source| source.attr("srcset")),
+    )
+}
+
+/// The candidate with the largest descriptor of all the `srcsets`, and its
+/// width if the descriptor is a width.
+fn largest_candidate<'a>(srcsets: impl Iterator<Item = &'a str>) -> Option<(&'a str, Option<u32>)> {
+    srcsets
+        .flat_map(|srcset| srcset.split(','))
+        .filter_map(|candidate| {
+            let mut parts = candidate.split_whitespace();
+            let url = parts.next().filter(|url| !url.starts_with("data:"))?;
+            let descriptor = parts.next().unwrap_or("1x");
+            let width = descriptor
+                .strip_suffix('w')
+                .and_then(|w| w.parse::<u32>().ok());
+            let density = descriptor
+                .strip_suffix('x')
+                .and_then(|x| x.parse::<f64>().ok())
+                .map(|x| (x * 100.0) as u32);
+            if width.is_none() && density.is_none() {
+                return None;
+            }
+            Some((url, width, density))
+        })
+        .max_by_key(|(_, width, density)| (width.unwrap_or_default(), density.unwrap_or_default()))
+        .map(|(url, width, _)| (url, width))
+}
+
+/// The images of the `<img>`s within the `noscript` node, whose content is
+/// parsed as raw text.
+fn noscript_images(noscript: Node, base_url: Option<&Url>) -> Vec<Image> {
+    let html = noscript.text();
+    if !html.contains("<img") {
+        return Vec::new();
+    }
+    Document::from(html.as_str())
+        .find(Name("img"))
+        .filter_map(|img| img_node_image(img, base_url))
+        .collect()
+}
+
+/// A `width` or `height` attribute in pixels, like `1200` or `1200px`.
+fn declared_dimension(node: &Node, attr: &str) -> Option<u32> {
+    node.attr(attr)
+        .map(|value| value.trim().trim_end_matches("px"))
+        .and_then(|value| value.parse::<u32>().ok())
+}
+
 /// Attribute key-value combinations to identify the root node for the textual
 /// content of the article
 pub const ARTICLE_BODY_ATTR: &[(&str, &str); 3] = &[
@@ -77,6 +207,15 @@ pub struct ArticleTextNode<'a> {
 }
 
 impl<'a> ArticleTextNode<'a> {
+    /// Minimum number of words of a quote.
+    pub const MIN_QUOTE_WORDS: usize = 3;
+
+    /// Minimum declared width of the lead image.
+    pub const MIN_LEAD_IMAGE_WIDTH: u32 = 300;
+
+    /// Minimum declared height of the lead image.
+    pub const MIN_LEAD_IMAGE_HEIGHT: u32 = 150;
+
     pub fn new(inner: Node<'a>) -> Self {
         Self { inner }
     }
@@ -84,17 +223,56 @@ impl<'a> ArticleTextNode<'a> {
     /// Extract the content from the node, but ignore those that not contain
     /// parts of the article
     pub fn clean_text(&self) -> String {
-        DefaultDocumentCleaner.clean_node_text(self.inner)
+        DefaultDocumentCleaner.clean_node_text_normalized(self.inner)
     }
 
-    /// Extract all of the images of the document.
-    pub fn images(&self, base_url: Option<&Url>) -> Vec<Url> {
-        let options = Url::options().base_url(base_url);
-        self.inner
-            .find(Name("img"))
-            .filter_map(|n| n.attr("href").map(str::trim))
-            .filter_map(|url| options.parse(url).ok())
-            .collect()
+    /// Extract all of the images of the node, deduped by their url.
+    ///
+    /// Besides `<img>`, this includes `<amp-img>` and the fallback `<img>`s
+    /// within `<noscript>` of lazy loaded images.
+    pub fn images(&self, base_url: Option<&Url>) -> Vec<Image> {
+        let mut uniques = HashSet::new();
+        let mut images = Vec::new();
+        for node in self
+            .inner
+            .find(Name("img").or(Name("amp-img")).or(Name("noscript")))
+        {
+            let found = if Name("noscript").matches(&node) {
+                noscript_images(node, base_url)
+            } else {
+                img_node_image(node, base_url).into_iter().collect()
+            };
+            images.extend(
+                found
+                    .into_iter()
+                    .filter(|image| uniques.insert(image.url.clone())),
+            );
+        }
+        images
+    }
+
+    /// The first image in the node that is declared at least
+    /// [`ArticleTextNode::MIN_LEAD_IMAGE_WIDTH`] wide and, if a height is
+    /// declared, at least [`ArticleTextNode::MIN_LEAD_IMAGE_HEIGHT`] high.
+    ///
+    /// The caption is taken from the `<figcaption>` of the surrounding
+    /// `<figure>`.
+    pub fn lead_image(&self, base_url: Option<&Url>) -> Option<Image> {
+        for node in self.inner.find(Name("img")) {
+            match declared_dimension(&node, "width") {
+                Some(width) if width >= Self::MIN_LEAD_IMAGE_WIDTH => {}
+                _ => continue,
+            }
+            if let Some(height) = declared_dimension(&node, "height") {
+                if height < Self::MIN_LEAD_IMAGE_HEIGHT {
+                    continue;
+                }
+            }
+            if let Some(image) = img_node_image(node, base_url) {
+                return Some(image);
+            }
+        }
+        None
     }
 
     /// Extract all the links within the node's descendants
@@ -109,6 +287,38 @@ impl<'a> ArticleTextNode<'a> {
             .collect()
     }
 
+    /// Extract the text of all quotes within the node.
+    ///
+    /// These are `<blockquote>`, `<q>` and pull-quote nodes. Quotes with less
+    /// than [`ArticleTextNode::MIN_QUOTE_WORDS`] words and embedded social
+    /// media posts are ignored.
+    pub fn quotes(&self) -> Vec<String> {
+        let is_quote = |node: &Node| {
+            Name("blockquote").or(Name("q")).matches(node)
+                || node
+                    .attr("class")
+                    .map(|class| RE_PULL_QUOTE.is_match(class))
+                    .unwrap_or_default()
+        };
+        let mut quotes: Vec<String> = Vec::new();
+        for node in self.inner.descendants().filter(|n| is_quote(n)) {
+            if let Some(class) = node.attr("class") {
+                if class.contains("twitter-tweet") || class.contains("instagram-media") {
+                    continue;
+                }
+            }
+            let quote = DefaultDocumentCleaner.clean_node_text_normalized(node);
+            if ArticleTextNodeExtractor::words(&quote).count() < Self::MIN_QUOTE_WORDS {
+                continue;
+            }
+            // skip `<q>`s that are part of an already collected quote
+            if quotes.iter().all(|q| !q.contains(quote.as_str())) {
+                quotes.push(quote);
+            }
+        }
+        quotes
+    }
+
     /// Extract all the nodes that hold video data
     pub fn videos(&self) -> Vec<VideoNode<'a>> {
         let mut videos: Vec<_> = self
@@ -148,6 +358,9 @@ impl ArticleTextNodeExtractor {
 
     pub const MAX_STEPSAWAY_FROM_NODE: usize = 3;
 
+    /// Default max. number of text nodes that are scored.
+    pub const DEFAULT_MAX_NODES_TO_SCORE: usize = 50_000;
+
     pub fn article_body_predicate() -> for<'r, 's> fn(&'r Node<'s>) -> bool {
         |node| {
             for (k, v) in ARTICLE_BODY_ATTR.iter().cloned() {
@@ -160,9 +373,37 @@ impl ArticleTextNodeExtractor {
     }
 
     pub fn calculate_best_node(doc: &Document, lang: Language) -> Option<ArticleTextNode> {
+        Self::calculate_best_node_with_limit(doc, lang, Self::DEFAULT_MAX_NODES_TO_SCORE)
+    }
+
+    /// Same as [`ArticleTextNodeExtractor::calculate_best_node`], but only
+    /// the first `max_nodes` text nodes of the document are scored, so that
+    /// huge documents can't stall the extraction.
+    pub fn calculate_best_node_with_limit(
+        doc: &Document,
+        lang: Language,
+        max_nodes: usize,
+    ) -> Option<ArticleTextNode<'_>> {
+        Self::calculate_best_node_with_stopwords(doc, &lang.into(), max_nodes)
+    }
+
+    /// Same as [`ArticleTextNodeExtractor::calculate_best_node_with_limit`],
+    /// but the extra stopwords of the `lang` are counted as well.
+    pub fn calculate_best_node_with_stopwords<'a>(
+        doc: &'a Document,
+        lang: &CustomLanguage,
+        max_nodes: usize,
+    ) -> Option<ArticleTextNode<'a>> {
         let mut starting_boost = 1.0;
 
         let txt_nodes: Vec<_> = ArticleTextNodeExtractor::nodes_to_check(doc)
+            // nodes with too few words to be scored don't count towards the limit
+            .filter(|node| {
+                node.first_children_text()
+                    .map(|txt| ArticleTextNodeExtractor::words(txt).nth(2).is_some())
+                    .unwrap_or_default()
+            })
+            .take(max_nodes)
             .filter(|n| !ArticleTextNodeExtractor::is_high_link_density(n))
             .filter_map(|node| {
                 if let Some(stats) = node
@@ -186,7 +427,7 @@ impl ArticleTextNodeExtractor {
         for (i, (node, stats)) in txt_nodes.iter().enumerate() {
             let mut boost_score = 0.0;
 
-            if ArticleTextNodeExtractor::is_boostable(node, lang.clone()) {
+            if ArticleTextNodeExtractor::is_boostable(node, lang) {
                 boost_score = (1.0 / starting_boost) * 50.0;
                 starting_boost += 1.0;
             }
@@ -255,7 +496,7 @@ impl ArticleTextNodeExtractor {
     /// should be connected to other paragraphs, at least for the first n
     /// paragraphs so we'll want to make sure that the next sibling is a
     /// paragraph and has at least some substantial weight to it.
-    fn is_boostable(node: &Node, lang: Language) -> bool {
+    fn is_boostable(node: &Node, lang: &CustomLanguage) -> bool {
         let mut steps_away = 0;
         while let Some(sibling) = node.prev().filter(|n| n.is(Name("p"))) {
             if steps_away >= ArticleTextNodeExtractor::MAX_STEPSAWAY_FROM_NODE {
@@ -356,3 +597,121 @@ pub struct WordsStats {
     /// All the stop words.
     pub stopword_count: usize,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picture_sources() {
+        let doc = Document::from(
+            r#"<html><body><figure><picture>
+            <source media="(max-width: 600px)" srcset="/hero-480.jpg 480w, /hero-800.jpg 800w">
+            <source srcset="/hero-1200.jpg 1200w">
+            <img src="/hero-placeholder.jpg" width="600" height="400" alt="The hero">
+            </picture><figcaption>The hero image.</figcaption></figure>
+            <picture><source srcset="/logo.png, /logo@2x.png 2x"><img src="/logo-small.png"></picture>
+            <img src="/plain.jpg">
+            </body></html>"#,
+        );
+        let base_url = Url::parse("https://extrablatt.com/").unwrap();
+        let images: Vec<_> = doc
+            .find(Name("img"))
+            .filter_map(|img| img_node_image(img, Some(&base_url)))
+            .collect();
+
+        assert_eq!(
+            images[0].url.as_str(),
+            "https://extrablatt.com/hero-1200.jpg"
+        );
+        assert_eq!((images[0].width, images[0].height), (Some(1200), Some(800)));
+        assert_eq!(images[0].alt.as_deref(), Some("The hero"));
+        assert_eq!(images[0].caption.as_deref(), Some("The hero image."));
+        assert_eq!(images[1].url.as_str(), "https://extrablatt.com/logo@2x.png");
+        assert_eq!(images[1].width, None);
+        assert_eq!(images[2].url.as_str(), "https://extrablatt.com/plain.jpg");
+    }
+
+    #[test]
+    fn lazy_loaded_images() {
+        let doc = Document::from(
+            r#"<html><body><div id="article">
+            <amp-img src="/amp-hero.jpg" width="1200" height="800" layout="responsive"></amp-img>
+            <img src="data:image/gif;base64,R0lGODlhAQABAAAAACw=" data-lazy-src="/lazy.jpg" alt="Lazy">
+            <img src="/placeholder.jpg" data-original="/original.jpg">
+            <img data-srcset="/wide-640.jpg 640w, /wide-1280.jpg 1280w" src="/wide.jpg" width="640" height="360">
+            <img class="lazyload" data-src="/noscript.jpg">
+            <noscript><img src="/noscript.jpg"></noscript>
+            <noscript><img src="/fallback.jpg" width="300"></noscript>
+            </div></body></html>"#,
+        );
+        let base_url = Url::parse("https://extrablatt.com/").unwrap();
+        let node = ArticleTextNode::new(doc.find(Attr("id", "article")).next().unwrap());
+        let images = node.images(Some(&base_url));
+
+        assert_eq!(
+            images
+                .iter()
+                .map(|image| image.url.path())
+                .collect::<Vec<_>>(),
+            vec![
+                "/amp-hero.jpg",
+                "/lazy.jpg",
+                "/original.jpg",
+                "/wide-1280.jpg",
+                "/noscript.jpg",
+                "/fallback.jpg"
+            ]
+        );
+        assert_eq!((images[0].width, images[0].height), (Some(1200), Some(800)));
+        assert_eq!(images[1].alt.as_deref(), Some("Lazy"));
+        assert_eq!((images[3].width, images[3].height), (Some(1280), Some(720)));
+        assert_eq!(images[5].width, Some(300));
+    }
+
+    #[test]
+    #[cfg(feature = "stopwords")]
+    fn limit_scored_nodes() {
+        let paragraph = "<p>This is a paragraph of the article and it has some words in it.</p>";
+        let html = format!(
+            r#"<html><body><div>{}</div><div id="first">{}</div><div id="second">{}</div></body></html>"#,
+            "<p></p><p>Share</p>".repeat(100),
+            paragraph.repeat(20),
+            paragraph.repeat(2000)
+        );
+        let doc = Document::from(html.as_str());
+
+        let node = ArticleTextNodeExtractor::calculate_best_node(&doc, Language::English).unwrap();
+        assert_eq!(node.attr("id"), Some("second"));
+
+        let node =
+            ArticleTextNodeExtractor::calculate_best_node_with_limit(&doc, Language::English, 20)
+                .unwrap();
+        assert_eq!(node.attr("id"), Some("first"));
+    }
+
+    #[test]
+    #[cfg(feature = "stopwords")]
+    fn extra_stopwords_affect_scoring() {
+        let html = r#"<html><body>
+            <div id="story"><p>Council votes on the new budget for schools and roads.</p></div>
+            <div id="promo"><p>Newsletter deals newsletter offers newsletter deals newsletter offers and more.</p></div>
+            </body></html>"#;
+        let doc = Document::from(html);
+        assert_eq!(
+            ArticleTextNodeExtractor::calculate_best_node(&doc, Language::English)
+                .unwrap()
+                .attr("id"),
+            Some("story")
+        );
+
+        let lang = Language::English.with_extra_stopwords(&["newsletter", "deals", "offers"]);
+        let node = ArticleTextNodeExtractor::calculate_best_node_with_stopwords(
+            &doc,
+            &lang,
+            ArticleTextNodeExtractor::DEFAULT_MAX_NODES_TO_SCORE,
+        )
+        .unwrap();
+        assert_eq!(node.attr("id"), Some("promo"));
+    }
+}
@@ -1,10 +1,15 @@
 use std::ops::Deref;
 
+use bytes::Bytes;
+use chrono::NaiveDateTime;
 use select::node::Node;
 use select::predicate::{Attr, Name, Predicate};
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
 use url::{ParseError, Url};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
 pub enum VideoProvider {
     Youtube,
     Vimeo,
@@ -25,6 +30,91 @@ impl VideoProvider {
         }
         Err(VideoProvider::Other(s.to_string()))
     }
+
+    /// The provider that hosts the `url`.
+    pub fn from_url(url: &Url) -> Option<Self> {
+        url.host_str()
+            .map(|host| match VideoProvider::from_host(host) {
+                Ok(p) => p,
+                Err(p) => p,
+            })
+    }
+}
+
+/// A video of an article.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct Video {
+    /// The url of the video or its player.
+    pub url: Url,
+    /// Preview image of the video.
+    pub thumbnail: Option<Url>,
+    /// The downloaded [`Video::thumbnail`], see
+    /// [`crate::article::ArticleContent::download_media`].
+    pub thumbnail_bytes: Option<Bytes>,
+    /// The length of the video in seconds.
+    pub duration_secs: Option<u64>,
+    /// When the video was uploaded.
+    pub upload_date: Option<NaiveDateTime>,
+    pub provider: Option<VideoProvider>,
+}
+
+impl Video {
+    pub fn new(url: Url) -> Self {
+        Self {
+            provider: VideoProvider::from_url(&url),
+            url,
+            thumbnail: None,
+            thumbnail_bytes: None,
+            duration_secs: None,
+            upload_date: None,
+        }
+    }
+}
+
+/// Parses an ISO 8601 duration like `PT1M30S` into seconds.
+///
+/// Years and months are rejected, since their length is ambiguous, fractions
+/// of a second are truncated.
+pub fn parse_iso8601_duration(s: &str) -> Option<u64> {
+    let s = s.trim();
+    let s = s.strip_prefix('P').or_else(|| s.strip_prefix('p'))?;
+    let (date, time) = match s.find(['T', 't']) {
+        Some(idx) => (&s[..idx], Some(&s[idx + 1..])),
+        None => (s, None),
+    };
+
+    fn components(s: &str, units: &[(char, f64)]) -> Option<f64> {
+        let mut secs = 0.;
+        let mut num = String::new();
+        for c in s.chars() {
+            if c.is_ascii_digit() || c == '.' || c == ',' {
+                num.push(if c == ',' { '.' } else { c });
+            } else {
+                let (_, factor) = units
+                    .iter()
+                    .find(|(unit, _)| unit.eq_ignore_ascii_case(&c))?;
+                secs += num.parse::<f64>().ok()? * factor;
+                num.clear();
+            }
+        }
+        if num.is_empty() {
+            Some(secs)
+        } else {
+            None
+        }
+    }
+
+    let mut secs = components(date, &[('W', 604_800.), ('D', 86_400.)])?;
+    if let Some(time) = time {
+        if time.is_empty() {
+            return None;
+        }
+        secs += components(time, &[('H', 3_600.), ('M', 60.), ('S', 1.)])?;
+    } else if date.is_empty() {
+        return None;
+    }
+    Some(secs as u64)
 }
 
 pub struct VideoNode<'a> {
@@ -92,3 +182,18 @@ impl<'a> Deref for VideoNode<'a> {
         &self.inner
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iso8601_duration() {
+        assert_eq!(parse_iso8601_duration("PT1M30S"), Some(90));
+        assert_eq!(parse_iso8601_duration("PT1H"), Some(3600));
+        assert_eq!(parse_iso8601_duration("P1DT2.5S"), Some(86_402));
+        assert_eq!(parse_iso8601_duration("PT"), None);
+        assert_eq!(parse_iso8601_duration("P1Y"), None);
+        assert_eq!(parse_iso8601_duration("90"), None);
+    }
+}